@@ -0,0 +1,238 @@
+use crate::config::Network;
+use crate::error::{Result, ZecDevError};
+use serde::Serialize;
+use std::fmt;
+
+/// Kind of Zcash address, as told by its encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressKind {
+    Transparent,
+    Sapling,
+    Unified,
+}
+
+impl fmt::Display for AddressKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressKind::Transparent => write!(f, "transparent"),
+            AddressKind::Sapling => write!(f, "Sapling"),
+            AddressKind::Unified => write!(f, "unified"),
+        }
+    }
+}
+
+/// Network an address was encoded for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressNetwork {
+    Mainnet,
+    Testnet,
+    Regtest,
+    /// Transparent addresses are encoded the same way on testnet and regtest
+    #[serde(rename = "testnet/regtest")]
+    TestnetOrRegtest,
+}
+
+impl AddressNetwork {
+    /// Whether a devnet on `network` can use the address
+    pub fn accepts(&self, network: Network) -> bool {
+        matches!(
+            (self, network),
+            (AddressNetwork::Testnet, Network::Testnet)
+                | (AddressNetwork::Regtest, Network::Regtest)
+                | (AddressNetwork::TestnetOrRegtest, _)
+        )
+    }
+}
+
+impl fmt::Display for AddressNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressNetwork::Mainnet => write!(f, "mainnet"),
+            AddressNetwork::Testnet => write!(f, "testnet"),
+            AddressNetwork::Regtest => write!(f, "regtest"),
+            AddressNetwork::TestnetOrRegtest => write!(f, "testnet/regtest"),
+        }
+    }
+}
+
+/// Base58check-encoded transparent addresses are always this long
+const TRANSPARENT_LEN: usize = 35;
+
+/// Characters a Sapling address has after its `1` separator
+const SAPLING_DATA_LEN: usize = 75;
+
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Checksum constants: Sapling addresses use Bech32, unified ones Bech32m
+const BECH32: u32 = 1;
+const BECH32M: u32 = 0x2bc830a3;
+
+/// Check `address` is well-formed for a devnet on `network`.
+///
+/// Transparent addresses are checked by prefix and length (their base58
+/// checksum is left to the node); Sapling and unified addresses have
+/// their Bech32 checksums verified too.
+pub fn validate(address: &str, network: Network) -> Result<AddressKind> {
+    let (kind, encoded_for) = parse(address)?;
+    if !encoded_for.accepts(network) {
+        let hint = match encoded_for {
+            AddressNetwork::Mainnet => format!(
+                "Mainnet addresses can't receive devnet funds; zecdev wallet new generates a {} address.",
+                network
+            ),
+            _ => format!("Use a {} address; zecdev wallet new generates one.", network),
+        };
+        return Err(ZecDevError::InvalidAddress(format!(
+            "'{}' is a {} {} address, but the devnet runs on {}",
+            address.trim(),
+            encoded_for,
+            kind,
+            network
+        ))
+        .with_hint(hint));
+    }
+    Ok(kind)
+}
+
+/// The kind of `address` and the network it was encoded for
+pub fn parse(address: &str) -> Result<(AddressKind, AddressNetwork)> {
+    let address = address.trim();
+    if address.is_empty() {
+        return Err(ZecDevError::InvalidAddress("address is empty".into()));
+    }
+    let invalid = |reason: &str| ZecDevError::InvalidAddress(format!("'{}' {}", address, reason));
+
+    if let Some((hrp, data)) = address.rsplit_once('1') {
+        if let Some((kind, network, checksum)) = shielded_encoding(hrp) {
+            if kind == AddressKind::Sapling && data.len() != SAPLING_DATA_LEN {
+                return Err(invalid("has the wrong length for a Sapling address"));
+            }
+            if !bech32_checksum_ok(hrp, data, checksum) {
+                return Err(invalid("has an invalid checksum (is it mistyped or truncated?)"));
+            }
+            return Ok((kind, network));
+        }
+    }
+
+    let network = match address.get(..2) {
+        Some("t1") | Some("t3") => AddressNetwork::Mainnet,
+        Some("tm") | Some("t2") => AddressNetwork::TestnetOrRegtest,
+        Some("zc") | Some("zt") => return Err(invalid("is a Sprout address, which devnets don't support")),
+        _ => return Err(unrecognized(address)),
+    };
+    if address.len() != TRANSPARENT_LEN {
+        return Err(invalid("has the wrong length for a transparent address"));
+    }
+    if !address.chars().all(is_base58) {
+        return Err(invalid("contains characters base58 doesn't use (0, O, I or l)"));
+    }
+    Ok((AddressKind::Transparent, network))
+}
+
+/// Kind, network and checksum constant of a Bech32 human-readable part
+fn shielded_encoding(hrp: &str) -> Option<(AddressKind, AddressNetwork, u32)> {
+    Some(match hrp {
+        "zs" => (AddressKind::Sapling, AddressNetwork::Mainnet, BECH32),
+        "ztestsapling" => (AddressKind::Sapling, AddressNetwork::Testnet, BECH32),
+        "zregtestsapling" => (AddressKind::Sapling, AddressNetwork::Regtest, BECH32),
+        "u" => (AddressKind::Unified, AddressNetwork::Mainnet, BECH32M),
+        "utest" => (AddressKind::Unified, AddressNetwork::Testnet, BECH32M),
+        "uregtest" => (AddressKind::Unified, AddressNetwork::Regtest, BECH32M),
+        _ => return None,
+    })
+}
+
+fn unrecognized(address: &str) -> ZecDevError {
+    ZecDevError::InvalidAddress(format!(
+        "'{}' is not a Zcash address (expected a transparent, Sapling or unified address)",
+        address
+    ))
+}
+
+fn is_base58(c: char) -> bool {
+    c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l')
+}
+
+/// BIP 173 checksum over the human-readable part and data characters
+fn bech32_checksum_ok(hrp: &str, data: &str, constant: u32) -> bool {
+    if data.len() < 6 {
+        return false;
+    }
+    let Some(data) = data
+        .chars()
+        .map(|c| BECH32_CHARSET.find(c).map(|i| i as u8))
+        .collect::<Option<Vec<u8>>>()
+    else {
+        return false;
+    };
+
+    let mut values: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|b| b & 31));
+    values.extend(data);
+    polymod(&values) == constant
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut checksum: u32 = 1;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ *value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGTEST_SAPLING: &str =
+        "zregtestsapling1quyqjzstpsxsurcszyfpx9q4zct3sxg6rvwp68slyqsjygeyy5nzw2pf9g4jctfw9ucrzyrev5f";
+    const MAINNET_SAPLING: &str = "zs1quyqjzstpsxsurcszyfpx9q4zct3sxg6rvwp68slyqsjygeyy5nzw2pf9g4jctfw9ucrznszzz6";
+    const REGTEST_UNIFIED: &str = "uregtest1qvzq2ps8pqys5zcvp58q7yq3zgf3g9gkzuvpjxsmrsw3u8eqyy3zxfp9ycnjs2f29vkz6t30xqcnyve5x5mrwwpe8ganc0f78aqyzsjrg3zspnuvw4";
+
+    #[test]
+    fn kinds_and_networks_are_recognized() {
+        assert_eq!(
+            parse("tmBsTi2xWTjUdEXnuTceL7fecEQKeWu4u6d").unwrap(),
+            (AddressKind::Transparent, AddressNetwork::TestnetOrRegtest)
+        );
+        assert_eq!(
+            parse("t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs").unwrap(),
+            (AddressKind::Transparent, AddressNetwork::Mainnet)
+        );
+        assert_eq!(parse(REGTEST_SAPLING).unwrap(), (AddressKind::Sapling, AddressNetwork::Regtest));
+        assert_eq!(parse(MAINNET_SAPLING).unwrap(), (AddressKind::Sapling, AddressNetwork::Mainnet));
+        assert_eq!(parse(REGTEST_UNIFIED).unwrap(), (AddressKind::Unified, AddressNetwork::Regtest));
+    }
+
+    #[test]
+    fn malformed_addresses_are_rejected() {
+        assert!(parse("").is_err());
+        assert!(parse("hello").is_err());
+        assert!(parse("tmBsTi2xWTjUdEXnuTceL7fecEQKeWu4u6").is_err());
+        assert!(parse("tmBsTi2xWTjUdEXnuTceL7fecEQKeWu4u60").is_err());
+        // One character changed
+        assert!(parse(&REGTEST_SAPLING.replace("quyq", "quyp")).is_err());
+        assert!(parse(&REGTEST_UNIFIED[..REGTEST_UNIFIED.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn addresses_must_match_the_devnet_network() {
+        assert_eq!(validate(REGTEST_SAPLING, Network::Regtest).unwrap(), AddressKind::Sapling);
+        assert!(validate(REGTEST_SAPLING, Network::Testnet).is_err());
+        assert!(validate(MAINNET_SAPLING, Network::Regtest).is_err());
+        assert!(validate("tmBsTi2xWTjUdEXnuTceL7fecEQKeWu4u6d", Network::Testnet).is_ok());
+
+        let e = validate("t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs", Network::Regtest).unwrap_err();
+        assert!(e.to_string().contains("is a mainnet transparent address, but the devnet runs on regtest"));
+    }
+}
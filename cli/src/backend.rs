@@ -0,0 +1,346 @@
+use crate::config::Network;
+use crate::error::{Result, ZecDevError};
+use reqwest::{Certificate, Client, ClientBuilder};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tokio::time::Duration;
+
+static TLS: OnceLock<BackendTls> = OnceLock::new();
+
+/// gRPC service implemented by both lightwalletd and Zaino
+const SERVICE: &str = "cash.z.wallet.sdk.rpc.CompactTxStreamer";
+
+/// Minimal gRPC client for the light-client backends.
+///
+/// Only unary calls are needed, so this speaks gRPC framing directly over
+/// HTTP/2 rather than pulling in a full gRPC stack.
+pub struct BackendClient {
+    client: Client,
+    url: String,
+    timeout: Duration,
+}
+
+/// What lightwalletd reports about itself and its chain in `GetLightdInfo`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LightdInfo {
+    pub version: String,
+    pub vendor: String,
+    pub taddr_support: bool,
+    /// BIP70 chain name of the node it follows: `main`, `test` or `regtest`
+    pub chain_name: String,
+    pub sapling_activation_height: u64,
+    pub consensus_branch_id: String,
+    /// Latest block it has ingested
+    pub block_height: u64,
+    pub git_commit: String,
+    pub branch: String,
+    pub build_date: String,
+    pub build_user: String,
+    /// Chain tip height according to its node, while that node syncs
+    pub estimated_height: u64,
+    pub zcashd_build: String,
+    pub zcashd_subversion: String,
+}
+
+impl LightdInfo {
+    /// Decode a `LightdInfo` protobuf message; unknown fields are skipped
+    pub fn decode(message: &[u8]) -> Result<Self> {
+        let fields = proto_fields(message)
+            .ok_or_else(|| ZecDevError::HealthCheck("GetLightdInfo returned a malformed reply".into()))?;
+
+        let mut info = Self::default();
+        for (field, value) in fields {
+            match (field, value) {
+                (1, Field::Bytes(v)) => info.version = text(v),
+                (2, Field::Bytes(v)) => info.vendor = text(v),
+                (3, Field::Varint(v)) => info.taddr_support = v != 0,
+                (4, Field::Bytes(v)) => info.chain_name = text(v),
+                (5, Field::Varint(v)) => info.sapling_activation_height = v,
+                (6, Field::Bytes(v)) => info.consensus_branch_id = text(v),
+                (7, Field::Varint(v)) => info.block_height = v,
+                (8, Field::Bytes(v)) => info.git_commit = text(v),
+                (9, Field::Bytes(v)) => info.branch = text(v),
+                (10, Field::Bytes(v)) => info.build_date = text(v),
+                (11, Field::Bytes(v)) => info.build_user = text(v),
+                (12, Field::Varint(v)) => info.estimated_height = v,
+                (13, Field::Bytes(v)) => info.zcashd_build = text(v),
+                (14, Field::Bytes(v)) => info.zcashd_subversion = text(v),
+                _ => {}
+            }
+        }
+        Ok(info)
+    }
+
+    /// Fail unless the backend follows `network`'s chain
+    pub fn check_chain(&self, backend: &str, network: Network) -> Result<()> {
+        if self.chain_name == network.chain_name() {
+            return Ok(());
+        }
+        Err(ZecDevError::BackendChainMismatch {
+            backend: backend.to_string(),
+            reported: self.chain_name.clone(),
+            expected: network,
+        })
+    }
+}
+
+/// How to dial the light-client backends.
+///
+/// Set once from `--backend-tls`/`--backend-ca` in `main` and picked up by
+/// every `BackendClient::new()` and by the URLs in `Settings`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum BackendTls {
+    /// The bundled devnet serves gRPC in plaintext
+    #[default]
+    Plaintext,
+    /// Verify against this CA, or accept any certificate when `None` (self-signed dev certs)
+    Tls { ca: Option<PathBuf> },
+}
+
+impl BackendTls {
+    pub fn from_flags(tls: bool, ca: Option<PathBuf>) -> Self {
+        if tls {
+            BackendTls::Tls { ca }
+        } else {
+            BackendTls::Plaintext
+        }
+    }
+
+    /// Make this the TLS mode for the rest of the process
+    pub fn install(self) {
+        let _ = TLS.set(self);
+    }
+
+    /// The installed mode, or plaintext if nothing was installed
+    pub fn current() -> BackendTls {
+        TLS.get().cloned().unwrap_or_default()
+    }
+
+    /// URL scheme for backend endpoints
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            BackendTls::Plaintext => "http",
+            BackendTls::Tls { .. } => "https",
+        }
+    }
+
+    fn configure(&self, builder: ClientBuilder) -> Result<ClientBuilder> {
+        match self {
+            BackendTls::Plaintext => Ok(builder),
+            BackendTls::Tls { ca: None } => Ok(builder.danger_accept_invalid_certs(true)),
+            BackendTls::Tls { ca: Some(path) } => {
+                let pem = std::fs::read(path).map_err(|e| {
+                    ZecDevError::Config(format!("could not read backend CA {}: {}", path.display(), e))
+                })?;
+                let cert = Certificate::from_pem(&pem).map_err(|e| {
+                    ZecDevError::Config(format!("backend CA {} is not a PEM certificate: {}", path.display(), e))
+                        .with_hint("Pass the CA that signed the backend's certificate, or drop --backend-ca to skip verification.")
+                })?;
+                Ok(builder.add_root_certificate(cert))
+            }
+        }
+    }
+}
+
+impl BackendClient {
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        let builder = Client::builder().http2_prior_knowledge();
+        let client = BackendTls::current().configure(builder)?.build()?;
+
+        Ok(Self {
+            client,
+            url: url.into(),
+            timeout: Duration::from_secs(5),
+        })
+    }
+
+    /// Check that `backend` is serving light-client requests.
+    ///
+    /// lightwalletd is asked for `GetLightdInfo`, which only succeeds once it
+    /// has connected to Zebra. Zaino is asked for `GetLatestBlock`, which
+    /// needs its own view of the chain to be initialised.
+    pub async fn probe(&self, backend: &str) -> Result<()> {
+        match backend {
+            "lwd" => self.unary("GetLightdInfo", &[]).await.map(|_| ()),
+            "zaino" => self.unary("GetLatestBlock", &[]).await.map(|_| ()),
+            _ => Err(ZecDevError::HealthCheck(format!("Unknown backend: {}", backend))),
+        }
+    }
+
+    /// The version the backend reports in `GetLightdInfo`
+    pub async fn version(&self) -> Result<String> {
+        let info = self.lightd_info().await?;
+        if info.version.is_empty() {
+            return Err(ZecDevError::HealthCheck("GetLightdInfo did not report a version".into()));
+        }
+        Ok(info.version)
+    }
+
+    /// The backend's `GetLightdInfo` reply
+    pub async fn lightd_info(&self) -> Result<LightdInfo> {
+        LightdInfo::decode(&self.unary("GetLightdInfo", &[]).await?)
+    }
+
+    /// Make a unary gRPC call with an already-encoded protobuf `message`
+    async fn unary(&self, method: &str, message: &[u8]) -> Result<Vec<u8>> {
+        // Length-prefixed message: 1 byte compression flag + 4 byte big-endian length
+        let mut body = Vec::with_capacity(5 + message.len());
+        body.push(0);
+        body.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        body.extend_from_slice(message);
+
+        let resp = self
+            .client
+            .post(format!("{}/{}/{}", self.url, SERVICE, method))
+            .header("content-type", "application/grpc")
+            .header("te", "trailers")
+            .body(body)
+            .timeout(self.timeout)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(ZecDevError::HealthCheck(format!(
+                "{} returned HTTP {}",
+                method,
+                resp.status()
+            )));
+        }
+
+        // Errors without a body come back as a trailers-only response
+        if let Some(status) = resp.headers().get("grpc-status") {
+            if status != "0" {
+                let message = resp
+                    .headers()
+                    .get("grpc-message")
+                    .and_then(|m| m.to_str().ok())
+                    .unwrap_or("")
+                    .to_string();
+                return Err(ZecDevError::HealthCheck(format!(
+                    "{} failed with gRPC status {:?}: {}",
+                    method, status, message
+                )));
+            }
+        }
+
+        let bytes = resp.bytes().await?;
+        if bytes.len() < 5 {
+            return Err(ZecDevError::HealthCheck(format!("{} returned no message", method)));
+        }
+
+        Ok(bytes[5..].to_vec())
+    }
+}
+
+/// A protobuf field value, as far as the replies read here need
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field<'a> {
+    Varint(u64),
+    /// Length-delimited: strings, bytes and nested messages
+    Bytes(&'a [u8]),
+    /// 32- and 64-bit fixed-width values, which nothing here reads
+    Fixed,
+}
+
+/// Every `(field number, value)` of a protobuf message, or `None` if it is malformed.
+///
+/// Just enough protobuf decoding to read fields out of a reply.
+fn proto_fields(mut message: &[u8]) -> Option<Vec<(u64, Field<'_>)>> {
+    let mut fields = Vec::new();
+    while !message.is_empty() {
+        let key = read_varint(&mut message)?;
+        let value = match key & 7 {
+            0 => Field::Varint(read_varint(&mut message)?),
+            wire @ (1 | 2 | 5) => {
+                let len = match wire {
+                    1 => 8,
+                    5 => 4,
+                    _ => read_varint(&mut message)? as usize,
+                };
+                if message.len() < len {
+                    return None;
+                }
+                let (value, rest) = message.split_at(len);
+                message = rest;
+                if wire == 2 {
+                    Field::Bytes(value)
+                } else {
+                    Field::Fixed
+                }
+            }
+            _ => return None,
+        };
+        fields.push((key >> 3, value));
+    }
+    Some(fields)
+}
+
+fn text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).to_string()
+}
+
+fn read_varint(message: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = message.split_first()?;
+        *message = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_field_is_found_past_other_fields() {
+        // vendor (2) = "ECC", taddrSupport (3) = true, version (1) = "v0.4.18"
+        let mut message = vec![0x12, 3];
+        message.extend_from_slice(b"ECC");
+        message.extend_from_slice(&[0x18, 1, 0x0a, 7]);
+        message.extend_from_slice(b"v0.4.18");
+
+        let info = LightdInfo::decode(&message).unwrap();
+        assert_eq!(info.version, "v0.4.18");
+        assert_eq!(info.vendor, "ECC");
+        assert!(info.taddr_support);
+        assert_eq!(info.chain_name, "");
+    }
+
+    #[test]
+    fn truncated_message_yields_nothing() {
+        assert_eq!(proto_fields(&[0x0a, 10, b'v']), None);
+        assert!(LightdInfo::decode(&[0x0a, 10, b'v']).is_err());
+    }
+
+    fn string_field(field: u8, value: &str) -> Vec<u8> {
+        let mut bytes = vec![field << 3 | 2, value.len() as u8];
+        bytes.extend_from_slice(value.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn lightd_info_is_decoded_and_checked_against_the_network() {
+        let mut message = string_field(1, "v0.4.17");
+        message.extend(string_field(4, "test"));
+        // saplingActivationHeight (5) = 280000, a fixed64 field nobody sent (20), blockHeight (7) = 300
+        message.extend_from_slice(&[0x28, 0xc0, 0x8b, 0x11]);
+        message.extend_from_slice(&[0xa1, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]);
+        message.extend_from_slice(&[0x38, 0xac, 0x02]);
+
+        let info = LightdInfo::decode(&message).unwrap();
+        assert_eq!(info.version, "v0.4.17");
+        assert_eq!(info.sapling_activation_height, 280_000);
+        assert_eq!(info.block_height, 300);
+        assert!(info.check_chain("lightwalletd", Network::Testnet).is_ok());
+
+        let e = info.check_chain("lightwalletd", Network::Regtest).unwrap_err();
+        assert!(e.to_string().contains("'test'"), "{}", e);
+        assert!(LightdInfo::decode(&[0x38]).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+use crate::config::Network;
+use crate::error::{Result, ZecDevError};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+
+/// Bumped when the bundle layout changes incompatibly
+pub const BUNDLE_FORMAT: u32 = 1;
+
+/// First entry of every bundle
+pub const MANIFEST: &str = "manifest.json";
+
+/// Optional last entry: Zebra's state, as `zecdev snapshot` writes it
+pub const CHAIN_SNAPSHOT: &str = "chain.tar.zst";
+
+/// What `zecdev export` put in a bundle and how the devnet was started
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: u32,
+    pub zecdev_version: String,
+    /// Unix timestamp (seconds)
+    pub created_at: u64,
+    pub project: String,
+    /// As `up` last started it; `None` if no devnet was recorded
+    pub network: Option<Network>,
+    pub backends: Vec<String>,
+    /// Bundle paths of the `--compose-override` files, in order
+    pub overrides: Vec<String>,
+    /// Bundle paths of every other entry except the manifest
+    pub files: Vec<String>,
+    /// `<file>: <key>` for each value replaced with `REDACTED`
+    pub redacted: Vec<String>,
+    pub snapshot: bool,
+}
+
+impl Manifest {
+    /// The `up` invocation that recreates the exported devnet
+    pub fn up_command(&self) -> String {
+        let mut command = String::from("zecdev");
+        for file in &self.overrides {
+            command.push_str(&format!(" --compose-override {}", file));
+        }
+        command.push_str(" up");
+        if !self.backends.is_empty() {
+            command.push_str(&format!(" --backend {}", self.backends.join(",")));
+        }
+        if let Some(network) = self.network {
+            command.push_str(&format!(" --network {}", network));
+        }
+        command
+    }
+}
+
+/// Compose as the exporting machine resolved it; kept for reference only
+pub const RESOLVED_COMPOSE: &str = "resolved-compose.yml";
+
+/// Whether `name` is a file `zecdev import` may write under the project root.
+///
+/// Bundles come from elsewhere, so anything outside the known layout, or
+/// that could climb out of it, is refused.
+pub fn is_project_file(name: &str) -> bool {
+    let nested = |dir: &str| {
+        name.strip_prefix(dir)
+            .is_some_and(|file| !file.is_empty() && !file.contains(['/', '\\']) && !file.starts_with('.'))
+    };
+    matches!(name, "docker-compose.yml" | "zeckit.toml") || nested("overrides/") || nested("docker/configs/")
+}
+
+/// Keys whose values never leave the machine unless asked to
+const SECRET_KEYS: &[&str] = &["password", "passwd", "secret", "token", "api_key", "apikey"];
+
+pub const REDACTED: &str = "REDACTED";
+
+/// Replace the values of secret-looking keys in a config file.
+///
+/// Understands `key=value` (zcash.conf, env files, compose list items),
+/// `key = value` (TOML) and `key: value` (YAML). Returns the redacted text
+/// and the keys that were hit.
+pub fn redact(contents: &str) -> (String, Vec<String>) {
+    let mut keys = Vec::new();
+    let mut out = String::with_capacity(contents.len());
+
+    for line in contents.split_inclusive('\n') {
+        let (body, newline) = match line.strip_suffix('\n') {
+            Some(body) => (body, "\n"),
+            None => (line, ""),
+        };
+        match redact_line(body) {
+            Some((redacted, key)) => {
+                out.push_str(&redacted);
+                keys.push(key);
+            }
+            None => out.push_str(body),
+        }
+        out.push_str(newline);
+    }
+    (out, keys)
+}
+
+fn redact_line(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') {
+        return None;
+    }
+
+    let split = line.find(['=', ':'])?;
+    let (head, rest) = line.split_at(split);
+    let key = head.trim().trim_start_matches("- ").trim().trim_matches('"');
+    let value = rest[1..].trim();
+    let lowered = key.to_ascii_lowercase();
+    if value.is_empty() || !SECRET_KEYS.iter().any(|secret| lowered.contains(secret)) {
+        return None;
+    }
+
+    let quote = match value.chars().next() {
+        Some(q @ ('"' | '\'')) => q.to_string(),
+        _ => String::new(),
+    };
+    let spacing = &rest[1..rest.len() - rest[1..].trim_start().len()];
+    Some((
+        format!("{}{}{}{}{}{}", head, &rest[..1], spacing, quote, REDACTED, quote),
+        key.to_string(),
+    ))
+}
+
+/// Writes entries as a plain ustar archive
+pub struct TarWriter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> TarWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    pub fn add(&mut self, name: &str, contents: &[u8]) -> Result<()> {
+        self.add_reader(name, contents.len() as u64, &mut &contents[..])
+    }
+
+    /// Add an entry of `size` bytes read from `reader`
+    pub fn add_reader(&mut self, name: &str, size: u64, reader: &mut impl Read) -> Result<()> {
+        self.out.write_all(&header(name, size)?)?;
+        let copied = io::copy(&mut reader.take(size), &mut self.out)?;
+        if copied != size {
+            return Err(ZecDevError::Bundle(format!("{} shrank while being archived", name)));
+        }
+        self.out.write_all(&vec![0; padding(size)])?;
+        Ok(())
+    }
+
+    /// Write the end-of-archive marker and hand back the output
+    pub fn finish(mut self) -> Result<W> {
+        self.out.write_all(&[0; 1024])?;
+        Ok(self.out)
+    }
+}
+
+/// Reads the entries `TarWriter` wrote, one at a time.
+///
+/// After `next_entry`, the reader yields that entry's contents.
+pub struct TarReader<R: Read> {
+    inner: R,
+    remaining: u64,
+    padding: usize,
+}
+
+impl<R: Read> TarReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, remaining: 0, padding: 0 }
+    }
+
+    /// Move to the next entry, skipping whatever of the current one is unread
+    pub fn next_entry(&mut self) -> Result<Option<(String, u64)>> {
+        io::copy(&mut (&mut self.inner).take(self.remaining + self.padding as u64), &mut io::sink())?;
+
+        let mut block = [0u8; 512];
+        self.inner.read_exact(&mut block)?;
+        if block.iter().all(|b| *b == 0) {
+            return Ok(None);
+        }
+
+        let (name, size) = parse_header(&block)?;
+        self.remaining = size;
+        self.padding = padding(size);
+        Ok(Some((name, size)))
+    }
+
+    /// The rest of the current entry
+    pub fn read_entry(&mut self) -> Result<Vec<u8>> {
+        let mut contents = Vec::new();
+        self.read_to_end(&mut contents)?;
+        Ok(contents)
+    }
+}
+
+impl<R: Read> Read for TarReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let max = buf.len().min(self.remaining as usize);
+        if max == 0 {
+            return Ok(0);
+        }
+        let n = self.inner.read(&mut buf[..max])?;
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+fn padding(size: u64) -> usize {
+    ((512 - size % 512) % 512) as usize
+}
+
+fn header(name: &str, size: u64) -> Result<[u8; 512]> {
+    if name.len() > 99 || !name.is_ascii() {
+        return Err(ZecDevError::Bundle(format!("cannot archive '{}': name too long", name)));
+    }
+
+    let mut block = [0u8; 512];
+    block[..name.len()].copy_from_slice(name.as_bytes());
+    write_octal(&mut block[100..108], 0o644);
+    write_octal(&mut block[108..116], 0);
+    write_octal(&mut block[116..124], 0);
+    write_octal(&mut block[124..136], size);
+    write_octal(&mut block[136..148], 0);
+    block[156] = b'0';
+    block[257..263].copy_from_slice(b"ustar\0");
+    block[263..265].copy_from_slice(b"00");
+
+    // The checksum is computed with its own field read as spaces
+    block[148..156].copy_from_slice(b"        ");
+    let sum: u32 = block.iter().map(|b| *b as u32).sum();
+    write_octal(&mut block[148..155], sum as u64);
+    Ok(block)
+}
+
+fn parse_header(block: &[u8; 512]) -> Result<(String, u64)> {
+    let corrupt = || ZecDevError::Bundle("bundle is corrupt (bad archive header)".into());
+
+    let mut unsummed = *block;
+    unsummed[148..156].copy_from_slice(b"        ");
+    let sum: u64 = unsummed.iter().map(|b| *b as u64).sum();
+    if read_octal(&block[148..156]) != Some(sum) {
+        return Err(corrupt());
+    }
+
+    let end = block[..100].iter().position(|b| *b == 0).unwrap_or(100);
+    let name = std::str::from_utf8(&block[..end]).map_err(|_| corrupt())?.to_string();
+    let size = read_octal(&block[124..136]).ok_or_else(corrupt)?;
+    Ok((name, size))
+}
+
+/// Zero-padded octal, NUL-terminated, filling `field`
+fn write_octal(field: &mut [u8], value: u64) {
+    let digits = format!("{:0width$o}", value, width = field.len() - 1);
+    field[..digits.len()].copy_from_slice(digits.as_bytes());
+    field[digits.len()] = 0;
+}
+
+fn read_octal(field: &[u8]) -> Option<u64> {
+    let text = std::str::from_utf8(field).ok()?;
+    let text = text.trim_matches(|c: char| c == '\0' || c == ' ');
+    u64::from_str_radix(text, 8).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archives_round_trip() {
+        let mut writer = TarWriter::new(Vec::new());
+        writer.add("manifest.json", b"{}").unwrap();
+        writer.add("docker/configs/zebra.toml", &[b'x'; 1000]).unwrap();
+        writer.add("empty", b"").unwrap();
+        let archive = writer.finish().unwrap();
+        assert_eq!(archive.len() % 512, 0);
+
+        let mut reader = TarReader::new(&archive[..]);
+        assert_eq!(reader.next_entry().unwrap(), Some(("manifest.json".into(), 2)));
+        // Skipped without being read
+        assert_eq!(reader.next_entry().unwrap(), Some(("docker/configs/zebra.toml".into(), 1000)));
+        assert_eq!(reader.next_entry().unwrap(), Some(("empty".into(), 0)));
+        assert_eq!(reader.read_entry().unwrap(), b"");
+        assert_eq!(reader.next_entry().unwrap(), None);
+
+        let mut reader = TarReader::new(&archive[..]);
+        reader.next_entry().unwrap();
+        reader.next_entry().unwrap();
+        assert_eq!(reader.read_entry().unwrap(), vec![b'x'; 1000]);
+    }
+
+    #[test]
+    fn corrupt_headers_are_rejected() {
+        let mut archive = TarWriter::new(Vec::new());
+        archive.add("a", b"1").unwrap();
+        let mut archive = archive.finish().unwrap();
+        archive[0] = b'b';
+
+        assert!(TarReader::new(&archive[..]).next_entry().is_err());
+    }
+
+    #[test]
+    fn secrets_are_redacted_in_each_config_syntax() {
+        let conf = "# RPC settings\nrpcuser=zeckit\nrpcpassword=hunter2\n";
+        let (redacted, keys) = redact(conf);
+        assert_eq!(redacted, "# RPC settings\nrpcuser=zeckit\nrpcpassword=REDACTED\n");
+        assert_eq!(keys, vec!["rpcpassword"]);
+
+        let (redacted, _) = redact("[rpc]\napi_token = \"abc\"\nlisten_addr = \"0.0.0.0:8232\"");
+        assert_eq!(redacted, "[rpc]\napi_token = \"REDACTED\"\nlisten_addr = \"0.0.0.0:8232\"");
+
+        let (redacted, keys) = redact("    environment:\n      - FAUCET_SECRET=s3cr3t\n      DB_PASSWORD: pw\n");
+        assert_eq!(redacted, "    environment:\n      - FAUCET_SECRET=REDACTED\n      DB_PASSWORD: REDACTED\n");
+        assert_eq!(keys, vec!["FAUCET_SECRET", "DB_PASSWORD"]);
+    }
+
+    #[test]
+    fn only_known_project_files_are_imported() {
+        assert!(is_project_file("docker-compose.yml"));
+        assert!(is_project_file("docker/configs/zebra.toml"));
+        assert!(is_project_file("overrides/extra.yml"));
+        assert!(!is_project_file("overrides/"));
+        assert!(!is_project_file("overrides/../../.bashrc"));
+        assert!(!is_project_file("docker/configs/sub/zebra.toml"));
+        assert!(!is_project_file("/etc/passwd"));
+        assert!(!is_project_file(RESOLVED_COMPOSE));
+    }
+
+    #[test]
+    fn up_command_recreates_the_devnet() {
+        let manifest = Manifest {
+            format: BUNDLE_FORMAT,
+            zecdev_version: "0.1.0".into(),
+            created_at: 0,
+            project: "zeckit".into(),
+            network: Some(Network::Regtest),
+            backends: vec!["lwd".into()],
+            overrides: vec!["overrides/extra.yml".into()],
+            files: Vec::new(),
+            redacted: Vec::new(),
+            snapshot: false,
+        };
+        assert_eq!(
+            manifest.up_command(),
+            "zecdev --compose-override overrides/extra.yml up --backend lwd --network regtest"
+        );
+    }
+}
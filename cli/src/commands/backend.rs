@@ -0,0 +1,47 @@
+use crate::backend::BackendClient;
+use crate::commands::up::Backend;
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::state::DevnetState;
+use colored::*;
+
+pub async fn info(backend: Backend, json: bool) -> Result<()> {
+    let name = backend.name().ok_or_else(|| {
+        ZecDevError::InvalidArgument("backend info needs a backend: lwd or zaino".into())
+    })?;
+    let project = DockerCompose::new()?.project_name()?;
+    let settings = DevnetState::settings_for(&project)?;
+    let info = BackendClient::new(settings.backend_url_for(name))?.lightd_info().await?;
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+        return Ok(());
+    }
+    
+    println!("  {} {} {}", "Version:".bold(), info.vendor, info.version);
+    println!("  {} {}", "Chain:".bold(), info.chain_name);
+    println!("  {} {}", "Block height:".bold(), info.block_height);
+    if info.estimated_height > info.block_height {
+        println!("  {} {}", "Estimated height:".bold(), info.estimated_height);
+    }
+    println!("  {} {}", "Sapling activation:".bold(), info.sapling_activation_height);
+    println!("  {} {}", "Consensus branch:".bold(), info.consensus_branch_id);
+    println!("  {} {}", "Transparent support:".bold(), if info.taddr_support { "yes" } else { "no" });
+    if !info.git_commit.is_empty() {
+        println!("  {} {} ({})", "Build:".bold(), info.git_commit, info.branch);
+    }
+    if !info.build_date.is_empty() {
+        println!("  {} {} by {}", "Built:".bold(), info.build_date, info.build_user);
+    }
+    if !info.zcashd_subversion.is_empty() {
+        println!("  {} {} {}", "Node:".bold(), info.zcashd_subversion, info.zcashd_build);
+    }
+    
+    // Flag a backend following another chain rather than failing, since this is a diagnostic
+    if let Err(e) = info.check_chain(name, DevnetState::network_for(&project)?) {
+        println!();
+        println!("  {} {}", "⚠".yellow(), e);
+    }
+    
+    Ok(())
+}
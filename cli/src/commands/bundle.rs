@@ -0,0 +1,286 @@
+use crate::bundle::{
+    is_project_file, redact, Manifest, TarReader, TarWriter, BUNDLE_FORMAT, CHAIN_SNAPSHOT, MANIFEST, REDACTED,
+    RESOLVED_COMPOSE,
+};
+use crate::commands::snapshot::{load_chain, min_free_disk, save_chain};
+use crate::config::file::CONFIG_FILE;
+use crate::docker::compose::DockerCompose;
+use crate::docker::options::ComposeOptions;
+use crate::error::{Result, ZecDevError};
+use crate::preflight::{check_disk, check_disk_at};
+use crate::state::DevnetState;
+use crate::utils::{format_bytes, print_banner, project_root, zeckit_home};
+use colored::*;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// zstd level for the bundle itself; its entries are small text files
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Package the devnet's definition, and optionally its chain, into `path`
+pub async fn export(path: PathBuf, with_snapshot: bool, include_secrets: bool) -> Result<()> {
+    print_banner("ZecKit - Exporting Devnet");
+    
+    let compose = DockerCompose::new()?;
+    let project = compose.project_name()?;
+    let state = DevnetState::load(&project)?;
+    
+    let mut files = project_files()?;
+    files.push((RESOLVED_COMPOSE.to_string(), compose.resolved_config()?.into_bytes()));
+    
+    let mut redacted = Vec::new();
+    if !include_secrets {
+        for (name, contents) in files.iter_mut() {
+            // Binary files have no key/value lines to redact
+            let Ok(text) = std::str::from_utf8(contents) else {
+                continue;
+            };
+            let (text, keys) = redact(text);
+            redacted.extend(keys.into_iter().map(|key| format!("{}: {}", name, key)));
+            *contents = text.into_bytes();
+        }
+    }
+    
+    // The chain goes in last, so it is saved first to learn its size
+    let chain = if with_snapshot {
+        let dir = zeckit_home()?.join("tmp");
+        fs::create_dir_all(&dir)?;
+        check_disk_at(&dir, min_free_disk(&compose)?)?;
+        let chain = dir.join(format!("export-{}.tar.zst", std::process::id()));
+        save_chain(&compose, &chain, "Saving chain state...".to_string())?;
+        Some(chain)
+    } else {
+        None
+    };
+    
+    let options = ComposeOptions::current();
+    let manifest = Manifest {
+        format: BUNDLE_FORMAT,
+        zecdev_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+        project,
+        network: state.as_ref().map(|s| s.network),
+        backends: state.map(|s| s.backends).unwrap_or_default(),
+        overrides: options.overrides.iter().filter_map(|p| override_name(p)).collect(),
+        files: files.iter().map(|(name, _)| name.clone()).collect(),
+        redacted,
+        snapshot: chain.is_some(),
+    };
+    
+    let result = write_bundle(&path, &manifest, &files, chain.as_deref());
+    if let Some(chain) = &chain {
+        let _ = fs::remove_file(chain);
+    }
+    if let Err(e) = result {
+        // Don't leave a truncated bundle behind
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+    
+    println!("{} Exported devnet to {}", "✓".green(), path.display());
+    println!("  {} {}", "Size:".bold(), format_bytes(fs::metadata(&path)?.len()));
+    println!("  {} {}", "Files:".bold(), manifest.files.join(", "));
+    if manifest.snapshot {
+        println!("  {} {}", "Chain:".bold(), CHAIN_SNAPSHOT);
+    }
+    if !manifest.redacted.is_empty() {
+        println!();
+        println!(
+            "{} Replaced {} secret(s) with {}: {}",
+            "⚠".yellow(),
+            manifest.redacted.len(),
+            REDACTED,
+            manifest.redacted.join(", ")
+        );
+        println!("  Pass --include-secrets to keep them.");
+    }
+    
+    println!();
+    Ok(())
+}
+
+/// Unpack a bundle from `zecdev export` into the project, restoring its chain if it has one
+pub async fn import(path: PathBuf, force: bool) -> Result<()> {
+    print_banner("ZecKit - Importing Devnet");
+    
+    let file = File::open(&path).map_err(|e| {
+        ZecDevError::Io(e).with_hint(format!("Check the path to the bundle: {}", path.display()))
+    })?;
+    let mut reader = TarReader::new(zstd::stream::read::Decoder::new(BufReader::new(file))?);
+    let manifest = read_manifest(&mut reader)?;
+    
+    // Only the chain needs Docker; check it can be restored before writing anything
+    let compose = if manifest.snapshot {
+        let compose = DockerCompose::new()?;
+        if compose.is_running() {
+            return Err(ZecDevError::Bundle("the devnet is running".into())
+                .with_hint("Stop it with zecdev down, then import again."));
+        }
+        check_disk(min_free_disk(&compose)?)?;
+        Some(compose)
+    } else {
+        None
+    };
+    
+    let root = project_root()?;
+    let mut files = Vec::new();
+    let mut has_chain = false;
+    while let Some((name, _)) = reader.next_entry()? {
+        if name == CHAIN_SNAPSHOT {
+            has_chain = true;
+            break;
+        }
+        if name == RESOLVED_COMPOSE {
+            continue;
+        }
+        if !is_project_file(&name) {
+            return Err(ZecDevError::Bundle(format!("unexpected file '{}' in bundle", name))
+                .with_hint("Only bundles made by zecdev export can be imported."));
+        }
+        files.push((name, reader.read_entry()?));
+    }
+    
+    // Refuse before writing anything, so a conflict leaves the project untouched
+    let conflicts: Vec<&str> = files
+        .iter()
+        .filter(|(name, contents)| fs::read(root.join(name)).is_ok_and(|current| current != *contents))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !conflicts.is_empty() && !force {
+        return Err(ZecDevError::Bundle(format!(
+            "the bundle would overwrite local changes to {}",
+            conflicts.join(", ")
+        ))
+        .with_hint("Pass --force to overwrite them."));
+    }
+    
+    for (name, contents) in &files {
+        let target = root.join(name);
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&target, contents)?;
+        println!("{} {}", "✓".green(), name);
+    }
+    
+    if let Some(compose) = &compose {
+        if !has_chain {
+            return Err(ZecDevError::Bundle(format!("bundle is missing its {}", CHAIN_SNAPSHOT)));
+        }
+        let mut decoder = zstd::stream::read::Decoder::new(&mut reader)?;
+        load_chain(compose, &mut decoder, "Restoring chain state...".to_string())?;
+        println!("{} Chain state restored", "✓".green());
+    }
+    
+    if !manifest.redacted.is_empty() {
+        println!();
+        println!("{} These values were redacted on export; set them before starting:", "⚠".yellow());
+        for key in &manifest.redacted {
+            println!("  {}", key);
+        }
+    }
+    
+    println!();
+    println!("Start the imported devnet with:");
+    println!("  {}", manifest.up_command().cyan());
+    println!();
+    Ok(())
+}
+
+/// The bundle's manifest, which `export` always writes first
+fn read_manifest<R: Read>(reader: &mut TarReader<R>) -> Result<Manifest> {
+    let not_a_bundle = || {
+        ZecDevError::Bundle(format!("not a zecdev bundle (no {})", MANIFEST))
+            .with_hint("Create one with zecdev export <BUNDLE>.")
+    };
+    match reader.next_entry()? {
+        Some((name, _)) if name == MANIFEST => {}
+        _ => return Err(not_a_bundle()),
+    }
+    
+    let manifest: Manifest = serde_json::from_slice(&reader.read_entry()?)?;
+    if manifest.format != BUNDLE_FORMAT {
+        return Err(ZecDevError::Bundle(format!(
+            "bundle format {} is not supported (expected {})",
+            manifest.format, BUNDLE_FORMAT
+        ))
+        .with_hint(format!("It was made by zecdev {}; import it with that version.", manifest.zecdev_version)));
+    }
+    Ok(manifest)
+}
+
+/// The devnet's definition as `(bundle path, contents)` pairs.
+///
+/// The base compose file is stored as docker-compose.yml even when `--file`
+/// chose another one, so `up` finds it after import without flags.
+fn project_files() -> Result<Vec<(String, Vec<u8>)>> {
+    let root = project_root()?;
+    let options = ComposeOptions::current();
+    let mut files = Vec::new();
+    
+    let base = options.file.clone().unwrap_or_else(|| root.join("docker-compose.yml"));
+    files.push(("docker-compose.yml".to_string(), fs::read(&base)?));
+    
+    for path in &options.overrides {
+        let name = override_name(path).ok_or_else(|| {
+            ZecDevError::Bundle(format!("cannot bundle override file {}", path.display()))
+        })?;
+        if files.iter().any(|(existing, _)| *existing == name) {
+            return Err(ZecDevError::Bundle(format!("two override files are named {}", name))
+                .with_hint("Rename one of them so both fit in the bundle."));
+        }
+        files.push((name, fs::read(path)?));
+    }
+    
+    let configs = root.join("docker").join("configs");
+    if configs.is_dir() {
+        let mut entries: Vec<PathBuf> = fs::read_dir(&configs)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file())
+            .collect();
+        entries.sort();
+        for path in entries {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                let name = format!("docker/configs/{}", name);
+                if is_project_file(&name) {
+                    files.push((name, fs::read(&path)?));
+                }
+            }
+        }
+    }
+    
+    let config = root.join(CONFIG_FILE);
+    if config.is_file() {
+        files.push((CONFIG_FILE.to_string(), fs::read(&config)?));
+    }
+    
+    Ok(files)
+}
+
+/// Where an override file goes in the bundle: `overrides/<file name>`
+fn override_name(path: &Path) -> Option<String> {
+    let name = format!("overrides/{}", path.file_name()?.to_str()?);
+    is_project_file(&name).then_some(name)
+}
+
+fn write_bundle(path: &Path, manifest: &Manifest, files: &[(String, Vec<u8>)], chain: Option<&Path>) -> Result<()> {
+    let file = BufWriter::new(File::create(path)?);
+    let mut bundle = TarWriter::new(zstd::stream::write::Encoder::new(file, COMPRESSION_LEVEL)?);
+    
+    bundle.add(MANIFEST, &serde_json::to_vec_pretty(manifest)?)?;
+    for (name, contents) in files {
+        bundle.add(name, contents)?;
+    }
+    if let Some(chain) = chain {
+        let size = fs::metadata(chain)?.len();
+        bundle.add_reader(CHAIN_SNAPSHOT, size, &mut BufReader::new(File::open(chain)?))?;
+    }
+    
+    bundle.finish()?.finish()?.flush()?;
+    Ok(())
+}
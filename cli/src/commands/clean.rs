@@ -0,0 +1,43 @@
+use crate::docker::compose::DockerCompose;
+use crate::error::Result;
+use crate::lock::lock_project;
+use crate::utils::{format_bytes, print_banner};
+use colored::*;
+
+pub async fn execute(force: bool) -> Result<()> {
+    print_banner("ZecKit - Clean");
+    
+    let compose = DockerCompose::new()?;
+    let _lock = lock_project(&compose, "clean", force, "--force")?;
+    let dry_run = compose.is_dry_run();
+    let stale = compose.stale_resources()?;
+    
+    if stale.is_empty() {
+        println!("{} Nothing to clean", "✓".green());
+        return Ok(());
+    }
+    
+    let verb = if dry_run { "Would remove" } else { "Removing" };
+    for (kind, names) in [
+        ("container", &stale.containers),
+        ("volume", &stale.volumes),
+        ("network", &stale.networks),
+    ] {
+        for name in names {
+            println!("  {} {} {}", verb, kind, name.bold());
+        }
+    }
+    println!();
+    
+    // In a dry run this prints the prune commands instead
+    let reclaimed = compose.prune()?;
+    if dry_run {
+        println!();
+        println!("{}", "Dry run - nothing was removed. Run without --dry-run to clean up.".yellow());
+        return Ok(());
+    }
+    
+    println!("{} Reclaimed {}", "✓".green(), format_bytes(reclaimed));
+    
+    Ok(())
+}
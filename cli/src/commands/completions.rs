@@ -0,0 +1,40 @@
+use crate::services::ServiceSet;
+use crate::error::Result;
+use crate::Cli;
+use clap::builder::{PossibleValue, TypedValueParser};
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::ffi::OsStr;
+use std::io;
+
+/// Print a completion script for `shell` to stdout.
+///
+/// Install it with e.g. `zecdev completions bash > /etc/bash_completion.d/zecdev`.
+pub async fn execute(shell: Shell) -> Result<()> {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut io::stdout());
+    Ok(())
+}
+
+/// Accepts any service name but offers the built-in ones for completion,
+/// so services added through compose overrides still work.
+#[derive(Clone)]
+pub struct ServiceNameParser;
+
+impl TypedValueParser for ServiceNameParser {
+    type Value = String;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> std::result::Result<Self::Value, clap::Error> {
+        clap::builder::StringValueParser::new().parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(ServiceSet::ALL.iter().map(|s| PossibleValue::new(s.name))))
+    }
+}
@@ -0,0 +1,173 @@
+use crate::commands::up::{selected_backends, Backend};
+use crate::config::settings::Settings;
+use crate::config::{Config, Network};
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::rpc::RpcAuth;
+use crate::state::DevnetState;
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Tool whose configuration `connect-config` writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConnectFormat {
+    /// zcash.conf lines for zcash-cli, pointing at Zebra's RPC
+    ZcashCli,
+    /// zingo-cli arguments, pointing at the light-client backend
+    Zingo,
+    /// Every endpoint as JSON, for tools with their own format
+    Json,
+}
+
+/// Endpoints as written by `--format json`
+#[derive(Debug, Serialize)]
+struct Connection<'a> {
+    network: Network,
+    zebra_rpc_url: &'a str,
+    faucet_url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lightwalletd_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    zaino_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rpc_user: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rpc_password: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rpc_cookie: Option<String>,
+}
+
+pub async fn execute(format: ConnectFormat, backends: Vec<Backend>) -> Result<()> {
+    // Without an explicit backend, describe what `up` started
+    let state = DevnetState::load(&DockerCompose::new()?.project_name()?)?;
+    let backends = if backends.is_empty() {
+        state.as_ref().ok_or(ZecDevError::DevnetNotFound)?.backend_names()
+    } else {
+        selected_backends(&backends)
+    };
+    let (network, settings) = match &state {
+        Some(state) => (state.network, state.settings.clone()),
+        None => {
+            let config = Config::load()?;
+            (config.resolve_network(None), Settings::from_config(&config))
+        }
+    };
+
+    print!("{}", render(format, network, &backends, &settings, &RpcAuth::current())?);
+    Ok(())
+}
+
+/// The snippet for `format`, connecting to the devnet described by the rest
+pub fn render(
+    format: ConnectFormat,
+    network: Network,
+    backends: &[&str],
+    settings: &Settings,
+    auth: &RpcAuth,
+) -> Result<String> {
+    match format {
+        ConnectFormat::ZcashCli => zcash_conf(network, settings, auth),
+        ConnectFormat::Zingo => zingo_args(network, backends, settings),
+        ConnectFormat::Json => {
+            let (rpc_user, rpc_password, rpc_cookie) = match auth {
+                RpcAuth::None => (None, None, None),
+                RpcAuth::Basic { user, password } => (Some(user.as_str()), Some(password.as_str()), None),
+                RpcAuth::Cookie(path) => (None, None, Some(path.display().to_string())),
+            };
+            let connection = Connection {
+                network,
+                zebra_rpc_url: &settings.zebra_rpc_url,
+                faucet_url: &settings.faucet_api_url,
+                lightwalletd_url: backends.contains(&"lwd").then_some(settings.backend_url.as_str()),
+                zaino_url: backends.contains(&"zaino").then_some(settings.zaino_url.as_str()),
+                rpc_user,
+                rpc_password,
+                rpc_cookie,
+            };
+            Ok(format!("{}\n", serde_json::to_string_pretty(&connection)?))
+        }
+    }
+}
+
+/// zcash.conf for zcash-cli talking to Zebra's RPC
+fn zcash_conf(network: Network, settings: &Settings, auth: &RpcAuth) -> Result<String> {
+    let url = reqwest::Url::parse(&settings.zebra_rpc_url)
+        .map_err(|e| ZecDevError::Config(format!("Zebra RPC URL {}: {}", settings.zebra_rpc_url, e)))?;
+    let host = url.host_str().unwrap_or("127.0.0.1");
+    let port = url.port_or_known_default().unwrap_or_default();
+
+    let mut conf = format!("# zcash-cli -conf=<this file> for the zecdev {} devnet\n", network);
+    conf.push_str(match network {
+        Network::Regtest => "regtest=1\n",
+        Network::Testnet => "testnet=1\n",
+    });
+    conf.push_str(&format!("rpcconnect={}\nrpcport={}\n", host, port));
+    match auth {
+        RpcAuth::None => {}
+        RpcAuth::Basic { user, password } => conf.push_str(&format!("rpcuser={}\nrpcpassword={}\n", user, password)),
+        RpcAuth::Cookie(path) => conf.push_str(&format!("rpccookiefile={}\n", path.display())),
+    }
+    Ok(conf)
+}
+
+/// zingo-cli arguments for the first backend; zingo only speaks the light-client protocol
+fn zingo_args(network: Network, backends: &[&str], settings: &Settings) -> Result<String> {
+    let backend = backends.first().ok_or_else(|| {
+        ZecDevError::InvalidArgument("zingo needs a light-client backend, and none is running".into())
+            .with_hint("Start one with zecdev up --backend lwd (or zaino), or pass --backend.")
+    })?;
+    Ok(format!(
+        "# zingo-cli arguments for the zecdev {} devnet\n--chain {} --server {}\n",
+        network,
+        network,
+        settings.backend_url_for(backend)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use std::path::PathBuf;
+
+    fn settings() -> Settings {
+        let config: Config = toml::from_str("zebra_rpc_port = 18232\nbackend_port = 19067").unwrap();
+        Settings::from_config(&config)
+    }
+
+    #[test]
+    fn zcash_cli_gets_the_rpc_port_network_and_auth() {
+        let settings = settings();
+        let conf = render(ConnectFormat::ZcashCli, Network::Testnet, &[], &settings, &RpcAuth::None).unwrap();
+        assert!(conf.contains("testnet=1\nrpcconnect=127.0.0.1\nrpcport=18232\n"));
+        assert!(!conf.contains("rpcuser"));
+
+        let auth = RpcAuth::Basic { user: "dev".into(), password: "secret".into() };
+        let conf = render(ConnectFormat::ZcashCli, Network::Regtest, &[], &settings, &auth).unwrap();
+        assert!(conf.contains("regtest=1\n"));
+        assert!(conf.ends_with("rpcuser=dev\nrpcpassword=secret\n"));
+
+        let auth = RpcAuth::Cookie(PathBuf::from("/tmp/.cookie"));
+        let conf = render(ConnectFormat::ZcashCli, Network::Regtest, &[], &settings, &auth).unwrap();
+        assert!(conf.ends_with("rpccookiefile=/tmp/.cookie\n"));
+    }
+
+    #[test]
+    fn zingo_needs_a_backend() {
+        let settings = settings();
+        let args = render(ConnectFormat::Zingo, Network::Regtest, &["lwd"], &settings, &RpcAuth::None).unwrap();
+        assert!(args.ends_with("--chain regtest --server http://127.0.0.1:19067\n"));
+        assert!(render(ConnectFormat::Zingo, Network::Regtest, &[], &settings, &RpcAuth::None).is_err());
+    }
+
+    #[test]
+    fn json_lists_only_the_running_backends() {
+        let json = render(ConnectFormat::Json, Network::Regtest, &["zaino"], &settings(), &RpcAuth::None).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["network"], "regtest");
+        assert_eq!(value["zebra_rpc_url"], "http://127.0.0.1:18232");
+        assert_eq!(value["zaino_url"], "http://127.0.0.1:9068");
+        assert!(value.get("lightwalletd_url").is_none());
+        assert!(value.get("rpc_user").is_none());
+    }
+}
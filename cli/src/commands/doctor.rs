@@ -0,0 +1,131 @@
+use crate::config::{Config, Network};
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::preflight::{check_ports, free_disk_space};
+use crate::services::ServiceSet;
+use crate::docker::runtime::{self, ContainerRuntime};
+use crate::utils::{format_bytes, print_banner};
+use colored::*;
+
+pub async fn execute() -> Result<()> {
+    print_banner("ZecKit - Environment Check");
+    
+    let mut failed = 0;
+    
+    // Container runtime CLI
+    let runtime = match runtime::detect() {
+        Ok(runtime) => {
+            let version = command_output(runtime.as_ref(), &["--version"]).unwrap_or_default();
+            pass(&format!("{} installed", runtime.display_name()), &version);
+            Some(runtime)
+        }
+        Err(e) => {
+            fail("Container runtime", &e.to_string());
+            failed += 1;
+            None
+        }
+    };
+    
+    // Daemon
+    let running = runtime.as_ref().is_some_and(|r| r.is_running());
+    if let Some(runtime) = &runtime {
+        let check = format!("{} running", runtime.display_name());
+        if running {
+            pass(&check, "daemon reachable");
+        } else {
+            fail(&check, &format!("start {} and retry", runtime.display_name()));
+            failed += 1;
+        }
+        
+        // Compose support
+        let check = format!("{} Compose", runtime.display_name());
+        if runtime.has_compose() {
+            let version =
+                command_output(runtime.as_ref(), &["compose", "version", "--short"]).unwrap_or_default();
+            pass(&check, &format!("v{}", version.trim_start_matches('v')));
+        } else {
+            fail(&check, &format!("the `{} compose` command is required", runtime.program()));
+            failed += 1;
+        }
+    }
+    
+    // Ports (our own running containers are allowed to hold theirs)
+    let config = Config::load()?;
+    let active = if running {
+        DockerCompose::new()?.running_services()?
+    } else {
+        Vec::new()
+    };
+    let mut port_issues = Vec::new();
+    for service in ServiceSet::ALL.iter().filter(|s| !active.iter().any(|a| a == s.name)) {
+        for port in service.ports(&config) {
+            if let Err(e) = check_ports(&[(port, service.name)]) {
+                if !port_issues.contains(&e.to_string()) {
+                    port_issues.push(e.to_string());
+                }
+            }
+        }
+    }
+    if port_issues.is_empty() {
+        pass("Ports free", "all devnet ports available");
+    } else {
+        for issue in &port_issues {
+            fail("Ports free", issue);
+        }
+        failed += 1;
+    }
+    
+    // Disk space where the runtime keeps its volumes
+    let min_free = config.min_free_disk(Network::Regtest);
+    let docker_root = match &runtime {
+        Some(runtime) if running => runtime.storage_root(),
+        _ => None,
+    };
+    match docker_root.as_deref().and_then(free_disk_space) {
+        Some(free) if free >= min_free => {
+            pass("Disk space", &format!("{} free", format_bytes(free)));
+        }
+        Some(free) => {
+            fail(
+                "Disk space",
+                &format!("{} free, need at least {}", format_bytes(free), format_bytes(min_free)),
+            );
+            failed += 1;
+        }
+        None => {
+            // Docker Desktop keeps volumes inside a VM we can't inspect
+            warn("Disk space", "could not determine free space for Docker volumes");
+        }
+    }
+    
+    println!();
+    
+    if failed > 0 {
+        return Err(ZecDevError::HealthCheck(format!("{} check(s) failed", failed)));
+    }
+    
+    println!("{}", "✓ Environment looks good".green().bold());
+    println!();
+    Ok(())
+}
+
+fn pass(check: &str, detail: &str) {
+    println!("  {} {} - {}", "✓".green(), check.bold(), detail);
+}
+
+fn fail(check: &str, detail: &str) {
+    println!("  {} {} - {}", "✗".red(), check.bold(), detail);
+}
+
+fn warn(check: &str, detail: &str) {
+    println!("  {} {} - {}", "!".yellow(), check.bold(), detail);
+}
+
+/// Trimmed stdout of a successful runtime invocation
+fn command_output(runtime: &dyn ContainerRuntime, args: &[&str]) -> Option<String> {
+    let output = runtime.command().args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -1,19 +1,28 @@
 use crate::docker::compose::DockerCompose;
 use crate::error::Result;
+use crate::lock::lock_project;
+use crate::state::DevnetState;
+use crate::utils::print_banner;
 use colored::*;
 
-pub async fn execute(purge: bool) -> Result<()> {
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!("{}", "  ZecKit - Stopping Devnet".cyan().bold());
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!();
+pub async fn execute(remove_volumes: bool, force: bool) -> Result<()> {
+    print_banner("ZecKit - Stopping Devnet");
     
     let compose = DockerCompose::new()?;
+    let _lock = lock_project(&compose, "down", force, "--force")?;
+    
+    if compose.is_dry_run() {
+        compose.down(remove_volumes)?;
+        println!();
+        println!("{}", "Dry run - nothing was stopped.".yellow());
+        return Ok(());
+    }
     
     println!("{} Stopping services...", "🛑".yellow());
-    compose.down(purge)?;
+    compose.down(remove_volumes)?;
+    DevnetState::clear(&compose.project_name()?)?;
     
-    if purge {
+    if remove_volumes {
         println!("{} Volumes removed (fresh start on next up)", "✓".green());
     }
     
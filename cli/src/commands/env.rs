@@ -0,0 +1,37 @@
+use crate::commands::up::{selected_backends, Backend};
+use crate::config::settings::Settings;
+use crate::config::Config;
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::state::DevnetState;
+use colored::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub async fn execute(backends: Vec<Backend>, out: Option<PathBuf>) -> Result<()> {
+    // Without an explicit backend, describe what `up` started
+    let state = DevnetState::load(&DockerCompose::new()?.project_name()?)?;
+    let backends = if backends.is_empty() {
+        state.as_ref().ok_or(ZecDevError::DevnetNotFound)?.backend_names()
+    } else {
+        selected_backends(&backends)
+    };
+    let settings = match &state {
+        Some(state) => state.settings.clone(),
+        None => Settings::from_config(&Config::load()?),
+    };
+    
+    match out {
+        Some(path) => write_env_file(&path, &backends, &settings)?,
+        None => print!("{}", settings.to_dotenv(&backends)),
+    }
+    
+    Ok(())
+}
+
+/// Write the connection info for `backends` to `path` in dotenv format
+pub fn write_env_file(path: &Path, backends: &[&str], settings: &Settings) -> Result<()> {
+    fs::write(path, settings.to_dotenv(backends))?;
+    println!("{} Wrote connection info to {}", "✓".green(), path.display());
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use std::process;
+
+pub async fn execute(service: String, command: Vec<String>, interactive: bool) -> Result<()> {
+    let compose = DockerCompose::new()?;
+    
+    let available = compose.services()?;
+    if !available.contains(&service) {
+        return Err(ZecDevError::UnknownService {
+            name: service,
+            available,
+        });
+    }
+    
+    let command: Vec<&str> = if command.is_empty() {
+        vec!["/bin/sh"]
+    } else {
+        command.iter().map(|s| s.as_str()).collect()
+    };
+    
+    let code = compose.exec(&service, &command, interactive)?;
+    
+    // Mirror the inner command's exit status
+    if code != 0 {
+        process::exit(code);
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,270 @@
+use crate::address;
+use crate::config::settings::Settings;
+use crate::commands::wallet::default_address;
+use crate::config::Config;
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::faucet::{load_batch, zec_to_zat, BatchEntry, FaucetClient, ZATS_PER_ZEC};
+use crate::rpc::ZebraRpc;
+use crate::state::DevnetState;
+use crate::utils::{is_quiet, spinner};
+use colored::*;
+use std::path::Path;
+use tokio::time::{sleep, Duration, Instant};
+
+/// How long `up --fund` waits for its transfers to confirm
+pub const CONFIRM_TIMEOUT: Duration = Duration::from_secs(300);
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long to wait for confirmations and whether regtest blocks may be
+/// mined to get them
+#[derive(Debug, Clone, Copy)]
+pub struct ConfirmOptions {
+    pub confirmations: u64,
+    pub timeout: Duration,
+    pub auto_mine: bool,
+}
+
+/// Fund `address` (default: the saved wallet) with `amount` ZEC, or the
+/// faucet's configured default amount, optionally waiting for it to confirm
+pub async fn fund(address: Option<String>, amount: Option<f64>, confirm: Option<ConfirmOptions>) -> Result<()> {
+    let settings = Settings::from_config(&Config::load()?);
+    let address = match address {
+        Some(address) => address,
+        None => default_address()?,
+    };
+    // A mistyped or wrong-network address fails here rather than in the faucet's wallet
+    let network = DevnetState::network_for(&DockerCompose::new()?.project_name()?)?;
+    address::validate(&address, network)?;
+    let faucet = FaucetClient::new(&settings);
+    
+    // An empty faucet would otherwise surface as an opaque wallet error
+    if faucet.get_stats().await?.balance_zat == 0 {
+        return Err(ZecDevError::FaucetEmpty);
+    }
+    
+    match amount {
+        Some(amount) => println!("{} Requesting {} ZEC for {}...", "💧".cyan(), amount, address),
+        None => println!("{} Requesting the default amount for {}...", "💧".cyan(), address),
+    }
+    let receipt = faucet.request_funds(&address, amount.map(zec_to_zat)).await?;
+    
+    println!("{} Sent {} ZEC to {}", "✓".green(), receipt.amount, receipt.address);
+    println!("  {} {}", "TXID:".bold(), receipt.txid);
+    
+    if let Some(balance) = receipt.new_balance {
+        println!("  {} {} ZEC", "Faucet balance:".bold(), balance);
+    }
+    
+    if let Some(confirm) = confirm.filter(|c| c.confirmations > 0) {
+        let confirmed = wait_for_confirmations(&settings, &faucet, &[&receipt.txid], confirm).await?;
+        if let Some(&(depth, height)) = confirmed.first() {
+            println!(
+                "{} Confirmed: {} confirmation(s), mined in block {}",
+                "✓".green(),
+                depth,
+                height
+            );
+        }
+    }
+    
+    Ok(())
+}
+
+/// Fund every `address amount` line of `path`, then wait for the transfers to confirm.
+///
+/// A transfer the faucet rejects is reported and skipped; the batch fails at
+/// the end if any did.
+pub async fn fund_batch(path: &Path, confirm: ConfirmOptions) -> Result<()> {
+    let settings = Settings::from_config(&Config::load()?);
+    let entries = load_batch(path)?;
+    let network = DevnetState::network_for(&DockerCompose::new()?.project_name()?)?;
+    let faucet = FaucetClient::new(&settings);
+    let quiet = is_quiet();
+    
+    if faucet.get_stats().await?.balance_zat == 0 {
+        return Err(ZecDevError::FaucetEmpty);
+    }
+    
+    if !quiet {
+        println!("{} Funding {} address(es) from {}...", "💧".cyan(), entries.len(), path.display());
+    }
+    
+    let mut sent: Vec<(&BatchEntry, String)> = Vec::new();
+    let mut failed: Vec<(&BatchEntry, ZecDevError)> = Vec::new();
+    for entry in &entries {
+        let result = match address::validate(&entry.address, network) {
+            Ok(_) => faucet.request_funds(&entry.address, Some(entry.amount_zat)).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(receipt) => {
+                if !quiet {
+                    println!(
+                        "  {} {} {} ZEC  {} {}",
+                        "✓".green(),
+                        entry.address,
+                        format_zec(entry.amount_zat),
+                        "TXID:".bold(),
+                        receipt.txid
+                    );
+                }
+                sent.push((entry, receipt.txid));
+            }
+            Err(e) => {
+                eprintln!("  {} {} (line {}): {}", "✗".red(), entry.address, entry.line, e);
+                failed.push((entry, e));
+            }
+        }
+    }
+    
+    let confirmations = confirm.confirmations;
+    if !sent.is_empty() && confirmations > 0 {
+        let txids: Vec<&str> = sent.iter().map(|(_, txid)| txid.as_str()).collect();
+        wait_for_confirmations(&settings, &faucet, &txids, confirm).await?;
+    }
+    
+    if !quiet {
+        let total: u64 = sent.iter().map(|(entry, _)| entry.amount_zat).sum();
+        println!();
+        println!(
+            "{} Funded {} of {} address(es), {} ZEC in total",
+            if failed.is_empty() { "✓".green() } else { "✗".red() },
+            sent.len(),
+            entries.len(),
+            format_zec(total)
+        );
+        if confirmations > 0 && !sent.is_empty() {
+            println!("  {} {} block(s) deep", "Confirmed:".bold(), confirmations);
+        }
+    }
+    
+    if failed.is_empty() {
+        return Ok(());
+    }
+    
+    eprintln!("  {}", "Failed:".red().bold());
+    for (entry, e) in &failed {
+        eprintln!("    line {}: {} - {}", entry.line, entry.address, e);
+    }
+    Err(ZecDevError::Faucet(format!("{} of {} transfers failed", failed.len(), entries.len())))
+}
+
+/// Wait until every txid is `confirmations` blocks deep, returning each
+/// one's confirmation count and block height in `txids` order.
+///
+/// Regtest only makes blocks on request, so with `auto_mine` they are mined
+/// to the faucet there, as `zecdev mine` does: one to take a mempool
+/// transaction in, then only the confirmations still missing. Nothing is
+/// mined for a transaction Zebra hasn't seen yet.
+async fn wait_for_confirmations(
+    settings: &Settings,
+    faucet: &FaucetClient,
+    txids: &[&str],
+    confirm: ConfirmOptions,
+) -> Result<Vec<(u64, u64)>> {
+    let ConfirmOptions { confirmations, timeout, auto_mine } = confirm;
+    let rpc = ZebraRpc::new(settings.zebra_rpc_url.clone());
+    let chain = rpc.get_blockchain_info().await?.chain;
+    let regtest = chain == "regtest";
+    let miner = if !auto_mine {
+        None
+    } else if regtest {
+        Some(faucet.get_address().await?)
+    } else {
+        return Err(ZecDevError::NotRegtest(chain).with_hint("Drop --auto-mine; this network makes blocks on its own."));
+    };
+    
+    let pb = spinner();
+    pb.enable_steady_tick(Duration::from_millis(100));
+    let start = Instant::now();
+    
+    loop {
+        let tip = rpc.get_block_count().await?;
+        let mut pending = 0;
+        let mut confirmed = Vec::new();
+        // Blocks to mine before the next check, and whether any transaction is still outside a block
+        let mut needed = 0;
+        let mut unmined = false;
+        for txid in txids {
+            match rpc.transaction_height(txid).await {
+                Ok(Some(height)) => {
+                    let depth = (tip + 1).saturating_sub(height);
+                    if depth >= confirmations {
+                        confirmed.push((depth, height));
+                        continue;
+                    }
+                    needed = needed.max(confirmations - depth);
+                }
+                // In the mempool: the next block takes it in
+                Ok(None) => {
+                    needed = needed.max(1);
+                    unmined = true;
+                }
+                // Not found yet: mining would not help until it reaches the mempool
+                Err(_) => unmined = true,
+            }
+            pending += 1;
+        }
+        
+        if pending == 0 {
+            pb.finish_and_clear();
+            return Ok(confirmed);
+        }
+        pb.set_message(format!(
+            "Waiting for {} of {} transaction(s) to reach {} confirmation(s)...",
+            pending,
+            txids.len(),
+            confirmations
+        ));
+        
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            pb.abandon_with_message(format!("✗ {} transaction(s) unconfirmed", pending).red().to_string());
+            return Err(ZecDevError::Faucet(format!(
+                "{} transaction(s) not confirmed after {:.0?}",
+                pending, elapsed
+            ))
+            .with_hint(if regtest && miner.is_none() {
+                "The transfers were sent. Regtest only makes blocks on request: pass --auto-mine, or run zecdev mine."
+            } else {
+                "The transfers were sent. Check that Zebra is producing blocks, or raise --timeout."
+            }));
+        }
+        
+        match &miner {
+            Some(address) if needed > 0 => {
+                rpc.generate_to_address(needed, address).await?;
+                if unmined {
+                    sleep(POLL_INTERVAL).await;
+                }
+            }
+            _ => sleep(POLL_INTERVAL).await,
+        }
+    }
+}
+
+pub async fn stats(json: bool) -> Result<()> {
+    let settings = Settings::from_config(&Config::load()?);
+    let stats = FaucetClient::new(&settings).get_stats().await?;
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+    
+    println!("  {} {}", "Address:".bold(), stats.address);
+    println!("  {} {} ZEC", "Balance:".bold(), format_zec(stats.balance_zat));
+    println!("  {} {} ZEC", "Dispensed:".bold(), format_zec(stats.total_dispensed));
+    println!("  {} {}", "Requests served:".bold(), stats.requests_served);
+    if !stats.uptime.is_empty() {
+        println!("  {} {}", "Uptime:".bold(), stats.uptime);
+    }
+    
+    Ok(())
+}
+
+fn format_zec(zat: u64) -> String {
+    format!("{:.8}", zat as f64 / ZATS_PER_ZEC as f64)
+}
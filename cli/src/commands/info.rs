@@ -0,0 +1,77 @@
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::utils::print_banner;
+use colored::*;
+
+/// Health check runs shown; Docker keeps the last five
+const HEALTH_LOG_ENTRIES: usize = 5;
+
+pub async fn execute(service: String) -> Result<()> {
+    let compose = DockerCompose::new()?;
+    
+    let available = compose.services()?;
+    if !available.contains(&service) {
+        return Err(ZecDevError::UnknownService {
+            name: service,
+            available,
+        });
+    }
+    
+    let info = compose.inspect(&service)?;
+    
+    print_banner(&format!("ZecKit - {}", info.service));
+    
+    let state = if info.state == "running" {
+        info.state.green()
+    } else {
+        info.state.red()
+    };
+    println!("  {} {} (since {})", "State:".bold(), state, info.started_at);
+    println!("  {} {}", "Health:".bold(), info.health);
+    println!("  {} {}", "Restarts:".bold(), info.restart_count);
+    println!("  {} {}", "Container:".bold(), info.container_id);
+    println!("  {} {}", "Image:".bold(), info.image);
+    println!(
+        "  {} {}",
+        "Digest:".bold(),
+        info.image_digest.as_deref().unwrap_or("none (built locally)")
+    );
+    
+    println!();
+    println!("  {}", "Ports".bold());
+    if info.ports.is_empty() {
+        println!("    none published");
+    }
+    for port in &info.ports {
+        println!("    {}", port);
+    }
+    
+    println!();
+    println!("  {}", "Mounts".bold());
+    if info.mounts.is_empty() {
+        println!("    none");
+    }
+    for mount in &info.mounts {
+        let mode = if mount.read_only { " (read-only)" } else { "" };
+        println!("    {} -> {}{}", mount.source, mount.destination, mode);
+    }
+    
+    println!();
+    println!("  {}", "Health checks".bold());
+    if info.health_log.is_empty() {
+        println!("    none recorded");
+    }
+    let skip = info.health_log.len().saturating_sub(HEALTH_LOG_ENTRIES);
+    for probe in info.health_log.iter().skip(skip) {
+        let mark = if probe.exit_code == 0 { "✓".green() } else { "✗".red() };
+        let output = probe.output.trim();
+        if output.is_empty() {
+            println!("    {} {}", mark, probe.start);
+        } else {
+            println!("    {} {} - {}", mark, probe.start, output.lines().next().unwrap_or_default());
+        }
+    }
+    
+    println!();
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::utils::{is_quiet, log_command};
+use colored::*;
+use regex::Regex;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncBufReadExt;
+
+/// `--grep` mode: follow the logs until a line matches
+pub struct LogWatch {
+    pub grep: Regex,
+    /// Give up as soon as a line matches this instead
+    pub fail_on: Option<Regex>,
+    pub timeout: Option<Duration>,
+}
+
+pub async fn execute(services: Vec<String>, follow: bool, tail: Option<usize>, watch: Option<LogWatch>) -> Result<()> {
+    let compose = DockerCompose::new()?;
+    
+    // Validate requested services against the compose definition
+    if !services.is_empty() {
+        let available = compose.services()?;
+        for service in &services {
+            if !available.iter().any(|s| s == service) {
+                return Err(ZecDevError::UnknownService {
+                    name: service.clone(),
+                    available,
+                });
+            }
+        }
+    }
+    
+    // Default to everything that is currently running
+    let targets = if services.is_empty() {
+        compose.running_services()?
+    } else {
+        services
+    };
+    
+    if targets.is_empty() {
+        return Err(ZecDevError::DevnetNotRunning);
+    }
+    
+    let targets: Vec<&str> = targets.iter().map(|s| s.as_str()).collect();
+    match watch {
+        Some(watch) => wait_for_line(&compose, &targets, tail, watch).await,
+        None => compose.logs(&targets, follow, tail),
+    }
+}
+
+/// Stream the logs, lines already written included, until one matches
+/// `--grep`, then print it. A `--fail-on` match or the timeout is an error.
+async fn wait_for_line(compose: &DockerCompose, targets: &[&str], tail: Option<usize>, watch: LogWatch) -> Result<()> {
+    let mut cmd = compose.follow_logs_command(&[], None);
+    if let Some(tail) = tail {
+        cmd.arg(format!("--tail={}", tail));
+    }
+    cmd.args(targets);
+    let _span = log_command(&cmd);
+    let mut child = tokio::process::Command::from(cmd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    
+    let mut lines = tokio::io::BufReader::new(child.stdout.take().expect("stdout is piped")).lines();
+    let scan = async {
+        while let Some(line) = lines.next_line().await? {
+            if !is_quiet() {
+                println!("{}", line);
+            }
+            // Checked first, so a line matching both counts as a failure
+            if let Some(fail_on) = watch.fail_on.as_ref().filter(|p| p.is_match(&line)) {
+                return Err(ZecDevError::LogFailure { pattern: fail_on.to_string(), line });
+            }
+            if watch.grep.is_match(&line) {
+                return Ok(line);
+            }
+        }
+        Err(ZecDevError::Docker(format!("log stream ended before a line matched '{}'", watch.grep))
+            .with_hint("The services may have stopped; check them with zecdev ps."))
+    };
+    
+    let result = match watch.timeout {
+        Some(timeout) => tokio::time::timeout(timeout, scan).await.unwrap_or_else(|_| {
+            Err(ZecDevError::LogTimeout { pattern: watch.grep.to_string(), elapsed: timeout })
+        }),
+        None => scan.await,
+    };
+    let _ = child.kill().await;
+    
+    let line = result?;
+    if is_quiet() {
+        println!("{}", line);
+    } else {
+        println!("{} Matched '{}': {}", "✓".green(), watch.grep, line.trim());
+    }
+    Ok(())
+}
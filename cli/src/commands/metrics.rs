@@ -0,0 +1,347 @@
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::state::DevnetState;
+use crate::utils::is_quiet;
+use colored::*;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A closed metrics port should fail fast, not look like a hang
+const SCRAPE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Largest request head `--serve` will read before answering
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// A client that connects and sends nothing is dropped after this long
+const REQUEST_HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A component that exposes a Prometheus endpoint
+struct Source {
+    /// Compose service name, also used as the `component` label
+    service: &'static str,
+    url: String,
+}
+
+/// Print the merged metrics of the running devnet, or keep serving them on `serve`
+pub async fn execute(serve: Option<String>) -> Result<()> {
+    let sources = sources()?;
+    match serve {
+        Some(addr) => serve_metrics(&addr, sources).await,
+        None => print_metrics(&sources).await,
+    }
+}
+
+/// Zebra always, lightwalletd when `up` started it
+fn sources() -> Result<Vec<Source>> {
+    let compose = DockerCompose::new()?;
+    let project = compose.project_name()?;
+    let settings = DevnetState::settings_for(&project)?;
+    let backends = DevnetState::load(&project)?
+        .map(|state| state.backends)
+        .unwrap_or_default();
+    
+    let mut sources = vec![Source {
+        service: "zebra",
+        url: format!("{}/metrics", settings.zebra_metrics_url),
+    }];
+    if backends.iter().any(|b| b == "lwd") {
+        sources.push(Source {
+            service: "lightwalletd",
+            url: format!("{}/metrics", settings.backend_metrics_url),
+        });
+    }
+    if backends.iter().any(|b| b == "zaino") && !is_quiet() {
+        eprintln!("{}", "zaino does not expose Prometheus metrics; skipping it".dimmed());
+    }
+    Ok(sources)
+}
+
+async fn print_metrics(sources: &[Source]) -> Result<()> {
+    let results = scrape_all(sources).await?;
+    if results.iter().all(|(_, r)| r.is_err()) {
+        if let Some((_, Err(e))) = results.into_iter().next() {
+            return Err(e);
+        }
+        return Ok(());
+    }
+    
+    for (service, result) in &results {
+        if let Err(e) = result {
+            eprintln!("{} {}: {}", "✗".red(), service, e);
+            if let Some(hint) = e.hint() {
+                eprintln!("  {} {}", "Hint:".yellow().bold(), hint.yellow());
+            }
+        }
+    }
+    print!("{}", merge(&answered(&results)));
+    Ok(())
+}
+
+/// Re-expose the merged metrics at `http://<addr>/metrics` until Ctrl-C
+async fn serve_metrics(addr: &str, sources: Vec<Source>) -> Result<()> {
+    let listener = TcpListener::bind(addr).await.map_err(|e| {
+        ZecDevError::Io(e).with_hint(format!("Pick another address for --serve than {}.", addr))
+    })?;
+    
+    if !is_quiet() {
+        println!("{} Serving merged metrics at http://{}/metrics", "✓".green(), listener.local_addr()?);
+        for source in &sources {
+            println!("  {} {}", source.service, source.url.dimmed());
+        }
+        println!("Press Ctrl-C to stop");
+    }
+    
+    tokio::select! {
+        result = accept_loop(listener, Arc::new(sources)) => result,
+        _ = tokio::signal::ctrl_c() => Ok(()),
+    }
+}
+
+/// Answer each connection on its own task, so a slow or idle client can't
+/// hold up the scrapes behind it
+async fn accept_loop(listener: TcpListener, sources: Arc<Vec<Source>>) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let sources = Arc::clone(&sources);
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &sources).await {
+                tracing::debug!(error = %e, "metrics request failed");
+            }
+        });
+    }
+}
+
+/// Answer one HTTP request: the merged metrics for `GET /metrics`, 404 otherwise
+async fn handle_connection(mut stream: TcpStream, sources: &[Source]) -> Result<()> {
+    let head = tokio::time::timeout(REQUEST_HEAD_TIMEOUT, read_head(&mut stream))
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "client sent no request"))??;
+    
+    let request_line = String::from_utf8_lossy(&head);
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let path = path.split('?').next().unwrap_or("");
+    
+    let (status, body) = if method == "GET" && path == "/metrics" {
+        let results = scrape_all(sources).await?;
+        for (service, result) in &results {
+            if let Err(e) = result {
+                tracing::debug!(service, error = %e, "scrape failed");
+            }
+        }
+        let mut body = merge(&answered(&results));
+        body.push_str(&up_gauge(&results));
+        ("200 OK", body)
+    } else {
+        ("404 Not Found", "Only /metrics is served here\n".to_string())
+    };
+    
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Read up to the end of the request head, or `MAX_REQUEST_HEAD` bytes of it
+async fn read_head(stream: &mut TcpStream) -> Result<Vec<u8>> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_HEAD {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    Ok(head)
+}
+
+/// Scrape every source, in order, tagging each result with its service
+async fn scrape_all(sources: &[Source]) -> Result<Vec<(&'static str, Result<String>)>> {
+    let client = reqwest::Client::builder().timeout(SCRAPE_TIMEOUT).build()?;
+    let running = DockerCompose::new()?.running_services()?;
+    
+    let mut results = Vec::new();
+    for source in sources {
+        results.push((source.service, scrape(&client, source, &running).await));
+    }
+    Ok(results)
+}
+
+/// The sources that answered, as `(component, body)` pairs for `merge`
+fn answered<'a>(results: &'a [(&'static str, Result<String>)]) -> Vec<(&'a str, &'a str)> {
+    results
+        .iter()
+        .filter_map(|(service, result)| Some((*service, result.as_ref().ok()?.as_str())))
+        .collect()
+}
+
+async fn scrape(client: &reqwest::Client, source: &Source, running: &[String]) -> Result<String> {
+    if !running.iter().any(|s| s == source.service) {
+        return Err(ZecDevError::ServiceNotRunning(source.service.to_string()));
+    }
+    
+    let disabled = || ZecDevError::MetricsDisabled {
+        service: source.service.to_string(),
+        url: source.url.clone(),
+    };
+    let response = match client.get(&source.url).send().await {
+        Ok(response) => response,
+        Err(e) if e.is_timeout() => {
+            return Err(ZecDevError::HealthCheck(format!(
+                "{} metrics at {} did not answer within {:?}",
+                source.service, source.url, SCRAPE_TIMEOUT
+            )));
+        }
+        // Docker publishes the port either way; with nothing listening
+        // inside, the connection is refused or dropped without a reply
+        Err(e) if e.is_connect() || e.is_request() => return Err(disabled()),
+        Err(e) => return Err(e.into()),
+    };
+    
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(disabled());
+    }
+    Ok(response.error_for_status()?.text().await?)
+}
+
+/// `zeckit_scrape_up{component="..."}`: 1 for sources that answered, 0 otherwise
+fn up_gauge(results: &[(&'static str, Result<String>)]) -> String {
+    let mut out = String::from("# HELP zeckit_scrape_up Whether the component's metrics could be scraped\n");
+    out.push_str("# TYPE zeckit_scrape_up gauge\n");
+    for (service, result) in results {
+        out.push_str(&format!("zeckit_scrape_up{{component=\"{}\"}} {}\n", service, result.is_ok() as u8));
+    }
+    out
+}
+
+/// Merge several components' exposition text into one, labelling every
+/// sample with its `component` and keeping each family's HELP, TYPE and
+/// samples together so Prometheus accepts the result.
+fn merge(sources: &[(&str, &str)]) -> String {
+    let mut families: Vec<(String, Vec<String>, Vec<String>)> = Vec::new();
+    
+    for (component, body) in sources {
+        for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(rest) = line.strip_prefix("# HELP ").or_else(|| line.strip_prefix("# TYPE ")) {
+                let name = rest.split_whitespace().next().unwrap_or_default();
+                let index = family_index(&mut families, name);
+                if !families[index].1.iter().any(|l| l == line) {
+                    families[index].1.push(line.to_string());
+                }
+            } else if !line.starts_with('#') {
+                let name = sample_name(line);
+                let family = families
+                    .iter()
+                    .position(|(f, ..)| family_of(name) == f || name == f)
+                    .unwrap_or_else(|| family_index(&mut families, name));
+                families[family].2.push(with_label(line, component));
+            }
+        }
+    }
+    
+    let mut out = String::new();
+    for (_, comments, samples) in families {
+        for line in comments.iter().chain(&samples) {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn family_index(families: &mut Vec<(String, Vec<String>, Vec<String>)>, name: &str) -> usize {
+    match families.iter().position(|(f, ..)| f == name) {
+        Some(index) => index,
+        None => {
+            families.push((name.to_string(), Vec::new(), Vec::new()));
+            families.len() - 1
+        }
+    }
+}
+
+/// Metric name of a sample line: everything before its labels or value
+fn sample_name(line: &str) -> &str {
+    let end = line.find(|c: char| c == '{' || c.is_whitespace()).unwrap_or(line.len());
+    &line[..end]
+}
+
+/// The family a histogram or summary sample belongs to
+fn family_of(name: &str) -> &str {
+    ["_bucket", "_sum", "_count"]
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .unwrap_or(name)
+}
+
+/// `line` with `component="<component>"` as its first label
+fn with_label(line: &str, component: &str) -> String {
+    let name = sample_name(line);
+    let rest = &line[name.len()..];
+    let label = format!("component=\"{}\"", component);
+    match rest.strip_prefix('{') {
+        Some(labels) if labels.starts_with('}') => format!("{}{{{}{}", name, label, labels),
+        Some(labels) => format!("{}{{{},{}", name, label, labels),
+        None => format!("{}{{{}}}{}", name, label, rest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn samples_get_a_component_label() {
+        assert_eq!(with_label("up 1", "zebra"), r#"up{component="zebra"} 1"#);
+        assert_eq!(with_label("up{} 1", "zebra"), r#"up{component="zebra"} 1"#);
+        assert_eq!(
+            with_label(r#"rpc_calls{method="getinfo"} 3 1700000000"#, "lightwalletd"),
+            r#"rpc_calls{component="lightwalletd",method="getinfo"} 3 1700000000"#
+        );
+    }
+    
+    #[test]
+    fn merged_families_stay_together_without_duplicate_metadata() {
+        let zebra = "# HELP process_open_fds Open fds\n# TYPE process_open_fds gauge\nprocess_open_fds 12\n\
+                     # TYPE sync_seconds histogram\nsync_seconds_bucket{le=\"1\"} 2\nsync_seconds_sum 1.5\nsync_seconds_count 2\n";
+        let lwd = "# HELP process_open_fds Open fds\n# TYPE process_open_fds gauge\nprocess_open_fds 7\n";
+    
+        assert_eq!(
+            merge(&[("zebra", zebra), ("lightwalletd", lwd)]),
+            "# HELP process_open_fds Open fds\n\
+             # TYPE process_open_fds gauge\n\
+             process_open_fds{component=\"zebra\"} 12\n\
+             process_open_fds{component=\"lightwalletd\"} 7\n\
+             # TYPE sync_seconds histogram\n\
+             sync_seconds_bucket{component=\"zebra\",le=\"1\"} 2\n\
+             sync_seconds_sum{component=\"zebra\"} 1.5\n\
+             sync_seconds_count{component=\"zebra\"} 2\n"
+        );
+    }
+    
+    #[tokio::test]
+    async fn an_idle_client_does_not_block_other_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(accept_loop(listener, Arc::new(Vec::new())));
+        
+        // Connects and never sends a request
+        let _idle = TcpStream::connect(addr).await.unwrap();
+        
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        client.write_all(b"GET /other HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        tokio::time::timeout(Duration::from_secs(5), client.read_to_string(&mut response))
+            .await
+            .expect("the second client should be answered while the first is idle")
+            .unwrap();
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"), "{}", response);
+    }
+}
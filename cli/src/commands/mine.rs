@@ -0,0 +1,46 @@
+use crate::config::settings::Settings;
+use crate::config::Config;
+use crate::error::Result;
+use crate::faucet::FaucetClient;
+use crate::rpc::ZebraRpc;
+use colored::*;
+use indicatif::{ProgressBar, ProgressStyle};
+
+pub async fn execute(blocks: u64, address: Option<String>) -> Result<()> {
+    let settings = Settings::from_config(&Config::load()?);
+    let rpc = ZebraRpc::new(settings.zebra_rpc_url.clone());
+    
+    // Block production on demand only exists on regtest
+    let info = rpc.require_regtest().await?;
+    
+    // Pay the coinbase to the faucet unless told otherwise
+    let address = match address {
+        Some(address) => address,
+        None => FaucetClient::new(&settings).get_address().await?,
+    };
+    
+    let pb = ProgressBar::new(blocks);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} Mining [{bar:30.green}] {pos}/{len} blocks")
+            .unwrap()
+    );
+    
+    for _ in 0..blocks {
+        rpc.generate_to_address(1, &address).await?;
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+    
+    let height = rpc.get_block_count().await?;
+    println!(
+        "{} Mined {} block(s): height {} → {}",
+        "✓".green(),
+        blocks,
+        info.blocks,
+        height
+    );
+    println!("  {} {}", "Coinbase to:".bold(), address);
+    
+    Ok(())
+}
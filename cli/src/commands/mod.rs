@@ -1,4 +1,33 @@
 pub mod up;
 pub mod down;
 pub mod status;
-pub mod test;
\ No newline at end of file
+pub mod test;
+pub mod logs;
+pub mod restart;
+pub mod faucet;
+pub mod rpc;
+pub mod exec;
+pub mod pull;
+pub mod completions;
+pub mod ps;
+pub mod mine;
+pub mod snapshot;
+pub mod doctor;
+pub mod env;
+pub mod clean;
+pub mod wait;
+pub mod watch;
+pub mod info;
+pub mod wallet;
+pub mod stop;
+pub mod start;
+pub mod version;
+pub mod reorg;
+pub mod metrics;
+pub mod shell;
+pub mod bundle;
+pub mod validate_address;
+pub mod reset;
+pub mod connect_config;
+pub mod top;
+pub mod backend;
@@ -0,0 +1,30 @@
+use crate::docker::compose::DockerCompose;
+use crate::error::Result;
+use crate::utils::print_table;
+
+pub async fn execute() -> Result<()> {
+    let compose = DockerCompose::new()?;
+    let services = compose.ps()?;
+    
+    if services.is_empty() {
+        println!("  No ZecKit containers found - run zecdev up to start the devnet");
+        return Ok(());
+    }
+    
+    let rows: Vec<Vec<String>> = services
+        .iter()
+        .map(|s| {
+            vec![
+                s.name.clone(),
+                s.container_id.clone(),
+                s.state.clone(),
+                s.health.clone(),
+                s.status.clone(),
+                s.ports.join(", "),
+            ]
+        })
+        .collect();
+    
+    print_table(&["SERVICE", "CONTAINER ID", "STATE", "HEALTH", "STATUS", "PORTS"], &rows);
+    Ok(())
+}
@@ -0,0 +1,267 @@
+use crate::commands::up::{selected_backends, Backend};
+use crate::config::{Config, Network};
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::preflight::check_disk;
+use crate::services::ServiceSet;
+use crate::state::DevnetState;
+use crate::utils::{print_banner, progress_group, spinner};
+use colored::*;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Share of a layer's progress bar given to downloading; extracting is the rest
+const DOWNLOAD_SHARE: f64 = 0.8;
+
+pub async fn execute(backends: Vec<Backend>, quiet_pull: bool) -> Result<()> {
+    print_banner("ZecKit - Pulling Images");
+    
+    let config = Config::load()?;
+    let backends = config.resolve_backends(backends);
+    let compose = DockerCompose::new()?;
+    let network = DevnetState::load(&compose.project_name()?)?
+        .map_or(Network::Regtest, |state| state.network);
+    check_disk(config.min_free_disk(network))?;
+    pull_services(&compose, &ServiceSet::names_for(&selected_backends(&backends)), quiet_pull)?;
+    
+    println!();
+    Ok(())
+}
+
+/// Pull every service's image at once, with a progress bar each, or a
+/// single summary line with `quiet_pull`.
+///
+/// Every pull runs to the end, so one failure still shows how the others
+/// finished; failures are returned together afterwards.
+pub fn pull_services(compose: &DockerCompose, services: &[&str], quiet_pull: bool) -> Result<()> {
+    let multi = progress_group();
+    let summary = quiet_pull.then(|| {
+        let pb = multi.add(spinner());
+        pb.enable_steady_tick(Duration::from_millis(100));
+        pb.set_message(format!("Pulling {} image(s)...", services.len()));
+        pb
+    });
+    let finished = AtomicUsize::new(0);
+    
+    let results: Vec<(&str, Result<()>)> = std::thread::scope(|scope| {
+        let pulls: Vec<_> = services
+            .iter()
+            .map(|service| {
+                let bar = (!quiet_pull).then(|| multi.add(pull_bar(service)));
+                let (summary, finished) = (&summary, &finished);
+                scope.spawn(move || {
+                    let result = pull_one(compose, service, bar.as_ref());
+                    let done = finished.fetch_add(1, Ordering::SeqCst) + 1;
+                    if let Some(pb) = summary {
+                        pb.set_message(format!("Pulling {} image(s)... {} done", services.len(), done));
+                    }
+                    result
+                })
+            })
+            .collect();
+    
+        services
+            .iter()
+            .zip(pulls)
+            .map(|(service, pull)| {
+                let result = pull
+                    .join()
+                    .unwrap_or_else(|_| Err(ZecDevError::Docker(format!("pulling {} panicked", service))));
+                (*service, result)
+            })
+            .collect()
+    });
+    
+    let failed: Vec<&str> = results.iter().filter(|(_, r)| r.is_err()).map(|(s, _)| *s).collect();
+    if let Some(pb) = &summary {
+        if failed.is_empty() {
+            pb.finish_with_message(format!("✓ Pulled {} image(s)", services.len()).green().to_string());
+        } else {
+            pb.abandon_with_message(
+                format!("✗ {} of {} image(s) failed: {}", failed.len(), services.len(), failed.join(", "))
+                    .red()
+                    .to_string(),
+            );
+        }
+    }
+    
+    let mut errors: Vec<ZecDevError> = results.into_iter().filter_map(|(_, r)| r.err()).collect();
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        n => Err(ZecDevError::Docker(format!("{} image pulls failed: {}", n, failed.join(", ")))
+            .with_hint("Run zecdev pull --verbose to see each pull's command, then retry.")),
+    }
+}
+
+/// Pull one image, driving `bar` from compose's layer progress
+fn pull_one(compose: &DockerCompose, service: &str, bar: Option<&ProgressBar>) -> Result<()> {
+    let mut progress = PullProgress::default();
+    let result = compose.pull_with_progress(service, |line| {
+        progress.record(line);
+        if let Some(pb) = bar {
+            pb.set_position(progress.percent());
+            pb.set_message(progress.summary());
+        }
+    });
+    
+    if let Some(pb) = bar {
+        // Swap the bar for a plain line so finished pulls read like other steps
+        pb.set_style(ProgressStyle::default_spinner().template("{spinner:.green} {msg}").unwrap());
+        match &result {
+            Ok(()) if progress.skipped => {
+                pb.finish_with_message(format!("✓ {} built locally, skipped", service).green().to_string());
+            }
+            Ok(()) => pb.finish_with_message(format!("✓ {} up to date", service).green().to_string()),
+            Err(e) => {
+                let reason = e.to_string();
+                let reason = reason.lines().find(|l| !l.trim().is_empty()).unwrap_or_default().trim().to_string();
+                pb.abandon_with_message(format!("✗ {} failed: {}", service, reason).red().to_string());
+            }
+        }
+    }
+    result
+}
+
+fn pull_bar(service: &str) -> ProgressBar {
+    let pb = ProgressBar::new(100);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} {prefix:<12} [{bar:25.cyan}] {pos:>3}% {msg}")
+            .unwrap(),
+    );
+    pb.set_prefix(service.to_string());
+    pb.enable_steady_tick(Duration::from_millis(100));
+    pb
+}
+
+/// Layer-by-layer state of one image pull, from compose's progress lines
+#[derive(Debug, Default)]
+struct PullProgress {
+    /// How far along each layer is, from 0 to 1
+    layers: BTreeMap<String, f64>,
+    /// Compose skipped the service because it is built locally
+    skipped: bool,
+}
+
+impl PullProgress {
+    /// Take in one line such as `a2318d6c47ec Downloading [==>  ]  1.2MB/29.1MB`
+    fn record(&mut self, line: &str) {
+        let Some((id, status)) = line.trim().split_once(char::is_whitespace) else {
+            return;
+        };
+        let status = status.trim();
+        if !is_layer_id(id) {
+            // Service lines, e.g. `faucet Skipped - No image to be pulled`
+            if status.starts_with("Skipped") {
+                self.skipped = true;
+            }
+            return;
+        }
+    
+        let done = if status.starts_with("Downloading") {
+            transferred(status) * DOWNLOAD_SHARE
+        } else if status.starts_with("Extracting") {
+            DOWNLOAD_SHARE + transferred(status) * (1.0 - DOWNLOAD_SHARE)
+        } else if status.starts_with("Verifying") || status.starts_with("Download complete") {
+            DOWNLOAD_SHARE
+        } else if status.starts_with("Pull complete") || status.starts_with("Already exists") {
+            1.0
+        } else {
+            0.0
+        };
+        // Lines can arrive out of order; a layer never goes backwards
+        let layer = self.layers.entry(id.to_string()).or_default();
+        *layer = layer.max(done);
+    }
+    
+    /// Whole-image progress, 0 to 100
+    fn percent(&self) -> u64 {
+        if self.layers.is_empty() {
+            return 0;
+        }
+        let total: f64 = self.layers.values().sum();
+        (total / self.layers.len() as f64 * 100.0).round() as u64
+    }
+    
+    fn summary(&self) -> String {
+        let complete = self.layers.values().filter(|done| **done >= 1.0).count();
+        format!("{}/{} layers", complete, self.layers.len())
+    }
+}
+
+/// Image layers are named by the first 12 hex digits of their digest
+fn is_layer_id(id: &str) -> bool {
+    id.len() == 12 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// `current/total` from the end of a progress status, as a fraction
+fn transferred(status: &str) -> f64 {
+    let Some((current, total)) = status.split_whitespace().last().and_then(|t| t.split_once('/')) else {
+        return 0.0;
+    };
+    match (parse_size(current), parse_size(total)) {
+        (Some(current), Some(total)) if total > 0.0 => (current / total).min(1.0),
+        _ => 0.0,
+    }
+}
+
+/// A size as docker prints it, e.g. `301.1kB` or `29.15MB`, in bytes
+fn parse_size(size: &str) -> Option<f64> {
+    let split = size.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = size.split_at(split);
+    let scale = match unit {
+        "B" => 1.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        _ => return None,
+    };
+    Some(value.parse::<f64>().ok()? * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn layers_advance_through_download_and_extract() {
+        let mut progress = PullProgress::default();
+        progress.record("zebra Pulling");
+        progress.record("a2318d6c47ec Pulling fs layer");
+        progress.record("0f1ba3a443a1 Already exists");
+        assert_eq!(progress.percent(), 50);
+    
+        progress.record("a2318d6c47ec Downloading [=========>          ]  14.5MB/29MB");
+        assert_eq!(progress.percent(), 70);
+    
+        progress.record("a2318d6c47ec Extracting [=====>      ]  14.5MB/29MB");
+        assert_eq!(progress.percent(), 95);
+    
+        progress.record("a2318d6c47ec Downloading [=>    ]  1MB/29MB");
+        assert_eq!(progress.percent(), 95);
+    
+        progress.record("a2318d6c47ec Pull complete");
+        assert_eq!(progress.percent(), 100);
+        assert_eq!(progress.summary(), "2/2 layers");
+        assert!(!progress.skipped);
+    }
+    
+    #[test]
+    fn buildable_services_are_reported_as_skipped() {
+        let mut progress = PullProgress::default();
+        progress.record("faucet Skipped - No image to be pulled");
+        assert!(progress.skipped);
+        assert_eq!(progress.percent(), 0);
+    }
+    
+    #[test]
+    fn sizes_parse_with_docker_units() {
+        assert_eq!(parse_size("301.1kB"), Some(301_100.0));
+        assert_eq!(parse_size("29.15MB"), Some(29_150_000.0));
+        assert_eq!(parse_size("12B"), Some(12.0));
+        assert_eq!(parse_size("12"), None);
+    }
+}
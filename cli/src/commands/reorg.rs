@@ -0,0 +1,71 @@
+use crate::config::settings::Settings;
+use crate::config::Config;
+use crate::error::{Result, ZecDevError};
+use crate::faucet::FaucetClient;
+use crate::rpc::ZebraRpc;
+use crate::utils::spinner;
+use colored::*;
+use std::time::Duration;
+
+/// JSON-RPC "method not found"
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Replace the last `depth + 1` blocks with a freshly mined branch (regtest only)
+pub async fn execute(depth: u64, address: Option<String>) -> Result<()> {
+    let settings = Settings::from_config(&Config::load()?);
+    let rpc = ZebraRpc::new(settings.zebra_rpc_url.clone());
+    
+    // Rewriting history on a shared network is not something to do by accident
+    let info = rpc.require_regtest().await?;
+    let old_height = info.blocks;
+    if depth >= old_height {
+        return Err(ZecDevError::Config(format!(
+            "cannot invalidate {} block(s) at height {}; the genesis block must stay",
+            depth + 1,
+            old_height
+        ))
+        .with_hint(format!("Mine more blocks first, e.g. zecdev mine {}.", depth + 1 - old_height)));
+    }
+    
+    let address = match address {
+        Some(address) => address,
+        None => FaucetClient::new(&settings).get_address().await?,
+    };
+    
+    let old_tip = rpc.get_best_block_hash().await?;
+    let fork_height = old_height - depth;
+    let invalidated = rpc.get_block_hash(fork_height).await?;
+    
+    let pb = spinner();
+    pb.enable_steady_tick(Duration::from_millis(100));
+    pb.set_message(format!("Invalidating block {} and its descendants...", fork_height));
+    
+    if let Err(e) = rpc.invalidate_block(&invalidated).await {
+        pb.finish_and_clear();
+        return Err(match e {
+            ZecDevError::Rpc { code: METHOD_NOT_FOUND, .. } => {
+                e.with_hint("This Zebra version has no invalidateblock RPC; reorgs need a newer Zebra image.")
+            }
+            e => e,
+        });
+    }
+    
+    pb.set_message(format!("Mining {} replacement block(s)...", depth + 1));
+    rpc.generate_to_address(depth + 1, &address).await?;
+    pb.finish_and_clear();
+    
+    let new_height = rpc.get_block_count().await?;
+    let new_tip = rpc.get_best_block_hash().await?;
+    
+    println!(
+        "{} Reorged {} block(s) from height {}",
+        "✓".green(),
+        depth + 1,
+        fork_height
+    );
+    println!("  {} {} {}", "Old tip:".bold(), old_height, old_tip);
+    println!("  {} {} {}", "New tip:".bold(), new_height, new_tip);
+    println!("  {} {} {}", "Invalidated:".bold(), fork_height, invalidated);
+    
+    Ok(())
+}
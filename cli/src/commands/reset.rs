@@ -0,0 +1,74 @@
+use crate::config::Config;
+use crate::docker::compose::DockerCompose;
+use crate::docker::health::HealthChecker;
+use crate::error::{Result, ZecDevError};
+use crate::lock::lock_project;
+use crate::services::{ServiceSet, ZEBRA};
+use crate::state::DevnetState;
+use crate::utils::{is_quiet, print_banner, spinner};
+use colored::*;
+use std::time::Duration;
+
+/// Recreate one service from scratch: a new container and an empty volume.
+///
+/// `force` allows resetting Zebra; taking over the project lock is
+/// `force_unlock`'s alone, so clearing a stale lock never discards the chain.
+pub async fn execute(service: String, force: bool, force_unlock: bool, timeout: Option<Duration>) -> Result<()> {
+    print_banner("ZecKit - Resetting Service");
+    
+    let compose = DockerCompose::new()?;
+    let project = compose.project_name()?;
+    let _lock = lock_project(&compose, "reset", force_unlock, "--force-unlock")?;
+    
+    let available = compose.services()?;
+    if !available.contains(&service) {
+        return Err(ZecDevError::UnknownService { name: service, available });
+    }
+    if service == ZEBRA.name && !force {
+        return Err(ZecDevError::InvalidArgument("resetting zebra discards the whole chain".into())
+            .with_hint("Pass --force to reset it anyway; zecdev snapshot <NAME> saves the chain first."));
+    }
+    
+    // Recreate it for the network `up` chose, not zeckit.toml's default, and
+    // with the same overrides, so `--mount` or `--peers` survive a reset
+    let network = DevnetState::network_for(&project)?;
+    let compose = compose
+        .with_env(network.compose_env())
+        .with_layers(&DevnetState::layers_for(&project)?);
+    let settings = DevnetState::settings_for(&project)?;
+    let volume = ServiceSet::get(&service).and_then(|s| s.volume);
+    let target = [service.as_str()];
+    
+    if !is_quiet() {
+        println!("{} Resetting: {}", "🔄".yellow(), service);
+    }
+    compose.remove(&target)?;
+    if let Some(volume) = volume {
+        compose.remove_volume(volume)?;
+        if !is_quiet() {
+            println!("{} Removed volume {}", "✓".green(), volume);
+        }
+    }
+    
+    compose.up(&target)?;
+    if compose.is_dry_run() {
+        return Ok(());
+    }
+    compose.check_all_running(&target)?;
+    
+    let mut checker = HealthChecker::new()
+        .with_settings(settings)
+        .with_network(network)
+        .with_probes(Config::load()?.health_probes()?);
+    if let Some(timeout) = timeout {
+        checker = checker.with_timeout(timeout);
+    }
+    
+    let pb = spinner();
+    pb.set_message(format!("Waiting for {}...", service));
+    checker.wait_for_service(&service, &pb).await?;
+    pb.finish_with_message(format!("✓ {} ready", service).green().to_string());
+    
+    println!();
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::config::Config;
+use crate::docker::compose::DockerCompose;
+use crate::docker::health::HealthChecker;
+use crate::error::{Result, ZecDevError};
+use crate::state::DevnetState;
+use crate::utils::{print_banner, spinner};
+use colored::*;
+use std::time::Duration;
+
+pub async fn execute(services: Vec<String>, timeout: Option<Duration>) -> Result<()> {
+    print_banner("ZecKit - Restarting Services");
+    
+    let compose = DockerCompose::new()?;
+    let project = compose.project_name()?;
+    let settings = DevnetState::settings_for(&project)?;
+    let network = DevnetState::network_for(&project)?;
+    
+    // Only restart services that exist and are already up
+    let available = compose.services()?;
+    let running = compose.running_services()?;
+    for service in &services {
+        if !available.contains(service) {
+            return Err(ZecDevError::UnknownService {
+                name: service.clone(),
+                available,
+            });
+        }
+        if !running.contains(service) {
+            return Err(ZecDevError::ServiceNotRunning(service.clone()));
+        }
+    }
+    
+    let targets: Vec<&str> = services.iter().map(|s| s.as_str()).collect();
+    println!("{} Restarting: {}", "🔄".yellow(), targets.join(", "));
+    compose.restart(&targets)?;
+    
+    // A restarted container is usually back within seconds, so poll tighter
+    let mut checker = HealthChecker::new()
+        .with_settings(settings)
+        .with_network(network)
+        .with_probes(Config::load()?.health_probes()?)
+        .with_poll_interval(Duration::from_millis(100), Duration::from_secs(1));
+    if let Some(timeout) = timeout {
+        checker = checker.with_timeout(timeout);
+    }
+    
+    for service in &targets {
+        let pb = spinner();
+        pb.set_message(format!("Waiting for {}...", service));
+        checker.wait_for_service(service, &pb).await?;
+        pb.finish_with_message(format!("✓ {} ready", service).green().to_string());
+    }
+    
+    println!();
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::config::settings::Settings;
+use crate::config::Config;
+use crate::error::{Result, ZecDevError};
+use crate::rpc::ZebraRpc;
+use serde_json::Value;
+
+pub async fn execute(method: String, params: Vec<String>, raw: Option<String>) -> Result<()> {
+    let settings = Settings::from_config(&Config::load()?);
+    let rpc = ZebraRpc::new(settings.zebra_rpc_url);
+    
+    let params = match raw {
+        Some(raw) => parse_raw(&raw)?,
+        None => params.iter().map(|p| parse_param(p)).collect(),
+    };
+    let result = rpc.call(&method, params).await?;
+    
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    Ok(())
+}
+
+/// Treat arguments as JSON when they parse (numbers, bools, arrays), else as strings
+fn parse_param(param: &str) -> Value {
+    serde_json::from_str(param).unwrap_or_else(|_| Value::String(param.to_string()))
+}
+
+/// Parse `--raw`, a JSON array sent as the params verbatim
+fn parse_raw(raw: &str) -> Result<Vec<Value>> {
+    match serde_json::from_str(raw) {
+        Ok(Value::Array(params)) => Ok(params),
+        Ok(_) => Err(ZecDevError::InvalidArgument(
+            "--raw must be a JSON array of params, e.g. '[\"txid\", 1]'".into(),
+        )),
+        Err(e) => {
+            // Point at the offending character under the line it is on
+            let line = raw.lines().nth(e.line().saturating_sub(1)).unwrap_or("");
+            let offset = line
+                .char_indices()
+                .take_while(|(i, _)| *i < e.column().saturating_sub(1))
+                .count();
+            Err(ZecDevError::InvalidArgument(format!(
+                "--raw is not valid JSON: {}\n  {}\n  {}^",
+                e,
+                line,
+                " ".repeat(offset)
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn raw_params_are_passed_verbatim() {
+        let params = parse_raw(r#"[{"addresses": ["tmA"]}, 1, "x"]"#).unwrap();
+        assert_eq!(params, vec![serde_json::json!({"addresses": ["tmA"]}), 1.into(), "x".into()]);
+    }
+    
+    #[test]
+    fn raw_parse_error_points_at_the_position() {
+        let message = parse_raw(r#"["a" 1]"#).unwrap_err().to_string();
+        assert!(message.contains("line 1 column 6"), "{}", message);
+        assert!(message.ends_with("\n  [\"a\" 1]\n       ^"), "{}", message);
+        
+        assert!(parse_raw(r#"{"a": 1}"#).is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::services::ServiceSet;
+use std::process;
+
+/// Open an interactive shell in `service`, where its data lives
+pub async fn execute(service: String) -> Result<()> {
+    let compose = DockerCompose::new()?;
+    
+    let available = compose.services()?;
+    if !available.contains(&service) {
+        return Err(ZecDevError::UnknownService {
+            name: service,
+            available,
+        });
+    }
+    if !compose.running_services()?.contains(&service) {
+        return Err(ZecDevError::ServiceNotRunning(service));
+    }
+    
+    let code = compose.shell(&service, workdir(&service))?;
+    
+    // Mirror the shell's exit status, like exec
+    if code != 0 {
+        process::exit(code);
+    }
+    
+    Ok(())
+}
+
+/// Where each bundled service keeps its state; others start in the image's default
+fn workdir(service: &str) -> Option<&'static str> {
+    ServiceSet::get(service).and_then(|s| s.workdir)
+}
@@ -0,0 +1,167 @@
+use crate::config::{Config, Network};
+use crate::docker::compose::{DockerCompose, ZEBRA_VOLUME};
+use crate::error::{Result, ZecDevError};
+use crate::preflight::{check_disk_at, check_state_disk};
+use crate::state::DevnetState;
+use crate::utils::{format_bytes, spinner, zeckit_home};
+use colored::*;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// zstd level: a good speed/size trade-off for chain state
+const COMPRESSION_LEVEL: i32 = 3;
+
+pub async fn snapshot(name: String) -> Result<()> {
+    let path = snapshot_path(&name)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    
+    let compose = DockerCompose::new()?;
+    if let Some(dir) = path.parent() {
+        check_disk_at(dir, min_free_disk(&compose)?)?;
+    }
+    
+    let raw = save_chain(&compose, &path, format!("Saving snapshot '{}'...", name))?;
+    let compressed = fs::metadata(&path)?.len();
+    
+    println!("{} Snapshot '{}' saved", "✓".green(), name);
+    println!("  {} {}", "Path:".bold(), path.display());
+    println!(
+        "  {} {} ({} uncompressed)",
+        "Size:".bold(),
+        format_bytes(compressed),
+        format_bytes(raw)
+    );
+    
+    Ok(())
+}
+
+pub async fn restore(name: String, force: bool) -> Result<()> {
+    let path = snapshot_path(&name)?;
+    if !path.exists() {
+        return Err(ZecDevError::Snapshot(format!(
+            "no snapshot named '{}' at {}",
+            name,
+            path.display()
+        ))
+        .with_hint("Create one with zecdev snapshot <NAME>."));
+    }
+    
+    let compose = DockerCompose::new()?;
+    check_state_disk(&compose, min_free_disk(&compose)?)?;
+    
+    let running = compose.is_running();
+    if running && !force {
+        return Err(ZecDevError::Snapshot("the devnet is running".into())
+            .with_hint("Stop it with zecdev down, or pass --force."));
+    }
+    
+    let mut decoder = zstd::stream::read::Decoder::new(BufReader::new(File::open(&path)?))?;
+    load_chain(&compose, &mut decoder, format!("Restoring snapshot '{}'...", name))?;
+    
+    println!(
+        "{} Restored '{}' ({}) into {}",
+        "✓".green(),
+        name,
+        format_bytes(fs::metadata(&path)?.len()),
+        ZEBRA_VOLUME
+    );
+    
+    Ok(())
+}
+
+/// Write Zebra's state to `path` as a zstd-compressed tar, stopping Zebra
+/// meanwhile so the state on disk is consistent.
+///
+/// Returns the number of uncompressed bytes saved.
+pub fn save_chain(compose: &DockerCompose, path: &Path, message: String) -> Result<u64> {
+    let was_running = compose.running_services()?.iter().any(|s| s == "zebra");
+    if was_running {
+        println!("{} Stopping zebra...", "🛑".yellow());
+        compose.stop(&["zebra"])?;
+    }
+    
+    let pb = spinner();
+    pb.enable_steady_tick(Duration::from_millis(100));
+    pb.set_message(message);
+    
+    let result = write_snapshot(compose, path);
+    pb.finish_and_clear();
+    
+    if was_running {
+        println!("{} Starting zebra...", "🚀".green());
+        compose.start(&["zebra"])?;
+    }
+    
+    result
+}
+
+/// Replace Zebra's state with the uncompressed tar read from `archive`,
+/// stopping Zebra meanwhile if it is running
+pub fn load_chain(compose: &DockerCompose, archive: &mut impl Read, message: String) -> Result<()> {
+    let zebra_running = compose.running_services()?.iter().any(|s| s == "zebra");
+    if zebra_running {
+        println!("{} Stopping zebra...", "🛑".yellow());
+        compose.stop(&["zebra"])?;
+    }
+    
+    let pb = spinner();
+    pb.enable_steady_tick(Duration::from_millis(100));
+    pb.set_message(message);
+    
+    let result = compose.import_volume(ZEBRA_VOLUME, archive);
+    pb.finish_and_clear();
+    
+    if zebra_running {
+        println!("{} Starting zebra...", "🚀".green());
+        compose.start(&["zebra"])?;
+    }
+    
+    result
+}
+
+fn write_snapshot(compose: &DockerCompose, path: &Path) -> Result<u64> {
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = zstd::stream::write::Encoder::new(file, COMPRESSION_LEVEL)?;
+    
+    let raw = match compose.export_volume(ZEBRA_VOLUME, &mut encoder) {
+        Ok(raw) => raw,
+        Err(e) => {
+            // Don't leave a truncated archive behind
+            drop(encoder);
+            let _ = fs::remove_file(path);
+            return Err(e);
+        }
+    };
+    
+    encoder.finish()?;
+    Ok(raw)
+}
+
+/// Free space to insist on, going by the network `up` last started
+pub fn min_free_disk(compose: &DockerCompose) -> Result<u64> {
+    let network = DevnetState::load(&compose.project_name()?)?
+        .map_or(Network::Regtest, |state| state.network);
+    Ok(Config::load()?.min_free_disk(network))
+}
+
+/// `~/.zeckit/snapshots/<name>.tar.zst`, rejecting names that could escape it
+fn snapshot_path(name: &str) -> Result<PathBuf> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    
+    if !valid {
+        return Err(ZecDevError::Snapshot(format!(
+            "invalid snapshot name '{}' (use letters, digits, '-', '_' or '.')",
+            name
+        )));
+    }
+    
+    Ok(zeckit_home()?.join("snapshots").join(format!("{}.tar.zst", name)))
+}
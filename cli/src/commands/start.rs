@@ -0,0 +1,66 @@
+use crate::config::Config;
+use crate::docker::compose::DockerCompose;
+use crate::docker::health::HealthChecker;
+use crate::error::{Result, ZecDevError};
+use crate::state::DevnetState;
+use crate::utils::{is_quiet, print_banner, progress_group};
+use colored::*;
+use std::time::Duration;
+
+pub async fn execute(services: Vec<String>, timeout: Option<Duration>) -> Result<()> {
+    print_banner("ZecKit - Resuming Devnet");
+    
+    let compose = DockerCompose::new()?;
+    let project = compose.project_name()?;
+    let settings = DevnetState::settings_for(&project)?;
+    let network = DevnetState::network_for(&project)?;
+    
+    // Only containers `up` created can be resumed; `start` never creates any
+    let created: Vec<String> = compose.ps()?.into_iter().map(|s| s.name).collect();
+    
+    let targets: Vec<String> = if services.is_empty() {
+        if created.is_empty() {
+            return Err(ZecDevError::DevnetNotRunning
+                .with_hint("There is nothing to resume; create the devnet with zecdev up."));
+        }
+        created
+    } else {
+        let available = compose.services()?;
+        for service in &services {
+            if !available.contains(service) {
+                return Err(ZecDevError::UnknownService {
+                    name: service.clone(),
+                    available,
+                });
+            }
+            if !created.contains(service) {
+                return Err(ZecDevError::ServiceNotRunning(service.clone())
+                    .with_hint("It has no container yet; create it with zecdev up."));
+            }
+        }
+        services
+    };
+    
+    let targets: Vec<&str> = targets.iter().map(|s| s.as_str()).collect();
+    if !is_quiet() {
+        println!("{} Starting: {}", "▶".green(), targets.join(", "));
+    }
+    compose.start(&targets)?;
+    compose.check_all_running(&targets)?;
+    
+    // Paused containers come back quickly, but nothing is ready until it answers
+    let mut checker = HealthChecker::new()
+        .with_settings(settings)
+        .with_network(network)
+        .with_probes(Config::load()?.health_probes()?);
+    if let Some(timeout) = timeout {
+        checker = checker.with_timeout(timeout);
+    }
+    checker.wait_for_all(&targets, &progress_group()).await?;
+    
+    if !is_quiet() {
+        println!("{}", "✓ All services ready!".green());
+        println!();
+    }
+    Ok(())
+}
@@ -1,42 +1,209 @@
-use crate::docker::compose::DockerCompose;
+use crate::backend::BackendClient;
+use crate::config::settings::Settings;
+use crate::config::Config;
+use crate::docker::compose::{DockerCompose, ServiceStatus};
 use crate::error::Result;
+use crate::rpc::ZebraRpc;
+use crate::services::ServiceSet;
+use crate::state::DevnetState;
+use crate::utils::{disable_color, print_banner};
 use colored::*;
+use console::Term;
 use reqwest::Client;
+use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashMap;
+use tokio::time::{sleep, Duration, Instant};
 
-pub async fn execute() -> Result<()> {
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!("{}", "  ZecKit - Devnet Status".cyan().bold());
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!();
-    
+#[derive(Serialize)]
+struct StatusReport {
+    services: Vec<ServiceStatus>,
+}
+
+/// Changes kept on screen by `--watch`
+const MAX_TRANSITIONS: usize = 5;
+
+pub async fn execute(json: bool, watch: Option<Duration>) -> Result<()> {
     let compose = DockerCompose::new()?;
-    let containers = compose.ps()?;
     
-    // Display container status
-    for container in containers {
-        let status_color = if container.contains("Up") {
-            "green"
+    if json {
+        // Nothing but the document on stdout so it can be piped into jq
+        colored::control::set_override(false);
+        let report = StatusReport { services: compose.ps()? };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    
+    // Probe the endpoints and backends `up` recorded, if it has run
+    let state = DevnetState::load(&compose.project_name()?)?;
+    let (settings, backends) = match state {
+        Some(state) => (state.settings, state.backends),
+        None => (Settings::from_config(&Config::load()?), Vec::new()),
+    };
+    let client = Client::new();
+    let target = Target { settings: &settings, backends: &backends, client: &client };
+    
+    if let Some(interval) = watch {
+        return watch_status(&compose, &target, interval).await;
+    }
+    
+    print_banner("ZecKit - Devnet Status");
+    print_status(&compose.ps()?, &target).await;
+    Ok(())
+}
+
+/// Re-render the status every `interval` until Ctrl-C, like `watch docker ps`
+async fn watch_status(compose: &DockerCompose, target: &Target<'_>, interval: Duration) -> Result<()> {
+    let term = Term::stdout();
+    let interactive = term.is_term();
+    
+    // Redirected output gets plain text, one refresh after another
+    if !interactive {
+        disable_color();
+    }
+    let _cursor = interactive.then(|| HiddenCursor::new(&term));
+    
+    let start = Instant::now();
+    let mut previous: HashMap<String, String> = HashMap::new();
+    let mut changes: Vec<String> = Vec::new();
+    
+    loop {
+        let services = compose.ps()?;
+        let elapsed = start.elapsed().as_secs();
+        for (name, from, to) in transitions(&previous, &services) {
+            changes.push(format!("+{}s  {} {} → {}", elapsed, name, from, to));
+        }
+        if changes.len() > MAX_TRANSITIONS {
+            changes.drain(..changes.len() - MAX_TRANSITIONS);
+        }
+        previous = services
+            .iter()
+            .map(|s| (s.name.clone(), condition(s).to_string()))
+            .collect();
+        
+        if interactive {
+            let _ = term.clear_screen();
         } else {
-            "red"
-        };
+            println!("--- +{}s ---", elapsed);
+        }
+        print_banner("ZecKit - Devnet Status");
+        println!("  Every {:?} - Ctrl-C to stop", interval);
+        println!();
+        print_status(&services, target).await;
+        
+        if !changes.is_empty() {
+            println!("  {}", "Changes".bold());
+            for change in &changes {
+                println!("    {}", change);
+            }
+            println!();
+        }
         
-        println!("  {}", container.color(status_color));
+        tokio::select! {
+            _ = sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
     }
     
-    println!();
+    Ok(())
+}
+
+/// Hides the cursor while a live view redraws, and always gives it back
+pub struct HiddenCursor(Term);
+
+impl HiddenCursor {
+    pub fn new(term: &Term) -> Self {
+        let _ = term.hide_cursor();
+        Self(term.clone())
+    }
+}
+
+impl Drop for HiddenCursor {
+    fn drop(&mut self) {
+        let _ = self.0.show_cursor();
+    }
+}
+
+/// What a service's line reports: its health check result, else its state
+fn condition(service: &ServiceStatus) -> &str {
+    if service.health == "none" {
+        &service.state
+    } else {
+        &service.health
+    }
+}
+
+/// Services whose condition changed since the last refresh, as (name, from, to)
+fn transitions(previous: &HashMap<String, String>, services: &[ServiceStatus]) -> Vec<(String, String, String)> {
+    services
+        .iter()
+        .filter_map(|s| {
+            let from = previous.get(&s.name)?;
+            let to = condition(s);
+            (from != to).then(|| (s.name.clone(), from.clone(), to.to_string()))
+        })
+        .collect()
+}
+
+/// What the non-container checks probe
+struct Target<'a> {
+    settings: &'a Settings,
+    /// Backends `up` started (`lwd`, `zaino`)
+    backends: &'a [String],
+    client: &'a Client,
+}
+
+/// Container table followed by the Zebra, faucet and backend checks
+async fn print_status(services: &[ServiceStatus], target: &Target<'_>) {
+    let settings = target.settings;
+    // Display container status
+    for service in services {
+        let line = format!(
+            "{:<14} {:<10} {:<10} {}",
+            service.name,
+            service.state,
+            service.health,
+            service.ports.join(", ")
+        );
+        
+        if service.state == "running" {
+            println!("  {}", line.green());
+        } else {
+            println!("  {}", line.red());
+        }
+    }
     
-    // Check service health
-    let client = Client::new();
+    println!();
     
     // Zebra
-    print_service_status(&client, "Zebra", "http://127.0.0.1:8232").await;
+    let rpc = ZebraRpc::new(settings.zebra_rpc_url.clone());
+    match rpc.get_blockchain_info().await {
+        Ok(info) => {
+            println!("  {} {} - Height: {} ({})", "✓".green(), "Zebra".bold(), info.blocks, info.chain);
+        }
+        Err(_) => {
+            println!("  {} {} - Not responding", "✗".red(), "Zebra".bold());
+        }
+    }
     
     // Faucet
-    print_service_status(&client, "Faucet", "http://127.0.0.1:8080/stats").await;
+    let stats_url = format!("{}/stats", settings.faucet_api_url);
+    print_service_status(target.client, "Faucet", &stats_url).await;
+    
+    // Backends
+    for backend in target.backends {
+        let name = ServiceSet::backend(backend).map_or(backend.as_str(), |s| s.label);
+        let probe = match BackendClient::new(settings.backend_url_for(backend)) {
+            Ok(client) => client.probe(backend).await,
+            Err(e) => Err(e),
+        };
+        match probe {
+            Ok(()) => println!("  {} {} - Ready", "✓".green(), name.bold()),
+            Err(_) => println!("  {} {} - Not responding", "✗".red(), name.bold()),
+        }
+    }
     
     println!();
-    Ok(())
 }
 
 async fn print_service_status(client: &Client, name: &str, url: &str) {
@@ -45,11 +212,11 @@ async fn print_service_status(client: &Client, name: &str, url: &str) {
             if let Ok(json) = resp.json::<Value>().await {
                 println!("  {} {} - {}", "✓".green(), name.bold(), format_json(&json));
             } else {
-                println!("  {} {} - {}", "✓".green(), name.bold(), "OK");
+                println!("  {} {} - OK", "✓".green(), name.bold());
             }
         }
         _ => {
-            println!("  {} {} - {}", "✗".red(), name.bold(), "Not responding");
+            println!("  {} {} - Not responding", "✗".red(), name.bold());
         }
     }
 }
@@ -62,4 +229,38 @@ fn format_json(json: &Value) -> String {
     } else {
         "Running".to_string()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn service(name: &str, state: &str, health: &str) -> ServiceStatus {
+        ServiceStatus {
+            name: name.into(),
+            container_id: String::new(),
+            state: state.into(),
+            health: health.into(),
+            status: String::new(),
+            ports: Vec::new(),
+        }
+    }
+    
+    #[test]
+    fn health_changes_are_reported_between_refreshes() {
+        let previous = HashMap::from([
+            ("zebra".to_string(), "starting".to_string()),
+            ("faucet".to_string(), "running".to_string()),
+        ]);
+        let current = vec![
+            service("zebra", "running", "healthy"),
+            service("faucet", "running", "none"),
+            service("zaino", "running", "starting"),
+        ];
+        
+        assert_eq!(
+            transitions(&previous, &current),
+            vec![("zebra".to_string(), "starting".to_string(), "healthy".to_string())]
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::utils::{is_quiet, print_banner};
+use colored::*;
+
+pub async fn execute(services: Vec<String>) -> Result<()> {
+    print_banner("ZecKit - Pausing Devnet");
+    
+    let compose = DockerCompose::new()?;
+    let running = compose.running_services()?;
+    
+    // Default to everything that is up; named services must exist and be running
+    let targets: Vec<String> = if services.is_empty() {
+        if running.is_empty() {
+            return Err(ZecDevError::DevnetNotRunning);
+        }
+        running
+    } else {
+        let available = compose.services()?;
+        for service in &services {
+            if !available.contains(service) {
+                return Err(ZecDevError::UnknownService {
+                    name: service.clone(),
+                    available,
+                });
+            }
+            if !running.contains(service) {
+                return Err(ZecDevError::ServiceNotRunning(service.clone()));
+            }
+        }
+        services
+    };
+    
+    let targets: Vec<&str> = targets.iter().map(|s| s.as_str()).collect();
+    if !is_quiet() {
+        println!("{} Stopping: {}", "⏸".yellow(), targets.join(", "));
+    }
+    
+    // Containers and volumes stay in place, so `start` resumes with chain state intact
+    compose.stop(&targets)?;
+    
+    if !is_quiet() {
+        println!("{} Stopped - resume with zecdev start", "✓".green());
+    }
+    Ok(())
+}
@@ -1,81 +1,66 @@
-use crate::error::Result;
+use crate::backend::BackendClient;
+use crate::commands::up::{selected_backends, Backend};
+use crate::config::settings::Settings;
+use crate::config::Config;
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::faucet::{FaucetClient, ZATS_PER_ZEC};
+use crate::rpc::ZebraRpc;
+use crate::state::DevnetState;
+use crate::utils::print_banner;
 use colored::*;
 use reqwest::Client;
 use serde_json::Value;
 
-pub async fn execute() -> Result<()> {
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!("{}", "  ZecKit - Running Smoke Tests".cyan().bold());
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!();
-
+pub async fn execute(backends: Vec<Backend>) -> Result<()> {
+    print_banner("ZecKit - Running Smoke Tests");
+
+    // Test what `up` started unless told otherwise
+    let state = DevnetState::load(&DockerCompose::new()?.project_name()?)?;
+    let backends = if backends.is_empty() {
+        state.as_ref().ok_or(ZecDevError::DevnetNotFound)?.backend_names()
+    } else {
+        selected_backends(&backends)
+    };
+    let settings = match &state {
+        Some(state) => state.settings.clone(),
+        None => Settings::from_config(&Config::load()?),
+    };
     let client = Client::new();
+    let rpc = ZebraRpc::new(settings.zebra_rpc_url.clone());
+    let faucet = FaucetClient::new(&settings);
+    let total = 5 + backends.len();
     let mut passed = 0;
     let mut failed = 0;
 
     // Test 1: Zebra RPC
-    print!("  [1/5] Zebra RPC connectivity... ");
-    match test_zebra_rpc(&client).await {
-        Ok(_) => {
-            println!("{}", "✓ PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "✗ FAIL".red(), e);
-            failed += 1;
-        }
-    }
+    print!("  [1/{}] Zebra RPC getblockchaininfo... ", total);
+    record(test_zebra_rpc(&rpc).await, &mut passed, &mut failed);
 
     // Test 2: Faucet Health
-    print!("  [2/5] Faucet health check... ");
-    match test_faucet_health(&client).await {
-        Ok(_) => {
-            println!("{}", "✓ PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "✗ FAIL".red(), e);
-            failed += 1;
-        }
-    }
+    print!("  [2/{}] Faucet health check... ", total);
+    record(test_faucet_health(&client, &settings).await, &mut passed, &mut failed);
 
     // Test 3: Faucet Stats
-    print!("  [3/5] Faucet stats endpoint... ");
-    match test_faucet_stats(&client).await {
-        Ok(_) => {
-            println!("{}", "✓ PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "✗ FAIL".red(), e);
-            failed += 1;
-        }
-    }
+    print!("  [3/{}] Faucet stats endpoint... ", total);
+    record(test_faucet_stats(&client, &settings).await, &mut passed, &mut failed);
 
     // Test 4: Faucet Address
-    print!("  [4/5] Faucet address retrieval... ");
-    match test_faucet_address(&client).await {
-        Ok(_) => {
-            println!("{}", "✓ PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "✗ FAIL".red(), e);
-            failed += 1;
-        }
-    }
+    print!("  [4/{}] Faucet address retrieval... ", total);
+    record(faucet.get_address().await.map(|_| ()), &mut passed, &mut failed);
 
     // Test 5: Faucet Request (mock transaction)
-    print!("  [5/5] Faucet funding request... ");
-    match test_faucet_request(&client).await {
-        Ok(_) => {
-            println!("{}", "✓ PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "✗ FAIL".red(), e);
-            failed += 1;
-        }
+    print!("  [5/{}] Faucet funding request... ", total);
+    record(test_faucet_request(&client, &settings, &faucet).await, &mut passed, &mut failed);
+
+    // Test 6+: Light-client backends
+    for (i, backend) in backends.iter().enumerate() {
+        print!("  [{}/{}] {} backend request... ", 6 + i, total, backend);
+        let probe = match BackendClient::new(settings.backend_url_for(backend).to_string()) {
+            Ok(backend_client) => backend_client.probe(backend).await,
+            Err(e) => Err(e),
+        };
+        record(probe, &mut passed, &mut failed);
     }
 
     println!();
@@ -94,30 +79,27 @@ pub async fn execute() -> Result<()> {
     Ok(())
 }
 
-async fn test_zebra_rpc(client: &Client) -> Result<()> {
-    let resp = client
-        .post("http://127.0.0.1:8232")
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": "test",
-            "method": "getblockcount",
-            "params": []
-        }))
-        .send()
-        .await?;
-
-    if !resp.status().is_success() {
-        return Err(crate::error::ZecDevError::HealthCheck(
-            "Zebra RPC not responding".into()
-        ));
+fn record(result: Result<()>, passed: &mut u32, failed: &mut u32) {
+    match result {
+        Ok(_) => {
+            println!("{}", "✓ PASS".green());
+            *passed += 1;
+        }
+        Err(e) => {
+            println!("{} {}", "✗ FAIL".red(), e);
+            *failed += 1;
+        }
     }
+}
 
+async fn test_zebra_rpc(rpc: &ZebraRpc) -> Result<()> {
+    rpc.get_blockchain_info().await?;
     Ok(())
 }
 
-async fn test_faucet_health(client: &Client) -> Result<()> {
+async fn test_faucet_health(client: &Client, settings: &Settings) -> Result<()> {
     let resp = client
-        .get("http://127.0.0.1:8080/health")
+        .get(format!("{}/health", settings.faucet_api_url))
         .send()
         .await?;
 
@@ -130,9 +112,9 @@ async fn test_faucet_health(client: &Client) -> Result<()> {
     Ok(())
 }
 
-async fn test_faucet_stats(client: &Client) -> Result<()> {
+async fn test_faucet_stats(client: &Client, settings: &Settings) -> Result<()> {
     let resp = client
-        .get("http://127.0.0.1:8080/stats")
+        .get(format!("{}/stats", settings.faucet_api_url))
         .send()
         .await?;
 
@@ -161,32 +143,10 @@ async fn test_faucet_stats(client: &Client) -> Result<()> {
     Ok(())
 }
 
-async fn test_faucet_address(client: &Client) -> Result<()> {
-    let resp = client
-        .get("http://127.0.0.1:8080/address")
-        .send()
-        .await?;
-
-    if !resp.status().is_success() {
-        return Err(crate::error::ZecDevError::HealthCheck(
-            "Could not get faucet address".into()
-        ));
-    }
-
-    let json: Value = resp.json().await?;
-    if json.get("address").is_none() {
-        return Err(crate::error::ZecDevError::HealthCheck(
-            "Invalid address response".into()
-        ));
-    }
-
-    Ok(())
-}
-
-async fn test_faucet_request(client: &Client) -> Result<()> {
+async fn test_faucet_request(client: &Client, settings: &Settings, faucet: &FaucetClient) -> Result<()> {
     // First ensure faucet has funds
     let _ = client
-        .post("http://127.0.0.1:8080/admin/add-funds")
+        .post(format!("{}/admin/add-funds", settings.faucet_api_url))
         .json(&serde_json::json!({
             "amount": 100.0,
             "secret": "dev-secret-change-in-production"
@@ -195,27 +155,15 @@ async fn test_faucet_request(client: &Client) -> Result<()> {
         .await;
 
     // Test funding request
-    let resp = client
-        .post("http://127.0.0.1:8080/request")
-        .json(&serde_json::json!({
-            "address": "tmBsTi2xWTjUdEXnuTceL7fecEQKeWu4u6d",
-            "amount": 1.0
-        }))
-        .send()
+    let receipt = faucet
+        .request_funds("tmBsTi2xWTjUdEXnuTceL7fecEQKeWu4u6d", Some(ZATS_PER_ZEC))
         .await?;
 
-    if !resp.status().is_success() {
-        return Err(crate::error::ZecDevError::HealthCheck(
-            "Faucet request failed".into()
-        ));
-    }
-
-    let json: Value = resp.json().await?;
-    if json.get("txid").is_none() {
+    if receipt.txid.is_empty() {
         return Err(crate::error::ZecDevError::HealthCheck(
             "No TXID returned".into()
         ));
     }
 
     Ok(())
-}
\ No newline at end of file
+}
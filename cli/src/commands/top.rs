@@ -0,0 +1,69 @@
+use crate::commands::status::HiddenCursor;
+use crate::docker::compose::{DockerCompose, ServiceStats};
+use crate::error::Result;
+use crate::utils::{disable_color, print_banner, print_table};
+use console::Term;
+use tokio::time::{sleep, Duration, Instant};
+
+pub async fn execute(watch: Option<Duration>) -> Result<()> {
+    let compose = DockerCompose::new()?;
+    
+    let Some(interval) = watch else {
+        print_banner("ZecKit - Resource Usage");
+        print_stats(&compose.stats()?);
+        return Ok(());
+    };
+    
+    let term = Term::stdout();
+    let interactive = term.is_term();
+    // Redirected output gets plain text, one refresh after another
+    if !interactive {
+        disable_color();
+    }
+    let _cursor = interactive.then(|| HiddenCursor::new(&term));
+    let start = Instant::now();
+    
+    loop {
+        // Sampled before clearing, since `docker stats` takes a moment to answer
+        let stats = compose.stats()?;
+        if interactive {
+            let _ = term.clear_screen();
+        } else {
+            println!("--- +{}s ---", start.elapsed().as_secs());
+        }
+        print_banner("ZecKit - Resource Usage");
+        println!("  Every {:?} - Ctrl-C to stop", interval);
+        println!();
+        print_stats(&stats);
+        
+        tokio::select! {
+            _ = sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    
+    Ok(())
+}
+
+fn print_stats(stats: &[ServiceStats]) {
+    if stats.is_empty() {
+        println!("  No running ZecKit containers - run zecdev up to start the devnet");
+        return;
+    }
+    
+    let rows: Vec<Vec<String>> = stats
+        .iter()
+        .map(|s| {
+            vec![
+                s.name.clone(),
+                format!("{:.1}%", s.cpu_percent),
+                s.memory.clone(),
+                format!("{:.1}%", s.memory_percent),
+                s.net_io.clone(),
+                s.block_io.clone(),
+            ]
+        })
+        .collect();
+    
+    print_table(&["SERVICE", "CPU", "MEMORY", "MEM %", "NET I/O", "BLOCK I/O"], &rows);
+}
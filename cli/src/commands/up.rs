@@ -1,80 +1,1028 @@
-use crate::docker::compose::DockerCompose;
+use crate::config::settings::Settings;
+use crate::config::chain::{self, check_activation_heights, ActivationHeight};
+use crate::config::file::check_faucet_amount;
+use crate::config::peers::{self, Peer};
+use crate::config::{Config, LogLevel, Network};
+use crate::commands::env::write_env_file;
+use crate::commands::pull::pull_services;
+use crate::commands::{faucet, wallet, watch};
+use crate::docker::compose::{DockerCompose, ServiceStatus};
 use crate::docker::health::HealthChecker;
-use crate::error::Result;
+use crate::docker::limits::ResourceLimits;
+use crate::docker::mounts::{self, BindMount};
+use crate::error::{list_or_none, Result, ZecDevError};
+use crate::faucet::{load_batch, zec_to_zat};
+use crate::lock::{self, lock_project};
+use crate::preflight::{check_disk, check_ports, check_state_disk};
+use crate::rpc::RpcAuth;
+use crate::services::ServiceSet;
+use crate::state::DevnetState;
+use crate::wallet::AddressKind;
+use crate::utils::{is_quiet, is_verbose, log_command, print_banner, print_error, progress_group, spinner, Verbosity};
+use clap::ValueEnum;
 use colored::*;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::MultiProgress;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{self, Stdio};
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncBufReadExt;
+use tokio::time::Instant;
 
-pub async fn execute(backend: String, fresh: bool) -> Result<()> {
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!("{}", "  ZecKit - Starting Devnet".cyan().bold());
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!();
+/// Flags for `zecdev up` beyond the backend and network
+#[derive(Debug)]
+pub struct UpOptions {
+    pub fresh: bool,
+    /// Recreate every container, even ones already running and healthy
+    pub force_recreate: bool,
+    /// Take over the project's lock from a crashed or stuck run
+    pub force: bool,
+    pub pull: bool,
+    /// Show `--pull` as one summary line instead of a bar per image
+    pub quiet_pull: bool,
+    pub build: bool,
+    pub timeout: Option<Duration>,
+    pub startup_timeout: Option<Duration>,
+    /// How long Zebra's sync may stand still before `up` calls it stalled
+    pub sync_stall_timeout: Option<Duration>,
+    pub max_attempts: Option<u32>,
+    /// ZEC the faucet must hold before it counts as ready
+    pub require_faucet_funds: Option<f64>,
+    pub log_level: Option<LogLevel>,
+    pub limits: ResourceLimits,
+    /// Bind mounts from `--mount`
+    pub mounts: Vec<BindMount>,
+    /// Regtest activation heights from `--activation-height`, over zeckit.toml's
+    pub activation_heights: Vec<ActivationHeight>,
+    /// Testnet peers Zebra dials first, from `--peers`
+    pub peers: Vec<Peer>,
+    pub cleanup_on_interrupt: bool,
+    /// Start Zebra and wait for it before the services that need it
+    pub ordered_start: bool,
+    pub env_out: Option<PathBuf>,
+    /// Stream logs during health checks, then stop once everything is healthy
+    pub follow_until_healthy: bool,
+    pub attach: bool,
+    /// While attached, how many times each crashed service is restarted
+    pub restart_on_failure: Option<u32>,
+    pub watch: bool,
+    /// Compose profiles whose services start alongside the devnet
+    pub profiles: Vec<String>,
+    /// Services from `--optional`, whose failure is reported instead of fatal
+    pub optional: Vec<String>,
+    pub faucet_amount: Option<f64>,
+    pub faucet_cooldown: Option<Duration>,
+    pub create_wallet: bool,
+    pub fund: Option<PathBuf>,
+    pub summary_json: Option<PathBuf>,
+}
+
+/// Verbosity for `up`. `--summary-json -` needs stdout for the JSON document
+/// alone, so it leaves out the human output as `--quiet` does.
+pub fn verbosity(quiet: bool, verbose: bool, summary_json: Option<&Path>) -> Verbosity {
+    Verbosity::from_flags(quiet || summary_json == Some(Path::new("-")), verbose)
+}
+
+pub async fn execute(backends: Vec<Backend>, network: Option<Network>, options: UpOptions) -> Result<()> {
+    print_banner("ZecKit - Starting Devnet");
+    let quiet = is_quiet();
     
-    let compose = DockerCompose::new()?;
+    let config = Config::load()?;
+    let backends = config.resolve_backends(backends);
+    let network = config.resolve_network(network);
+    let settings = Settings::from_config(&config);
+    // A malformed funding file should fail before minutes of startup, not after
+    if let Some(path) = &options.fund {
+        load_batch(path)?;
+    }
+    let mut compose = DockerCompose::new()?.with_env(network.compose_env());
+    let lock = lock_project(&compose, "up", options.force, "--force")?;
+    if let Some(level) = options.log_level {
+        compose = compose.with_env(level.compose_env());
+    }
+    // Later values win, so these override zeckit.toml's faucet settings
+    if let Some(amount) = options.faucet_amount {
+        check_faucet_amount(amount)?;
+        compose = compose.with_env(vec![("FAUCET_AMOUNT_DEFAULT", amount.to_string())]);
+    }
+    if let Some(cooldown) = options.faucet_cooldown {
+        compose = compose.with_env(vec![("FAUCET_COOLDOWN_SECS", cooldown.as_secs().to_string())]);
+    }
+    if !options.limits.is_empty() {
+        check_services(&options.limits.services())?;
+        let path = compose.write_generated("resource-limits.yml", &options.limits.to_override_yaml())?;
+        if is_verbose() {
+            eprintln!("Resource limits ({}):", path.display());
+            for line in options.limits.summary() {
+                eprintln!("  {}", line);
+            }
+        }
+        compose = compose.with_override(&path);
+    }
     
-    // Fresh start if requested
-    if fresh {
-        println!("{}", "🧹 Cleaning up old data...".yellow());
-        compose.down(true)?;
+    let heights = config.regtest.with_overrides(&options.activation_heights);
+    if !heights.is_empty() {
+        if network != Network::Regtest {
+            return Err(ZecDevError::Config(format!("activation heights only apply to regtest, not {}", network))
+                .with_hint("Drop --activation-height and [regtest.activation_heights], or use --network regtest."));
+        }
+        check_activation_heights(&heights)?;
+        
+        let zebra_config = chain::zebra_config(&Network::Regtest.read_zebra_config()?, &heights)?;
+        let path = compose.write_zebra_config_override(Network::Regtest, &zebra_config, "chain-params.yml")?;
+        if is_verbose() {
+            eprintln!("Activation heights ({}):", path.display());
+            for (upgrade, height) in &heights {
+                eprintln!("  {}: {}", upgrade, height);
+            }
+        }
+        compose = compose.with_override(&path);
+    }
+    
+    if !options.peers.is_empty() {
+        if network != Network::Testnet {
+            return Err(ZecDevError::Config(format!("--peers only applies to testnet, not {}", network))
+                .with_hint("A regtest node makes its own blocks and never syncs from peers. Drop --peers, or use --network testnet."));
+        }
+        
+        let peers = peers::dedup(&options.peers);
+        let zebra_config = peers::zebra_config(&Network::Testnet.read_zebra_config()?, &peers)?;
+        let path = compose.write_zebra_config_override(Network::Testnet, &zebra_config, "peers.yml")?;
+        if is_verbose() {
+            eprintln!("Initial peers ({}):", path.display());
+            for peer in &peers {
+                eprintln!("  {}", peer);
+            }
+        }
+        compose = compose.with_override(&path);
+    }
+    
+    // After the generated Zebra configs, so a mounted zebrad.toml replaces them
+    if !options.mounts.is_empty() {
+        check_services(&mounts::services(&options.mounts))?;
+        let resolved = mounts::resolve(&options.mounts)?;
+        let path = compose.write_generated("mounts.yml", &mounts::to_override_yaml(&resolved))?;
+        if is_verbose() {
+            eprintln!("Mounts ({}):", path.display());
+            for mount in &resolved {
+                eprintln!("  {}: -v {}", mount.service, mount.spec());
+            }
+        }
+        compose = compose.with_override(&path);
+    }
+    
+    if !options.profiles.is_empty() {
+        let available = compose.profiles()?;
+        if let Some(unknown) = options.profiles.iter().find(|p| !available.contains(p)) {
+            return Err(ZecDevError::Config(format!("unknown compose profile '{}'", unknown))
+                .with_hint(format!("Profiles in the compose file: {}", list_or_none(&available))));
+        }
+        compose = compose.with_profiles(&options.profiles);
     }
     
     // Determine services to start
-    let services = match backend.as_str() {
-        "lwd" => vec!["zebra", "faucet", "lightwalletd"],
-        "zaino" => vec!["zebra", "faucet", "zaino"],
-        _ => vec!["zebra", "faucet"],
+    let backends = selected_backends(&backends);
+    config.check_backend_ports(&backends)?;
+    let mut services = ServiceSet::names_for(&backends);
+    let extras = profile_extras(&compose, &services, &options.profiles)?;
+    services.extend(extras.iter().map(String::as_str));
+    let optional = config.optional_services(&options.optional)?;
+    let is_optional = |service: &str| optional.iter().any(|o| o == service);
+    
+    // Catch port clashes now rather than as a health check timeout. Services
+    // that are already up legitimately hold their ports.
+    let running = compose.running_services()?;
+    let ports: Vec<(u16, &str)> = services
+        .iter()
+        .filter(|s| !running.iter().any(|r| r == *s))
+        .flat_map(|s| config.ports_for(s).into_iter().map(move |p| (p, *s)))
+        .collect();
+    check_ports(&ports)?;
+    
+    // Healthy services are left alone, so re-running `up` only starts what's
+    // missing. Fresh, pulled or rebuilt services go through compose, which
+    // recreates whatever changed.
+    let ready = if options.fresh || options.force_recreate || options.pull || options.build {
+        Vec::new()
+    } else {
+        already_ready(&compose.ps()?, &services)
     };
+    let to_start: Vec<&str> = services.iter().copied().filter(|s| !ready.contains(s)).collect();
     
-    println!("{} Starting services: {}", "🚀".green(), services.join(", "));
-    compose.up(&services)?;
+    if compose.is_dry_run() {
+        return print_dry_run(&compose, &services, &to_start, &options);
+    }
     
-    // Health checks with progress
-    let pb = ProgressBar::new_spinner();
-    pb.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg}")
-            .unwrap()
-    );
+    let deadline = Deadline::start(options.startup_timeout);
     
-    pb.set_message("Waiting for Zebra...");
-    let checker = HealthChecker::new();
-    checker.wait_for_zebra(&pb).await?;
+    // Fresh start if requested
+    if options.fresh {
+        if !quiet {
+            println!("{}", "🧹 Cleaning up old data...".yellow());
+        }
+        let remover = compose.clone();
+        deadline
+            .blocking("removing old data", &services, move || remover.down(true))
+            .await?;
+        // Checked once the old state is gone, since that space comes back
+        check_state_disk(&compose, config.min_free_disk(network))?;
+    }
     
-    pb.set_message("Waiting for Faucet...");
-    checker.wait_for_faucet(&pb).await?;
+    if options.pull {
+        check_disk(config.min_free_disk(network))?;
+        if !quiet {
+            println!("{} Pulling latest images...", "📦".cyan());
+        }
+        let (compose, to_pull) = (compose.clone(), owned(&services));
+        deadline
+            .blocking("pulling images", &services, move || {
+                pull_services(&compose, &borrowed(&to_pull), options.quiet_pull)
+            })
+            .await?;
+    }
     
-    if backend != "none" {
-        pb.set_message(format!("Waiting for {}...", backend));
-        checker.wait_for_backend(&backend, &pb).await?;
+    if options.build {
+        let (compose, to_build) = (compose.clone(), owned(&services));
+        deadline
+            .blocking("building images", &services, move || build_services(&compose, &borrowed(&to_build)))
+            .await?;
     }
     
-    pb.finish_with_message("✓ All services ready!".green().to_string());
+    let mut checker = HealthChecker::new()
+        .with_settings(settings.clone())
+        .with_network(network)
+        .with_probes(config.health_probes()?);
+    if let Some(timeout) = options.timeout {
+        checker = checker.with_timeout(timeout);
+    }
+    if let Some(attempts) = options.max_attempts {
+        checker = checker.with_max_attempts(attempts);
+    }
+    if let Some(window) = options.sync_stall_timeout {
+        checker = checker.with_stall_window(window);
+    }
+    if let Some(min) = options.require_faucet_funds {
+        // A bare flag asks for any balance at all
+        checker = checker.with_faucet_funds(zec_to_zat(min).max(1));
+    }
     
-    // Display connection info
-    print_connection_info(&backend);
+    // Each stage starts once the previous one is healthy; within a stage,
+    // services start and are health checked together
+    let multi = progress_group();
+    if !quiet {
+        for service in &ready {
+            println!("{} {} already ready", "✓".green(), service);
+        }
+    }
+    let mut started: Vec<&str> = ready.clone();
+    // Only these abort `up` when they crash; optional services are checked on their own
+    let mut required: Vec<&str> = ready.iter().copied().filter(|s| !is_optional(s)).collect();
+    let mut failed: Vec<(String, ZecDevError)> = Vec::new();
+    if to_start.is_empty() {
+        DevnetState::new(&backends, network, &settings, compose.layers()).save(&compose.project_name()?)?;
+    }
+    for (i, stage) in start_stages(&to_start, options.ordered_start).into_iter().enumerate() {
+        let stage_start = unix_now();
+        if !quiet {
+            if started.is_empty() {
+                println!("{} Starting services on {}: {}", "🚀".green(), network, stage.join(", "));
+            } else {
+                println!("{} Starting services that need {}: {}", "🚀".green(), started.join(", "), stage.join(", "));
+            }
+        }
+        let (starter, stage_services) = (compose.clone(), owned(&stage));
+        let recreate = options.force_recreate;
+        deadline
+            .blocking("starting containers", &services, move || {
+                let stage_services = borrowed(&stage_services);
+                if recreate {
+                    starter.recreate(&stage_services)
+                } else {
+                    starter.up(&stage_services)
+                }
+            })
+            .await?;
+        
+        // Recorded before health checks, so later commands find a devnet that came up unhealthy too
+        if i == 0 {
+            DevnetState::new(&backends, network, &settings, compose.layers()).save(&compose.project_name()?)?;
+        }
+        started.extend(&stage);
+        let (stage_required, stage_optional): (Vec<&str>, Vec<&str>) =
+            stage.iter().partition(|s| !is_optional(s));
+        required.extend(&stage_required);
+        
+        // A container that dies straight away would otherwise only show up as a
+        // health check timeout minutes later
+        compose.check_all_running(&required)?;
+        let mut to_check = Vec::new();
+        for service in stage_optional {
+            match compose.check_all_running(&[service]) {
+                Ok(()) => to_check.push(service),
+                Err(e) => failed.push((service.to_string(), e)),
+            }
+        }
+        
+        let follower = if options.follow_until_healthy {
+            Some(LogFollower::spawn(&compose, &started, stage_start, &multi)?)
+        } else {
+            None
+        };
+        let health = deadline.run(async {
+            tokio::select! {
+                (result, soft) = async {
+                    tokio::join!(
+                        checker.wait_for_all(&stage_required, &multi),
+                        checker.wait_for_optional(&to_check, &multi)
+                    )
+                } => {
+                    failed.extend(soft);
+                    result
+                }
+                result = watch_for_crashes(&compose, &required) => {
+                    let _ = multi.clear();
+                    result
+                }
+                _ = tokio::signal::ctrl_c() => {
+                    handle_interrupt(&compose, &multi, options.cleanup_on_interrupt);
+                }
+            }
+        });
+        let health = health.await;
+        // Stopped either way; lines already printed stay on screen, so a
+        // failure is shown next to the logs that explain it
+        if let Some(follower) = follower {
+            follower.stop().await;
+        }
+        match health {
+            Some(result) => result?,
+            None => {
+                let _ = multi.clear();
+                return Err(deadline.expired("waiting for health checks", &services, checker.ready_services()));
+            }
+        }
+    }
+    if options.follow_until_healthy && !quiet {
+        println!("{}", "Stopped following logs; services keep running.".dimmed());
+    }
+    let services: Vec<&str> = services
+        .into_iter()
+        .filter(|s| !failed.iter().any(|(f, _)| f == s))
+        .collect();
+    
+    // Display connection info; with --quiet the endpoints are all that's printed,
+    // unless the JSON summary is taking stdout instead
+    let summary_to_stdout = options.summary_json.as_deref() == Some(Path::new("-"));
+    if quiet {
+        if !summary_to_stdout {
+            print!("{}", settings.to_dotenv(&backends));
+        }
+    } else {
+        if failed.is_empty() {
+            println!("{}", "✓ All services ready!".green());
+        } else {
+            println!("{}", "✓ Required services ready!".green());
+        }
+        print_connection_info(&backends, network, &settings);
+        if !extras.is_empty() {
+            print_extras(&compose.ps()?, &extras);
+        }
+    }
+    
+    // On stderr, so the warning survives --quiet and stays out of the dotenv output
+    print_failed_optional(&failed);
+    
+    if let Some(path) = &options.summary_json {
+        let mut summary = UpSummary::collect(&compose, network, &backends, &settings, &checker, deadline.start)?;
+        summary.failed_optional = failed.iter().map(|(service, _)| service.clone()).collect();
+        write_summary(path, &summary)?;
+    }
+    
+    if options.create_wallet {
+        wallet::create(&compose, &settings, AddressKind::Transparent).await?;
+    }
+    
+    if let Some(path) = &options.fund {
+        let confirm = faucet::ConfirmOptions {
+            confirmations: 1,
+            timeout: faucet::CONFIRM_TIMEOUT,
+            auto_mine: network == Network::Regtest,
+        };
+        faucet::fund_batch(path, confirm).await?;
+    }
+    
+    if let Some(path) = &options.env_out {
+        write_env_file(path, &backends, &settings)?;
+    }
+    
+    // Startup is done; staying attached must not block `down` from another terminal
+    drop(lock);
+    if options.attach {
+        attach_logs(&compose, &services, &checker, options.restart_on_failure).await?;
+    } else if options.watch {
+        watch::run(&compose, &services, network, &checker).await?;
+    }
     
     Ok(())
 }
 
-fn print_connection_info(backend: &str) {
+/// What `up --summary-json` records, for CI to tear down and debug later.
+///
+/// `services` entries are `status --json` entries with `ready_after_secs` added.
+#[derive(Serialize)]
+struct UpSummary {
+    network: String,
+    backends: Vec<String>,
+    /// The `zecdev env` variables, e.g. `ZEBRA_RPC_URL`
+    endpoints: BTreeMap<&'static str, String>,
+    services: Vec<ServiceSummary>,
+    /// Optional services that failed to start or become healthy
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failed_optional: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ServiceSummary {
+    #[serde(flatten)]
+    status: ServiceStatus,
+    /// Seconds from the start of `up` until the service passed its health check
+    ready_after_secs: Option<f64>,
+}
+
+impl UpSummary {
+    fn collect(
+        compose: &DockerCompose,
+        network: Network,
+        backends: &[&str],
+        settings: &Settings,
+        checker: &HealthChecker,
+        start: Instant,
+    ) -> Result<Self> {
+        let ready = checker.ready_times();
+        let services = compose
+            .ps()?
+            .into_iter()
+            .map(|status| {
+                let ready_after_secs = ready
+                    .iter()
+                    .find(|(service, _)| *service == status.name)
+                    .map(|(_, at)| at.duration_since(start).as_secs_f64());
+                ServiceSummary { status, ready_after_secs }
+            })
+            .collect();
+        
+        Ok(Self {
+            network: network.to_string(),
+            backends: backends.iter().map(|b| b.to_string()).collect(),
+            endpoints: settings.env_vars(backends).into_iter().collect(),
+            services,
+            failed_optional: Vec::new(),
+        })
+    }
+}
+
+/// Warn about each optional service that didn't come up, and why
+fn print_failed_optional(failed: &[(String, ZecDevError)]) {
+    if failed.is_empty() {
+        return;
+    }
+    eprintln!(
+        "{} Optional services that didn't start: {}",
+        "⚠".yellow(),
+        failed.iter().map(|(service, _)| service.as_str()).collect::<Vec<_>>().join(", ")
+    );
+    for (service, e) in failed {
+        eprintln!("  {}: {}", service, e.to_string().lines().next().unwrap_or_default());
+    }
+}
+
+/// Write the summary to `path`, or to stdout when it is `-`
+fn write_summary(path: &Path, summary: &UpSummary) -> Result<()> {
+    let json = serde_json::to_string_pretty(summary)?;
+    if path == Path::new("-") {
+        println!("{}", json);
+    } else {
+        std::fs::write(path, json + "\n")?;
+        if !is_quiet() {
+            println!("{} Wrote startup summary to {}", "✓".green(), path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Print the compose commands `up` would run, in order, without running them
+fn print_dry_run(
+    compose: &DockerCompose,
+    services: &[&str],
+    to_start: &[&str],
+    options: &UpOptions,
+) -> Result<()> {
+    if options.fresh {
+        compose.down(true)?;
+    }
+    if options.pull {
+        // One pull per service, as pull_services runs them
+        for service in services {
+            compose.pull(&[service])?;
+        }
+    }
+    if options.build {
+        compose.build(services)?;
+    }
+    for stage in start_stages(to_start, options.ordered_start) {
+        if options.force_recreate {
+            compose.recreate(&stage)?;
+        } else {
+            compose.up(&stage)?;
+        }
+    }
+    
+    if !is_quiet() {
+        println!();
+        println!("{}", "Dry run - nothing was started. Health checks would follow each start.".yellow());
+    }
+    Ok(())
+}
+
+/// Fail on the first of `services` that ZecKit doesn't define, e.g. in `--mount` or `--memory-limit`
+fn check_services(services: &[&str]) -> Result<()> {
+    match services.iter().find(|s| ServiceSet::get(s).is_none()) {
+        Some(service) => Err(ZecDevError::UnknownService {
+            name: service.to_string(),
+            available: ServiceSet::names().iter().map(|s| s.to_string()).collect(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Those of `services` already running and passing their container health check.
+///
+/// A service without a container health check can't be vouched for, so it is
+/// started (a no-op for compose) and checked again like any other.
+fn already_ready<'a>(statuses: &[ServiceStatus], services: &[&'a str]) -> Vec<&'a str> {
+    services
+        .iter()
+        .copied()
+        .filter(|service| {
+            statuses
+                .iter()
+                .any(|s| s.name == *service && s.state == "running" && s.health == "healthy")
+        })
+        .collect()
+}
+
+/// Groups of services to start one after another.
+///
+/// Ordered, Zebra starts alone first since everything else talks to its
+/// RPC, and the rest (profile extras included) follow together once it is ready.
+fn start_stages<'a>(services: &[&'a str], ordered: bool) -> Vec<Vec<&'a str>> {
+    if services.is_empty() {
+        return Vec::new();
+    }
+    let (zebra, rest): (Vec<&str>, Vec<&str>) = services
+        .iter()
+        .partition(|s| ServiceSet::get(s).is_some_and(|s| !s.requires_zebra));
+    if !ordered || zebra.is_empty() || rest.is_empty() {
+        return vec![services.to_vec()];
+    }
+    vec![zebra, rest]
+}
+
+/// Hard cap on the whole `up` flow, from `--startup-timeout`
+struct Deadline {
+    start: Instant,
+    limit: Option<Duration>,
+}
+
+impl Deadline {
+    fn start(limit: Option<Duration>) -> Self {
+        Self {
+            start: Instant::now(),
+            limit,
+        }
+    }
+    
+    /// Run `step` to completion, or `None` if the deadline passes first
+    async fn run<T>(&self, step: impl Future<Output = T>) -> Option<T> {
+        match self.limit {
+            Some(limit) => tokio::time::timeout_at(self.start + limit, step).await.ok(),
+            None => Some(step.await),
+        }
+    }
+    
+    /// Run a blocking compose step off the async runtime so the deadline can interrupt it
+    async fn blocking<T: Send + 'static>(
+        &self,
+        phase: &'static str,
+        services: &[&str],
+        step: impl FnOnce() -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let task = tokio::task::spawn_blocking(step);
+        match self.run(task).await {
+            Some(result) => result.map_err(|e| ZecDevError::Docker(e.to_string()))?,
+            None => Err(self.expired(phase, services, Vec::new())),
+        }
+    }
+    
+    fn expired(&self, phase: &'static str, services: &[&str], ready: Vec<String>) -> ZecDevError {
+        let pending = services
+            .iter()
+            .filter(|s| !ready.iter().any(|r| r == *s))
+            .map(|s| s.to_string())
+            .collect();
+        
+        ZecDevError::StartupTimeout {
+            limit: self.limit.unwrap_or_default(),
+            phase,
+            ready,
+            pending,
+        }
+    }
+}
+
+/// Follow combined service logs until Ctrl-C, leaving the services running.
+///
+/// With `restart_limit`, a service that crashes is restarted and health
+/// checked again, up to that many times each, before tailing resumes.
+async fn attach_logs(
+    compose: &DockerCompose,
+    services: &[&str],
+    checker: &HealthChecker,
+    restart_limit: Option<u32>,
+) -> Result<()> {
+    println!("{}", "Attaching to logs (Ctrl-C to detach)...".cyan());
+    println!();
+    
+    let mut restarts: BTreeMap<String, u32> = BTreeMap::new();
+    let mut since = None;
+    loop {
+        let cmd = compose.follow_logs_command(services, since);
+        let _span = log_command(&cmd);
+        let mut logs = tokio::process::Command::from(cmd)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+        
+        let crash = tokio::select! {
+            status = logs.wait() => {
+                let status = status?;
+                if !status.success() {
+                    return Err(ZecDevError::Docker(format!("docker compose logs exited with {}", status)));
+                }
+                return Ok(());
+            }
+            _ = tokio::signal::ctrl_c() => {
+                // docker compose logs receives the same SIGINT and exits on its own
+                println!();
+                println!(
+                    "{}",
+                    "Detached — services are still running; run zecdev down to stop them.".yellow()
+                );
+                return Ok(());
+            }
+            crash = watch_for_crashes(compose, services), if restart_limit.is_some() => crash,
+        };
+        
+        // Only a crash is worth a restart; anything else watch_for_crashes hit is fatal
+        let e = match crash {
+            Err(e) => e,
+            Ok(()) => continue,
+        };
+        let service = match &e {
+            ZecDevError::ServiceCrashed { service, .. } => service.clone(),
+            _ => return Err(e),
+        };
+        let _ = logs.kill().await;
+        
+        let max = restart_limit.unwrap_or_default();
+        let attempt = restarts.entry(service.clone()).or_default();
+        *attempt += 1;
+        if *attempt > max {
+            return Err(e.with_hint(format!(
+                "{} kept crashing after {} restart(s). zecdev logs {} shows the full output.",
+                service, max, service
+            )));
+        }
+        
+        println!();
+        println!("{}", format!("{} crashed, restarting ({}/{})", service, attempt, max).yellow().bold());
+        since = Some(unix_now());
+        compose.restart(&[&service])?;
+        
+        let pb = spinner();
+        pb.enable_steady_tick(Duration::from_millis(100));
+        pb.set_message(format!("Waiting for {}...", service));
+        match checker.wait_for_service(&service, &pb).await {
+            Ok(()) => pb.finish_with_message(format!("✓ {} ready, resuming logs", service).green().to_string()),
+            Err(e) => {
+                pb.abandon_with_message(format!("✗ {} did not recover", service).red().to_string());
+                return Err(e);
+            }
+        }
+        println!();
+    }
+}
+
+/// Combined logs streamed above the health check spinners during startup
+struct LogFollower {
+    child: tokio::process::Child,
+    printer: tokio::task::JoinHandle<()>,
+}
+
+impl LogFollower {
+    /// Follow `services`' logs from the Unix timestamp `since` on
+    fn spawn(compose: &DockerCompose, services: &[&str], since: u64, multi: &MultiProgress) -> Result<Self> {
+        let cmd = compose.follow_logs_command(services, Some(since));
+        let _span = log_command(&cmd);
+        let mut child = tokio::process::Command::from(cmd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let multi = multi.clone();
+        let printer = tokio::spawn(async move {
+            let mut lines = tokio::io::BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                // Printing through the group keeps lines from tearing the spinners;
+                // a hidden group drops them, so print directly then
+                if multi.is_hidden() {
+                    println!("{}", line);
+                } else {
+                    let _ = multi.println(line);
+                }
+            }
+        });
+        Ok(Self { child, printer })
+    }
+    
+    /// Stop tailing once whatever compose already wrote is printed
+    async fn stop(mut self) {
+        let _ = self.child.kill().await;
+        let _ = self.printer.await;
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Poll container states while health checks run; only returns on a crash
+async fn watch_for_crashes(compose: &DockerCompose, services: &[&str]) -> Result<()> {
+    loop {
+        tokio::time::sleep(CRASH_POLL_INTERVAL).await;
+        compose.check_all_running(services)?;
+    }
+}
+
+/// Rebuild locally built images, failing `up` before anything starts
+fn build_services(compose: &DockerCompose, services: &[&str]) -> Result<()> {
+    // Verbose mode streams the build itself; a spinner would garble it
+    if is_verbose() {
+        println!("{} Building images...", "🔨".cyan());
+        return compose.build(services);
+    }
+    
+    let pb = spinner();
+    pb.enable_steady_tick(Duration::from_millis(100));
+    pb.set_message("Building images...");
+    
+    match compose.build(services) {
+        Ok(()) => {
+            pb.finish_with_message("✓ Images built".green().to_string());
+            Ok(())
+        }
+        Err(e) => {
+            pb.abandon_with_message("✗ Build failed".red().to_string());
+            Err(e)
+        }
+    }
+}
+
+/// Leave the terminal tidy after Ctrl-C during health checks, then exit
+fn handle_interrupt(compose: &DockerCompose, multi: &MultiProgress, cleanup: bool) -> ! {
+    let _ = multi.clear();
+    let _ = console::Term::stderr().show_cursor();
+    println!();
+    
+    if cleanup {
+        println!("{}", "Interrupted — stopping partially-started services...".yellow());
+        match compose.down(false) {
+            Ok(()) => println!("{} Services stopped", "✓".green()),
+            Err(e) => print_error(&e),
+        }
+    } else {
+        println!(
+            "{}",
+            "Interrupted — services may still be running; run zecdev down to stop them.".yellow()
+        );
+    }
+    
+    // Conventional exit status for SIGINT
+    lock::release_held();
+    process::exit(130);
+}
+
+/// How often container states are re-checked during health waits
+const CRASH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Light-client backends accepted by `--backend` and zeckit.toml's `backend`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// No light-client backend
+    None,
+    /// lightwalletd
+    #[value(alias = "lightwalletd")]
+    #[serde(alias = "lightwalletd")]
+    Lwd,
+    /// Zaino (experimental)
+    Zaino,
+}
+
+impl Backend {
+    /// Name used throughout config and settings, or `None` for no backend
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Backend::None => None,
+            Backend::Lwd => Some("lwd"),
+            Backend::Zaino => Some("zaino"),
+        }
+    }
+}
+
+/// The distinct backends in `requested`, in order, without `none`
+pub fn selected_backends(requested: &[Backend]) -> Vec<&'static str> {
+    let mut backends: Vec<&'static str> = Vec::new();
+    for backend in requested.iter().filter_map(|b| b.name()) {
+        if !backends.contains(&backend) {
+            backends.push(backend);
+        }
+    }
+    backends
+}
+
+/// Owned copies of service names, for steps moved onto a blocking thread
+fn owned(services: &[&str]) -> Vec<String> {
+    services.iter().map(|s| s.to_string()).collect()
+}
+
+/// The names in `owned`'s form again, as compose takes them
+fn borrowed(services: &[String]) -> Vec<&str> {
+    services.iter().map(String::as_str).collect()
+}
+
+/// Services the selected profiles add on top of `services`
+fn profile_extras(compose: &DockerCompose, services: &[&str], profiles: &[String]) -> Result<Vec<String>> {
+    if profiles.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(compose
+        .enabled_services()?
+        .into_iter()
+        .filter(|s| !services.contains(&s.as_str()))
+        .collect())
+}
+
+/// Endpoints for services started by `--profile`, from their published ports
+fn print_extras(statuses: &[ServiceStatus], extras: &[String]) {
+    println!("{}", "Profile extras:".bold());
+    for service in extras {
+        let ports = statuses
+            .iter()
+            .find(|s| s.name == *service)
+            .map(|s| s.ports.as_slice())
+            .unwrap_or_default();
+        if ports.is_empty() {
+            println!("  {} (no published ports)", format!("{}:", service).bold());
+        }
+        for port in ports {
+            println!("  {} http://127.0.0.1:{}", format!("{}:", service).bold(), port);
+        }
+    }
+    println!();
+}
+
+fn print_connection_info(backends: &[&str], network: Network, settings: &Settings) {
     println!();
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
     println!("{}", "  Services Ready".green().bold());
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
     println!();
-    println!("  {} {}", "Zebra RPC:".bold(), "http://127.0.0.1:8232");
-    println!("  {} {}", "Faucet API:".bold(), "http://127.0.0.1:8080");
+    println!("  {} {}", "Network:".bold(), network);
+    match RpcAuth::current().describe() {
+        Some(auth) => println!("  {} {} (auth: {})", "Zebra RPC:".bold(), settings.zebra_rpc_url, auth),
+        None => println!("  {} {}", "Zebra RPC:".bold(), settings.zebra_rpc_url),
+    }
+    println!("  {} {}", "Faucet API:".bold(), settings.faucet_api_url);
     
-    if backend == "lwd" {
-        println!("  {} {}", "LightwalletD:".bold(), "http://127.0.0.1:9067");
-    } else if backend == "zaino" {
-        println!("  {} {}", "Zaino:".bold(), "http://127.0.0.1:9067 (experimental)");
+    for backend in backends {
+        match *backend {
+            "lwd" => println!("  {} {}", "LightwalletD:".bold(), settings.backend_url),
+            "zaino" => println!("  {} {} (experimental)", "Zaino:".bold(), settings.zaino_url),
+            _ => {}
+        }
     }
     
     println!();
     println!("{}", "Next steps:".bold());
-    println!("  • Test faucet: curl http://127.0.0.1:8080/stats");
+    println!("  • Test faucet: curl {}/stats", settings.faucet_api_url);
     println!("  • Run tests: zecdev test");
     println!("  • Check status: zecdev status");
     println!();
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_on_stdout_leaves_out_human_output() {
+        let stdout = Some(Path::new("-"));
+        assert_eq!(verbosity(false, false, stdout), Verbosity::Quiet);
+        assert_eq!(verbosity(false, true, stdout), Verbosity::Quiet);
+        assert_eq!(verbosity(false, false, Some(Path::new("up.json"))), Verbosity::Normal);
+        assert_eq!(verbosity(false, true, None), Verbosity::Verbose);
+    }
+
+    #[test]
+    fn backend_services_are_unioned() {
+        let requested = vec![Backend::Lwd, Backend::Zaino, Backend::Lwd];
+        let backends = selected_backends(&requested);
+
+        assert_eq!(backends, vec!["lwd", "zaino"]);
+    }
+
+    #[test]
+    fn lightwalletd_is_accepted_for_lwd() {
+        assert_eq!(Backend::from_str("lightwalletd", false), Ok(Backend::Lwd));
+        assert!(Backend::from_str("lightwallet", false).is_err());
+    }
+
+    #[test]
+    fn none_selects_no_backend() {
+        assert!(selected_backends(&[Backend::None]).is_empty());
+    }
+
+    #[test]
+    fn flags_may_only_name_known_services() {
+        assert!(check_services(&["zebra", "faucet"]).is_ok());
+        let e = check_services(&["zebra", "postgres"]).unwrap_err();
+        assert!(matches!(e, ZecDevError::UnknownService { ref name, .. } if name == "postgres"), "{}", e);
+    }
+    
+    #[test]
+    fn zebra_starts_before_the_services_that_need_it() {
+        let services = vec!["zebra", "faucet", "lightwalletd"];
+        assert_eq!(start_stages(&services, true), vec![vec!["zebra"], vec!["faucet", "lightwalletd"]]);
+        assert_eq!(start_stages(&services, false), vec![services.clone()]);
+        assert_eq!(start_stages(&["zebra"], true), vec![vec!["zebra"]]);
+    }
+
+    #[test]
+    fn only_healthy_running_services_are_skipped() {
+        let status = |name: &str, state: &str, health: &str| ServiceStatus {
+            name: name.into(),
+            container_id: String::new(),
+            state: state.into(),
+            health: health.into(),
+            status: String::new(),
+            ports: Vec::new(),
+        };
+        let statuses = [
+            status("zebra", "running", "healthy"),
+            status("faucet", "running", "starting"),
+            status("lightwalletd", "running", "none"),
+            status("zaino", "exited", "healthy"),
+        ];
+
+        let services = ["zebra", "faucet", "lightwalletd", "zaino"];
+        assert_eq!(already_ready(&statuses, &services), vec!["zebra"]);
+        assert!(start_stages(&[], true).is_empty());
+    }
+
+    #[test]
+    fn summary_services_extend_the_status_schema() {
+        let summary = ServiceSummary {
+            status: ServiceStatus {
+                name: "zebra".into(),
+                container_id: "abc123".into(),
+                state: "running".into(),
+                health: "healthy".into(),
+                status: "Up 30 seconds (healthy)".into(),
+                ports: vec!["127.0.0.1:8232->8232/tcp".into()],
+            },
+            ready_after_secs: Some(12.5),
+        };
+
+        let json = serde_json::to_value(&summary).unwrap();
+        let mut status = serde_json::to_value(&summary.status).unwrap();
+        status["ready_after_secs"] = 12.5.into();
+        assert_eq!(json, status);
+    }
+}
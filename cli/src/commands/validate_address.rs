@@ -0,0 +1,35 @@
+use crate::address;
+use crate::config::{Config, Network};
+use crate::docker::compose::DockerCompose;
+use crate::error::Result;
+use crate::state::DevnetState;
+use crate::utils::is_quiet;
+use colored::*;
+
+/// Check `address` against `network`, else the devnet's network, and print its kind
+pub async fn execute(address: String, network: Option<Network>) -> Result<()> {
+    let network = match network {
+        Some(network) => network,
+        None => devnet_network()?,
+    };
+    let (_, encoded_for) = address::parse(&address)?;
+    let kind = address::validate(&address, network)?;
+    
+    if is_quiet() {
+        println!("{}", kind);
+        return Ok(());
+    }
+    println!("{} {} is a valid {} address", "✓".green(), address.trim(), kind);
+    println!("  {} {}", "Kind:".bold(), kind);
+    println!("  {} {} (devnet: {})", "Network:".bold(), encoded_for, network);
+    Ok(())
+}
+
+/// The network `up` started, without requiring Docker: checking an
+/// address before the devnet exists falls back to zeckit.toml
+fn devnet_network() -> Result<Network> {
+    match DockerCompose::new().and_then(|compose| compose.project_name()) {
+        Ok(project) => DevnetState::network_for(&project),
+        Err(_) => Ok(Config::load()?.resolve_network(None)),
+    }
+}
@@ -0,0 +1,57 @@
+use crate::backend::BackendClient;
+use crate::config::settings::Settings;
+use crate::config::Config;
+use crate::docker::compose::DockerCompose;
+use crate::error::Result;
+use crate::faucet::FaucetClient;
+use crate::rpc::ZebraRpc;
+use crate::services::ServiceSet;
+use crate::utils::print_table;
+use std::time::Duration;
+
+pub async fn execute(components: bool) -> Result<()> {
+    println!("zecdev {}", env!("CARGO_PKG_VERSION"));
+    if !components {
+        return Ok(());
+    }
+    
+    let compose = DockerCompose::new()?;
+    let settings = Settings::from_config(&Config::load()?);
+    let images = compose.service_images()?;
+    let running = compose.running_services()?;
+    
+    let mut rows = Vec::new();
+    for (service, image) in images {
+        let reported = if running.contains(&service) {
+            running_version(&service, &settings)
+                .await
+                .unwrap_or_else(|| "unknown".into())
+        } else {
+            "not running".into()
+        };
+        rows.push(vec![
+            service,
+            image.unwrap_or_else(|| "(built locally)".into()),
+            reported,
+        ]);
+    }
+    
+    println!();
+    print_table(&["SERVICE", "IMAGE", "RUNNING VERSION"], &rows);
+    Ok(())
+}
+
+/// What a running service says its version is, for services that can tell us
+async fn running_version(service: &str, settings: &Settings) -> Option<String> {
+    let service = ServiceSet::get(service)?;
+    match (service.name, service.backend) {
+        ("zebra", _) => ZebraRpc::new(settings.zebra_rpc_url.clone())
+            .with_timeout(Duration::from_secs(5))
+            .get_subversion()
+            .await
+            .ok(),
+        ("faucet", _) => FaucetClient::new(settings).version().await.ok(),
+        (_, Some(_)) => BackendClient::new(service.url(settings)).ok()?.version().await.ok(),
+        _ => None,
+    }
+}
@@ -0,0 +1,83 @@
+use crate::config::settings::Settings;
+use crate::config::Config;
+use crate::docker::health::HealthChecker;
+use crate::error::{Result, ZecDevError};
+use crate::rpc::ZebraRpc;
+use crate::utils::spinner;
+use colored::*;
+use tokio::time::{sleep, Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub async fn execute(
+    target_height: Option<u64>,
+    port: Option<u16>,
+    host: Option<String>,
+    timeout: Duration,
+) -> Result<()> {
+    match (target_height, port) {
+        (_, Some(port)) => wait_for_port(host.as_deref().unwrap_or("127.0.0.1"), port, timeout).await,
+        (Some(height), None) => wait_for_height(height, timeout).await,
+        // clap requires one of the two
+        (None, None) => Ok(()),
+    }
+}
+
+/// Block until something listens on `host:port`
+async fn wait_for_port(host: &str, port: u16, timeout: Duration) -> Result<()> {
+    let checker = HealthChecker::new().with_timeout(timeout);
+    
+    let pb = spinner();
+    pb.enable_steady_tick(Duration::from_millis(100));
+    pb.set_message(format!("Waiting for {}:{}...", host, port));
+    
+    match checker.wait_for_port(host, port, &pb).await {
+        Ok(()) => {
+            pb.finish_and_clear();
+            println!("{} {}:{} accepting connections", "✓".green(), host, port);
+            Ok(())
+        }
+        Err(e) => {
+            pb.abandon_with_message(format!("✗ {}:{} not reachable", host, port).red().to_string());
+            Err(e)
+        }
+    }
+}
+
+async fn wait_for_height(target_height: u64, timeout: Duration) -> Result<()> {
+    let settings = Settings::from_config(&Config::load()?);
+    let rpc = ZebraRpc::new(settings.zebra_rpc_url.clone()).with_timeout(Duration::from_secs(5));
+    
+    let pb = spinner();
+    pb.enable_steady_tick(Duration::from_millis(100));
+    pb.set_message(format!("Waiting for Zebra... height ? / {}", target_height));
+    
+    let start = Instant::now();
+    let mut height = None;
+    
+    loop {
+        // Zebra may still be starting; keep polling until it answers
+        if let Ok(current) = rpc.get_block_count().await {
+            height = Some(current);
+            pb.set_message(format!("Waiting for Zebra... height {} / {}", current, target_height));
+            
+            if current >= target_height {
+                pb.finish_and_clear();
+                println!("{} Zebra at height {}", "✓".green(), current);
+                return Ok(());
+            }
+        }
+        
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            pb.abandon_with_message(format!("✗ height {} not reached", target_height).red().to_string());
+            return Err(ZecDevError::HeightTimeout {
+                target: target_height,
+                height,
+                elapsed,
+            });
+        }
+        
+        sleep(POLL_INTERVAL.min(timeout - elapsed)).await;
+    }
+}
@@ -0,0 +1,42 @@
+use crate::config::settings::Settings;
+use crate::config::Config;
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::rpc::ZebraRpc;
+use crate::utils::is_quiet;
+use crate::wallet::{AddressKind, Wallet};
+use colored::*;
+
+pub async fn new(kind: AddressKind) -> Result<()> {
+    let settings = Settings::from_config(&Config::load()?);
+    let compose = DockerCompose::new()?;
+    
+    create(&compose, &settings, kind).await?;
+    Ok(())
+}
+
+/// Generate an address, save it as the project's wallet and print it
+pub async fn create(compose: &DockerCompose, settings: &Settings, kind: AddressKind) -> Result<Wallet> {
+    let rpc = ZebraRpc::new(settings.zebra_rpc_url.clone());
+    let wallet = Wallet::generate(&rpc, kind).await?;
+    let path = wallet.save(&compose.project_name()?)?;
+    
+    // Keep `up --quiet` output a clean dotenv document
+    if !is_quiet() {
+        println!("{} New {} address: {}", "✓".green(), wallet.chain, wallet.address.bold());
+        println!("  Saved to {}; zecdev faucet fund now sends here by default", path.display());
+    }
+    
+    Ok(wallet)
+}
+
+/// The address `faucet fund` uses when none is given
+pub fn default_address() -> Result<String> {
+    let project = DockerCompose::new()?.project_name()?;
+    
+    match Wallet::load(&project)? {
+        Some(wallet) => Ok(wallet.address),
+        None => Err(ZecDevError::InvalidAddress("no address given and no wallet created".into())
+            .with_hint("Pass an address, or create one with zecdev wallet new.")),
+    }
+}
@@ -0,0 +1,199 @@
+use crate::config::Network;
+use crate::docker::compose::DockerCompose;
+use crate::docker::health::HealthChecker;
+use crate::docker::options::ComposeOptions;
+use crate::error::{Result, ZecDevError};
+use crate::utils::{print_error, progress_group, project_root};
+use colored::*;
+use notify::{RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use tokio::time::{timeout, Duration};
+
+/// Editors often write a file in several steps; wait for them to settle
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// What a changed file means for the running devnet
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    /// The compose file itself: let compose work out what to recreate
+    Compose,
+    /// A config file bind-mounted into one service
+    Service(&'static str),
+}
+
+/// Re-apply compose and config changes to the running services until Ctrl-C
+pub async fn run(
+    compose: &DockerCompose,
+    services: &[&str],
+    network: Network,
+    checker: &HealthChecker,
+) -> Result<()> {
+    let targets = watched_files(services, network)?;
+    
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            let _ = tx.send(event.paths);
+        }
+    })
+    .map_err(|e| ZecDevError::Config(format!("could not start file watcher: {}", e)))?;
+    
+    // Watch directories rather than files so atomic saves (write + rename)
+    // keep being noticed
+    let mut dirs: Vec<&Path> = targets.keys().filter_map(|p| p.parent()).collect();
+    dirs.sort();
+    dirs.dedup();
+    for dir in dirs {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| ZecDevError::Config(format!("could not watch {}: {}", dir.display(), e)))?;
+    }
+    
+    println!();
+    println!("{} Watching for changes (Ctrl-C to stop):", "👀".cyan());
+    for path in targets.keys() {
+        println!("  {}", path.display());
+    }
+    
+    loop {
+        let paths = tokio::select! {
+            paths = rx.recv() => match paths {
+                Some(paths) => paths,
+                None => return Ok(()),
+            },
+            _ = tokio::signal::ctrl_c() => {
+                println!();
+                println!(
+                    "{}",
+                    "Stopped watching — services are still running; run zecdev down to stop them.".yellow()
+                );
+                return Ok(());
+            }
+        };
+        
+        let mut changed = matching_targets(&targets, &paths);
+        while let Ok(Some(more)) = timeout(DEBOUNCE, rx.recv()).await {
+            for target in matching_targets(&targets, &more) {
+                if !changed.contains(&target) {
+                    changed.push(target);
+                }
+            }
+        }
+        
+        if changed.is_empty() {
+            continue;
+        }
+        
+        // A bad edit should be reported, not end the session
+        if let Err(e) = apply(compose, services, &changed, checker).await {
+            print_error(&e);
+            println!("{}", "Still watching; fix the file and save again.".yellow());
+        }
+    }
+}
+
+/// Bring the affected services up to date and wait on just those
+async fn apply(
+    compose: &DockerCompose,
+    services: &[&str],
+    changed: &[Target],
+    checker: &HealthChecker,
+) -> Result<()> {
+    let mut affected: Vec<&str> = Vec::new();
+    
+    if changed.contains(&Target::Compose) {
+        println!("{} Compose file changed, re-applying...", "🔄".cyan());
+        
+        // Compose only recreates services whose definition changed
+        let before = container_ids(compose)?;
+        compose.up(services)?;
+        let after = container_ids(compose)?;
+        
+        affected.extend(services.iter().filter(|s| before.get(**s) != after.get(**s)));
+    }
+    
+    for target in changed {
+        if let Target::Service(service) = target {
+            if !affected.contains(service) {
+                println!("{} {} config changed, restarting...", "🔄".cyan(), service);
+                compose.restart(&[service])?;
+                affected.push(service);
+            }
+        }
+    }
+    
+    if affected.is_empty() {
+        println!("{} No services affected", "✓".green());
+        return Ok(());
+    }
+    
+    compose.check_all_running(&affected)?;
+    checker.wait_for_all(&affected, &progress_group()).await?;
+    println!("{} Updated: {}", "✓".green(), affected.join(", "));
+    Ok(())
+}
+
+fn container_ids(compose: &DockerCompose) -> Result<HashMap<String, String>> {
+    Ok(compose
+        .ps()?
+        .into_iter()
+        .map(|s| (s.name, s.container_id))
+        .collect())
+}
+
+/// The active compose file and the config files mounted into `services`
+fn watched_files(services: &[&str], network: Network) -> Result<HashMap<PathBuf, Target>> {
+    let root = project_root()?;
+    let compose_file = ComposeOptions::current()
+        .file
+        .unwrap_or_else(|| root.join("docker-compose.yml"));
+    
+    let mut targets = HashMap::new();
+    targets.insert(compose_file, Target::Compose);
+    
+    let configs = root.join("docker").join("configs");
+    targets.insert(configs.join(network.zebra_config()), Target::Service("zebra"));
+    if services.contains(&"lightwalletd") {
+        targets.insert(
+            configs.join(network.lightwalletd_config()),
+            Target::Service("lightwalletd"),
+        );
+    }
+    if services.contains(&"zaino") {
+        targets.insert(configs.join(network.zaino_config()), Target::Service("zaino"));
+    }
+    
+    Ok(targets)
+}
+
+fn matching_targets(targets: &HashMap<PathBuf, Target>, paths: &[PathBuf]) -> Vec<Target> {
+    let mut matched = Vec::new();
+    for path in paths {
+        if let Some(target) = targets.get(path) {
+            if !matched.contains(target) {
+                matched.push(target.clone());
+            }
+        }
+    }
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_files_map_to_their_service() {
+        let targets = watched_files(&["zebra", "faucet"], Network::Testnet).unwrap();
+        let zebra = project_root().unwrap().join("docker/configs/zebra-testnet.toml");
+
+        assert_eq!(targets.get(&zebra), Some(&Target::Service("zebra")));
+        assert!(!targets.values().any(|t| *t == Target::Service("lightwalletd")));
+        assert_eq!(
+            matching_targets(&targets, &[zebra.clone(), zebra, PathBuf::from("/tmp/other")]),
+            vec![Target::Service("zebra")]
+        );
+    }
+}
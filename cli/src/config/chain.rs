@@ -0,0 +1,222 @@
+use crate::config::Network;
+use crate::error::{Result, ZecDevError};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Network upgrades whose regtest activation height can be set, in activation order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkUpgrade {
+    BeforeOverwinter,
+    Overwinter,
+    Sapling,
+    Blossom,
+    Heartwood,
+    Canopy,
+    Nu5,
+    Nu6,
+}
+
+impl NetworkUpgrade {
+    pub const ALL: [NetworkUpgrade; 8] = [
+        NetworkUpgrade::BeforeOverwinter,
+        NetworkUpgrade::Overwinter,
+        NetworkUpgrade::Sapling,
+        NetworkUpgrade::Blossom,
+        NetworkUpgrade::Heartwood,
+        NetworkUpgrade::Canopy,
+        NetworkUpgrade::Nu5,
+        NetworkUpgrade::Nu6,
+    ];
+
+    /// Name in zeckit.toml and on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetworkUpgrade::BeforeOverwinter => "before_overwinter",
+            NetworkUpgrade::Overwinter => "overwinter",
+            NetworkUpgrade::Sapling => "sapling",
+            NetworkUpgrade::Blossom => "blossom",
+            NetworkUpgrade::Heartwood => "heartwood",
+            NetworkUpgrade::Canopy => "canopy",
+            NetworkUpgrade::Nu5 => "nu5",
+            NetworkUpgrade::Nu6 => "nu6",
+        }
+    }
+
+    /// Key under Zebra's `[network.testnet_parameters.activation_heights]`
+    fn zebra_name(&self) -> &'static str {
+        match self {
+            NetworkUpgrade::BeforeOverwinter => "BeforeOverwinter",
+            NetworkUpgrade::Overwinter => "Overwinter",
+            NetworkUpgrade::Sapling => "Sapling",
+            NetworkUpgrade::Blossom => "Blossom",
+            NetworkUpgrade::Heartwood => "Heartwood",
+            NetworkUpgrade::Canopy => "Canopy",
+            NetworkUpgrade::Nu5 => "NU5",
+            NetworkUpgrade::Nu6 => "NU6",
+        }
+    }
+}
+
+impl fmt::Display for NetworkUpgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for NetworkUpgrade {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase().replace('-', "_");
+        NetworkUpgrade::ALL
+            .into_iter()
+            .find(|upgrade| upgrade.name() == s)
+            .ok_or_else(|| format!("unknown network upgrade '{}' (expected one of: {})", s, upgrade_names()))
+    }
+}
+
+fn upgrade_names() -> String {
+    NetworkUpgrade::ALL.map(|u| u.name()).join(", ")
+}
+
+/// `[regtest]` in zeckit.toml: chain parameters for a custom regtest
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RegtestParams {
+    /// Height each listed upgrade activates at; unlisted ones keep Zebra's defaults
+    pub activation_heights: BTreeMap<NetworkUpgrade, u32>,
+}
+
+impl RegtestParams {
+    /// These heights with `--activation-height` flags applied over them
+    pub fn with_overrides(&self, flags: &[ActivationHeight]) -> BTreeMap<NetworkUpgrade, u32> {
+        let mut heights = self.activation_heights.clone();
+        for flag in flags {
+            heights.insert(flag.upgrade, flag.height);
+        }
+        heights
+    }
+}
+
+/// One `--activation-height UPGRADE=HEIGHT` flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivationHeight {
+    pub upgrade: NetworkUpgrade,
+    pub height: u32,
+}
+
+/// clap parser for `--activation-height UPGRADE=HEIGHT`, e.g. `nu5=100`
+pub fn parse_activation_height(input: &str) -> std::result::Result<ActivationHeight, String> {
+    let (upgrade, height) = input
+        .split_once('=')
+        .ok_or_else(|| format!("invalid activation height '{}' (expected e.g. nu5=100)", input))?;
+    let height = height
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid height '{}' for {} (expected a block height)", height.trim(), upgrade.trim()))?;
+    Ok(ActivationHeight {
+        upgrade: upgrade.parse()?,
+        height,
+    })
+}
+
+/// Refuse heights Zebra would reject or that contradict each other
+pub fn check_activation_heights(heights: &BTreeMap<NetworkUpgrade, u32>) -> Result<()> {
+    let mut previous: Option<(NetworkUpgrade, u32)> = None;
+    for (&upgrade, &height) in heights {
+        if height == 0 {
+            return Err(ZecDevError::Config(format!(
+                "{} cannot activate at height 0, the genesis block",
+                upgrade
+            ))
+            .with_hint("Use height 1 or later."));
+        }
+        if let Some((earlier, earlier_height)) = previous.filter(|(_, h)| height < *h) {
+            return Err(ZecDevError::Config(format!(
+                "{} activates at height {}, before {} at {}",
+                upgrade, height, earlier, earlier_height
+            ))
+            .with_hint(format!(
+                "Upgrades activate in order ({}), so each height must be at least the previous one's.",
+                upgrade_names()
+            )));
+        }
+        previous = Some((upgrade, height));
+    }
+    Ok(())
+}
+
+/// `base` (Zebra's regtest config) with the activation heights set
+pub fn zebra_config(base: &str, heights: &BTreeMap<NetworkUpgrade, u32>) -> Result<String> {
+    let mut config: toml::Table = toml::from_str(base)
+        .map_err(|e| ZecDevError::Config(format!("{}: {}", Network::Regtest.zebra_config(), e)))?;
+
+    let activation_heights: toml::Table = heights
+        .iter()
+        .map(|(upgrade, height)| (upgrade.zebra_name().to_string(), toml::Value::Integer(*height as i64)))
+        .collect();
+    let network = config
+        .entry("network")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    let parameters = network
+        .as_table_mut()
+        .ok_or_else(|| ZecDevError::Config("[network] in Zebra's config is not a table".into()))?
+        .entry("testnet_parameters")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    parameters
+        .as_table_mut()
+        .ok_or_else(|| ZecDevError::Config("[network.testnet_parameters] in Zebra's config is not a table".into()))?
+        .insert("activation_heights".to_string(), toml::Value::Table(activation_heights));
+
+    let body = toml::to_string(&config).map_err(|e| ZecDevError::Config(e.to_string()))?;
+    Ok(format!(
+        "# Generated by zecdev up from docker/configs/{}; do not edit\n{}",
+        Network::Regtest.zebra_config(),
+        body
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_parse_and_override_the_file() {
+        let flag = parse_activation_height("NU5=100").unwrap();
+        assert_eq!(flag, ActivationHeight { upgrade: NetworkUpgrade::Nu5, height: 100 });
+        assert!(parse_activation_height("nu7=1").is_err());
+        assert!(parse_activation_height("nu5").is_err());
+        assert!(parse_activation_height("nu5=soon").is_err());
+
+        let params: RegtestParams = toml::from_str("[activation_heights]\ncanopy = 1\nnu5 = 50\n").unwrap();
+        let heights = params.with_overrides(&[flag]);
+        assert_eq!(heights.into_iter().collect::<Vec<_>>(), vec![(NetworkUpgrade::Canopy, 1), (NetworkUpgrade::Nu5, 100)]);
+    }
+
+    #[test]
+    fn heights_must_not_go_backwards() {
+        let heights = BTreeMap::from([(NetworkUpgrade::Canopy, 20), (NetworkUpgrade::Nu5, 20), (NetworkUpgrade::Nu6, 30)]);
+        assert!(check_activation_heights(&heights).is_ok());
+
+        let heights = BTreeMap::from([(NetworkUpgrade::Canopy, 20), (NetworkUpgrade::Nu5, 10)]);
+        let e = check_activation_heights(&heights).unwrap_err();
+        assert_eq!(e.to_string(), "Configuration error: nu5 activates at height 10, before canopy at 20");
+
+        assert!(check_activation_heights(&BTreeMap::from([(NetworkUpgrade::Sapling, 0)])).is_err());
+    }
+
+    #[test]
+    fn heights_are_written_under_testnet_parameters() {
+        let base = "[network]\nnetwork = \"Regtest\"\n\n[rpc]\nlisten_addr = \"0.0.0.0:8232\"\n";
+        let heights = BTreeMap::from([(NetworkUpgrade::Canopy, 1), (NetworkUpgrade::Nu5, 100)]);
+        let config: toml::Table = toml::from_str(&zebra_config(base, &heights).unwrap()).unwrap();
+
+        assert_eq!(config["network"]["network"].as_str(), Some("Regtest"));
+        assert_eq!(config["network"]["testnet_parameters"]["activation_heights"]["NU5"].as_integer(), Some(100));
+        assert_eq!(config["network"]["testnet_parameters"]["activation_heights"]["Canopy"].as_integer(), Some(1));
+        assert_eq!(config["rpc"]["listen_addr"].as_str(), Some("0.0.0.0:8232"));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::commands::up::Backend;
+use crate::config::chain::{check_activation_heights, RegtestParams};
+use crate::config::health::{HealthSpec, Probe};
+use crate::config::Network;
+use crate::error::{Result, ZecDevError};
+use crate::services::ServiceSet;
+use crate::utils::project_root;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Name of the optional config file in the project root
+pub const CONFIG_FILE: &str = "zeckit.toml";
+
+/// Zebra's P2P port is always published as-is
+pub const ZEBRA_P2P_PORT: u16 = 8233;
+
+/// Payouts the bundled faucet accepts (its FAUCET_AMOUNT_MIN and _MAX), in ZEC
+pub const FAUCET_AMOUNT_RANGE: std::ops::RangeInclusive<f64> = 1.0..=100.0;
+
+/// User overrides read from `zeckit.toml`.
+///
+/// Every key is optional; missing keys keep the built-in defaults.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub zebra_rpc_port: u16,
+    pub faucet_port: u16,
+    /// lightwalletd's gRPC port
+    pub backend_port: u16,
+    /// Zaino's gRPC port, distinct so both backends can run side by side
+    pub zaino_port: u16,
+    /// Zebra's Prometheus endpoint, served once `[metrics]` is enabled in its config
+    pub zebra_metrics_port: u16,
+    /// lightwalletd's Prometheus endpoint
+    pub backend_metrics_port: u16,
+    /// ZEC the faucet sends when a request names no amount
+    pub faucet_default_amount: f64,
+    /// Seconds an address must wait between payouts (0 for no cooldown)
+    pub faucet_cooldown_secs: u64,
+    /// Free disk, in GB, required before a regtest devnet writes chain state
+    pub regtest_min_disk_gb: u64,
+    /// Same for testnet, whose synced state is far larger
+    pub testnet_min_disk_gb: u64,
+    /// Backends `up` and `pull` use when neither `--backend` nor ZECKIT_BACKEND is set
+    pub backend: Vec<Backend>,
+    /// Network `up` uses when neither `--network` nor ZECKIT_NETWORK is set
+    pub network: Option<Network>,
+    /// Readiness checks by service name, overriding the built-in ones
+    pub health: BTreeMap<String, HealthSpec>,
+    /// Chain parameters for a custom regtest
+    pub regtest: RegtestParams,
+    /// Services whose failure to start `up` reports instead of aborting on
+    pub optional_services: Vec<String>,
+    /// Times a docker command is retried after a transient failure
+    pub docker_retries: u32,
+    /// Seconds a docker command may run before it is killed (0 for no limit)
+    pub docker_timeout_secs: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            zebra_rpc_port: 8232,
+            faucet_port: 8080,
+            backend_port: 9067,
+            zaino_port: 9068,
+            zebra_metrics_port: 9999,
+            backend_metrics_port: 9069,
+            faucet_default_amount: 10.0,
+            faucet_cooldown_secs: 0,
+            regtest_min_disk_gb: 1,
+            testnet_min_disk_gb: 40,
+            backend: Vec::new(),
+            network: None,
+            health: BTreeMap::new(),
+            regtest: RegtestParams::default(),
+            optional_services: Vec::new(),
+            docker_retries: 2,
+            docker_timeout_secs: 300,
+        }
+    }
+}
+
+impl Config {
+    /// Load `zeckit.toml` from the project root, falling back to defaults
+    pub fn load() -> Result<Self> {
+        let path = project_root()?.join(CONFIG_FILE);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)?;
+        let config: Self = toml::from_str(&contents)
+            .map_err(|e| ZecDevError::Config(format!("{}: {}", path.display(), e)))?;
+        check_faucet_amount(config.faucet_default_amount)?;
+        config.health_probes()?;
+        check_activation_heights(&config.regtest.activation_heights)?;
+        config.optional_services(&[])?;
+        Ok(config)
+    }
+
+    /// The `[health]` checks, each against its own port or else the service's
+    /// first published port
+    pub fn health_probes(&self) -> Result<BTreeMap<String, Probe>> {
+        self.health
+            .iter()
+            .map(|(service, spec)| {
+                let port = spec
+                    .port()
+                    .or_else(|| self.ports_for(service).first().copied())
+                    .ok_or_else(|| {
+                        ZecDevError::Config(format!("health check for '{}' needs a port", service))
+                            .with_hint(format!("Add port = <host port> to [health.{}] in {}.", service, CONFIG_FILE))
+                    })?;
+                Ok((service.clone(), spec.probe(port)))
+            })
+            .collect()
+    }
+
+    /// `optional_services` plus those from `--optional`, refusing the core
+    /// services every devnet needs
+    pub fn optional_services(&self, requested: &[String]) -> Result<Vec<String>> {
+        let mut optional: Vec<String> = Vec::new();
+        for service in self.optional_services.iter().chain(requested) {
+            if ServiceSet::is_core(service) {
+                return Err(ZecDevError::Config(format!("{} can't be optional; every devnet needs it", service))
+                    .with_hint("Only backends and compose profile extras can be marked optional."));
+            }
+            if !optional.contains(service) {
+                optional.push(service.clone());
+            }
+        }
+        Ok(optional)
+    }
+
+    /// Backends from the command line (flag or ZECKIT_BACKEND), else this file's
+    pub fn resolve_backends(&self, requested: Vec<Backend>) -> Vec<Backend> {
+        if requested.is_empty() {
+            self.backend.clone()
+        } else {
+            requested
+        }
+    }
+
+    /// Network from the command line (flag or ZECKIT_NETWORK), else this file's, else regtest
+    pub fn resolve_network(&self, requested: Option<Network>) -> Network {
+        requested.or(self.network).unwrap_or_default()
+    }
+
+    /// Free disk space, in bytes, a devnet on `network` needs
+    pub fn min_free_disk(&self, network: Network) -> u64 {
+        let gb = match network {
+            Network::Regtest => self.regtest_min_disk_gb,
+            Network::Testnet => self.testnet_min_disk_gb,
+        };
+        gb * 1024 * 1024 * 1024
+    }
+
+    /// Host ports published by `service`
+    pub fn ports_for(&self, service: &str) -> Vec<u16> {
+        ServiceSet::get(service).map_or_else(Vec::new, |s| s.ports(self))
+    }
+
+    /// Environment variables consumed by docker-compose.yml port mappings
+    pub fn compose_env(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("ZEBRA_RPC_PORT", self.zebra_rpc_port.to_string()),
+            ("FAUCET_PORT", self.faucet_port.to_string()),
+            ("BACKEND_PORT", self.backend_port.to_string()),
+            ("ZAINO_PORT", self.zaino_port.to_string()),
+            ("ZEBRA_METRICS_PORT", self.zebra_metrics_port.to_string()),
+            ("BACKEND_METRICS_PORT", self.backend_metrics_port.to_string()),
+            ("FAUCET_AMOUNT_DEFAULT", self.faucet_default_amount.to_string()),
+            ("FAUCET_COOLDOWN_SECS", self.faucet_cooldown_secs.to_string()),
+        ]
+    }
+
+    /// Fail if two of the selected backends would publish the same host port
+    pub fn check_backend_ports(&self, backends: &[&str]) -> Result<()> {
+        let both = backends.contains(&"lwd") && backends.contains(&"zaino");
+        if both && self.backend_port == self.zaino_port {
+            return Err(ZecDevError::Config(format!(
+                "lightwalletd and zaino are both assigned port {}",
+                self.backend_port
+            ))
+            .with_hint(format!("Set a different backend_port or zaino_port in {}.", CONFIG_FILE)));
+        }
+        Ok(())
+    }
+}
+
+/// The faucet refuses to start with a default payout outside its limits
+pub fn check_faucet_amount(amount: f64) -> Result<()> {
+    if FAUCET_AMOUNT_RANGE.contains(&amount) {
+        return Ok(());
+    }
+    Err(ZecDevError::Config(format!(
+        "faucet default amount {} ZEC is outside {}-{} ZEC",
+        amount,
+        FAUCET_AMOUNT_RANGE.start(),
+        FAUCET_AMOUNT_RANGE.end()
+    ))
+    .with_hint("The bundled faucet only pays out within that range."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backends_get_distinct_ports_by_default() {
+        let config = Config::default();
+        assert_ne!(config.ports_for("lightwalletd"), config.ports_for("zaino"));
+        assert!(config.check_backend_ports(&["lwd", "zaino"]).is_ok());
+    }
+
+    #[test]
+    fn shared_backend_port_is_rejected_only_when_both_run() {
+        let config = Config {
+            zaino_port: 9067,
+            ..Config::default()
+        };
+        assert!(config.check_backend_ports(&["zaino"]).is_ok());
+        assert!(config.check_backend_ports(&["lwd", "zaino"]).is_err());
+    }
+
+    #[test]
+    fn faucet_settings_reach_the_compose_environment() {
+        let config = Config {
+            faucet_default_amount: 2.5,
+            faucet_cooldown_secs: 30,
+            ..Config::default()
+        };
+        let env = config.compose_env();
+        assert!(env.contains(&("FAUCET_AMOUNT_DEFAULT", "2.5".to_string())));
+        assert!(env.contains(&("FAUCET_COOLDOWN_SECS", "30".to_string())));
+
+        assert!(check_faucet_amount(100.0).is_ok());
+        assert!(check_faucet_amount(0.5).is_err());
+    }
+
+    #[test]
+    fn command_line_wins_over_file_defaults() {
+        let config: Config = toml::from_str("backend = [\"lightwalletd\"]\nnetwork = \"testnet\"").unwrap();
+        assert_eq!(config.resolve_backends(Vec::new()), vec![Backend::Lwd]);
+        assert_eq!(config.resolve_backends(vec![Backend::Zaino]), vec![Backend::Zaino]);
+        assert_eq!(config.resolve_network(None), Network::Testnet);
+        assert_eq!(config.resolve_network(Some(Network::Regtest)), Network::Regtest);
+
+        let config = Config::default();
+        assert!(config.resolve_backends(Vec::new()).is_empty());
+        assert_eq!(config.resolve_network(None), Network::Regtest);
+    }
+
+    #[test]
+    fn optional_services_never_include_the_core() {
+        let config: Config = toml::from_str("optional_services = [\"explorer\"]").unwrap();
+        assert_eq!(
+            config.optional_services(&["zaino".into(), "explorer".into()]).unwrap(),
+            vec!["explorer", "zaino"]
+        );
+        assert!(config.optional_services(&["faucet".into()]).is_err());
+        assert!(toml::from_str::<Config>("optional_services = [\"zebra\"]")
+            .unwrap()
+            .optional_services(&[])
+            .is_err());
+    }
+
+    #[test]
+    fn health_specs_resolve_to_probes() {
+        let config: Config = toml::from_str(
+            r#"
+            [health.faucet]
+            type = "http"
+            path = "healthz"
+
+            [health.indexer]
+            type = "tcp"
+            port = 5432
+
+            [health.explorer]
+            type = "http"
+            port = 3000
+            path = "/status"
+            expect_status = 204
+            "#,
+        )
+        .unwrap();
+        let probes = config.health_probes().unwrap();
+
+        assert_eq!(
+            probes["faucet"],
+            Probe::Http { url: "http://127.0.0.1:8080/healthz".into(), expect_status: 200 }
+        );
+        assert_eq!(probes["indexer"], Probe::Tcp { address: "127.0.0.1:5432".into() });
+        assert_eq!(
+            probes["explorer"],
+            Probe::Http { url: "http://127.0.0.1:3000/status".into(), expect_status: 204 }
+        );
+    }
+
+    #[test]
+    fn unknown_services_need_a_health_port() {
+        let config: Config = toml::from_str("[health.indexer]\ntype = \"tcp\"").unwrap();
+        assert!(config.health_probes().is_err());
+
+        assert!(toml::from_str::<Config>("[health.zebra]\ntype = \"grpc\"").is_err());
+        assert!(toml::from_str::<Config>("[health.zebra]\ntype = \"tcp\"\npath = \"/\"").is_err());
+    }
+
+    #[test]
+    fn disk_minimum_depends_on_the_network() {
+        let config: Config = toml::from_str("testnet_min_disk_gb = 80").unwrap();
+        assert_eq!(config.min_free_disk(Network::Regtest), 1024 * 1024 * 1024);
+        assert_eq!(config.min_free_disk(Network::Testnet), 80 * 1024 * 1024 * 1024);
+    }
+}
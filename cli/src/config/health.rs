@@ -0,0 +1,59 @@
+use serde::Deserialize;
+
+/// A readiness check from `zeckit.toml`'s `[health.<service>]` tables.
+///
+/// Replaces the built-in check for ZecKit's own services, and gives
+/// services it doesn't know (e.g. from an override file) one at all.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub enum HealthSpec {
+    /// Ready once `GET path` answers with `expect_status`
+    Http {
+        /// Host port (default: the service's port from zeckit.toml)
+        port: Option<u16>,
+        #[serde(default = "default_path")]
+        path: String,
+        #[serde(default = "default_status")]
+        expect_status: u16,
+    },
+    /// Ready once the port accepts TCP connections
+    Tcp {
+        port: Option<u16>,
+    },
+}
+
+impl HealthSpec {
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            HealthSpec::Http { port, .. } | HealthSpec::Tcp { port } => *port,
+        }
+    }
+
+    /// The check against `127.0.0.1:<port>`
+    pub fn probe(&self, port: u16) -> Probe {
+        match self {
+            HealthSpec::Http { path, expect_status, .. } => Probe::Http {
+                url: format!("http://127.0.0.1:{}/{}", port, path.trim_start_matches('/')),
+                expect_status: *expect_status,
+            },
+            HealthSpec::Tcp { .. } => Probe::Tcp {
+                address: format!("127.0.0.1:{}", port),
+            },
+        }
+    }
+}
+
+/// A `HealthSpec` with its port resolved, ready for `HealthChecker`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Probe {
+    Http { url: String, expect_status: u16 },
+    Tcp { address: String },
+}
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+fn default_status() -> u16 {
+    200
+}
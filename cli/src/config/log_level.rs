@@ -0,0 +1,43 @@
+use clap::ValueEnum;
+
+/// Verbosity passed through to the Zebra and lightwalletd containers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /// `RUST_LOG` filter for Zebra
+    pub fn zebra_filter(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+
+    /// lightwalletd's numeric `--log-level` (logrus: 2 = error ... 6 = trace)
+    pub fn lightwalletd_level(&self) -> u8 {
+        match self {
+            LogLevel::Error => 2,
+            LogLevel::Warn => 3,
+            LogLevel::Info => 4,
+            LogLevel::Debug => 5,
+            LogLevel::Trace => 6,
+        }
+    }
+
+    /// Variables docker-compose.yml substitutes into each service's logging setup
+    pub fn compose_env(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("ZEBRA_LOG", self.zebra_filter().to_string()),
+            ("LIGHTWALLETD_LOG_LEVEL", self.lightwalletd_level().to_string()),
+        ]
+    }
+}
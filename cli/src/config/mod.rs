@@ -1 +1,11 @@
-pub mod settings;
\ No newline at end of file
+pub mod chain;
+pub mod file;
+pub mod health;
+pub mod log_level;
+pub mod network;
+pub mod peers;
+pub mod settings;
+
+pub use file::Config;
+pub use log_level::LogLevel;
+pub use network::Network;
@@ -0,0 +1,84 @@
+use crate::error::Result;
+use crate::utils::project_root;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+
+/// Zcash network the devnet's Zebra node runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    /// Local chain where blocks are only produced on demand
+    #[default]
+    Regtest,
+    /// The public Zcash test network
+    Testnet,
+}
+
+impl Network {
+    /// Value of Zebra's `NETWORK` setting
+    pub fn zebra_name(&self) -> &'static str {
+        match self {
+            Network::Regtest => "Regtest",
+            Network::Testnet => "Testnet",
+        }
+    }
+
+    /// BIP70 chain name, as in `getblockchaininfo` and lightwalletd's `GetLightdInfo`
+    pub fn chain_name(&self) -> &'static str {
+        match self {
+            Network::Regtest => "regtest",
+            Network::Testnet => "test",
+        }
+    }
+
+    /// Zebra config file under docker/configs/
+    pub fn zebra_config(&self) -> &'static str {
+        match self {
+            Network::Regtest => "zebra.toml",
+            Network::Testnet => "zebra-testnet.toml",
+        }
+    }
+
+    /// The project's own Zebra config for this network, which generated configs start from
+    pub fn read_zebra_config(&self) -> Result<String> {
+        let path = project_root()?.join("docker").join("configs").join(self.zebra_config());
+        Ok(fs::read_to_string(path)?)
+    }
+
+    /// lightwalletd's zcash.conf under docker/configs/
+    pub fn lightwalletd_config(&self) -> &'static str {
+        match self {
+            Network::Regtest => "zcash.conf",
+            Network::Testnet => "zcash-testnet.conf",
+        }
+    }
+
+    /// Zaino's zindexer.toml under docker/configs/
+    pub fn zaino_config(&self) -> &'static str {
+        match self {
+            Network::Regtest => "zindexer.toml",
+            Network::Testnet => "zindexer-testnet.toml",
+        }
+    }
+
+    /// Environment passed to docker compose to select this network
+    pub fn compose_env(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("ZEBRA_NETWORK", self.zebra_name().to_string()),
+            ("ZEBRA_CONFIG", self.zebra_config().to_string()),
+            ("LIGHTWALLETD_CONF", self.lightwalletd_config().to_string()),
+            ("ZAINO_CONFIG", self.zaino_config().to_string()),
+        ]
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Network::Regtest => write!(f, "regtest"),
+            Network::Testnet => write!(f, "testnet"),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::config::Network;
+use crate::error::{Result, ZecDevError};
+use std::fmt;
+
+/// One `--peers` entry: a node Zebra dials before its seed peers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Peer {
+    /// Hostname, IPv4 address or bracketed IPv6 address (`[::1]`)
+    pub host: String,
+    pub port: u16,
+}
+
+impl fmt::Display for Peer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+/// clap parser for one `--peers HOST:PORT` entry, e.g. `testnet.seeder.zfnd.org:18233`
+pub fn parse_peer(input: &str) -> std::result::Result<Peer, String> {
+    let invalid = || format!("invalid peer '{}' (expected HOST:PORT, e.g. 203.0.113.7:18233)", input);
+
+    let (host, port) = input.trim().rsplit_once(':').ok_or_else(invalid)?;
+    let port: u16 = port.parse().map_err(|_| invalid())?;
+    if port == 0 || !is_host(host) {
+        return Err(invalid());
+    }
+
+    Ok(Peer {
+        host: host.to_ascii_lowercase(),
+        port,
+    })
+}
+
+fn is_host(host: &str) -> bool {
+    if let Some(ip) = host.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+        return ip.parse::<std::net::Ipv6Addr>().is_ok();
+    }
+    !host.is_empty()
+        && !host.starts_with(['.', '-'])
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+}
+
+/// `peers` without repeats, in first-given order
+pub fn dedup(peers: &[Peer]) -> Vec<Peer> {
+    let mut unique: Vec<Peer> = Vec::new();
+    for peer in peers {
+        if !unique.contains(peer) {
+            unique.push(peer.clone());
+        }
+    }
+    unique
+}
+
+/// `base` (Zebra's testnet config) with `peers` as its initial testnet peers
+pub fn zebra_config(base: &str, peers: &[Peer]) -> Result<String> {
+    let mut config: toml::Table = toml::from_str(base)
+        .map_err(|e| ZecDevError::Config(format!("{}: {}", Network::Testnet.zebra_config(), e)))?;
+
+    let peers = peers.iter().map(|p| toml::Value::String(p.to_string())).collect();
+    config
+        .entry("network")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        .as_table_mut()
+        .ok_or_else(|| ZecDevError::Config("[network] in Zebra's config is not a table".into()))?
+        .insert("initial_testnet_peers".to_string(), toml::Value::Array(peers));
+
+    let body = toml::to_string(&config).map_err(|e| ZecDevError::Config(e.to_string()))?;
+    Ok(format!(
+        "# Generated by zecdev up from docker/configs/{}; do not edit\n{}",
+        Network::Testnet.zebra_config(),
+        body
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peers_parse_as_host_and_port() {
+        let peer = parse_peer("Testnet.Seeder.ZFND.org:18233").unwrap();
+        assert_eq!(peer.host, "testnet.seeder.zfnd.org");
+        assert_eq!(peer.port, 18233);
+        assert_eq!(parse_peer("[2001:db8::1]:18233").unwrap().to_string(), "[2001:db8::1]:18233");
+
+        for bad in ["203.0.113.7", "203.0.113.7:", ":18233", "host:0", "host:99999", "ho st:1", "2001:db8::1:18233", "[nope]:1"] {
+            assert!(parse_peer(bad).is_err(), "{} should be rejected", bad);
+        }
+
+        let a = parse_peer("203.0.113.7:18233").unwrap();
+        let b = parse_peer("peer.example:18233").unwrap();
+        assert_eq!(dedup(&[a.clone(), b.clone(), a.clone()]), vec![a, b]);
+    }
+
+    #[test]
+    fn peers_replace_the_initial_testnet_peers() {
+        let base = "[network]\nnetwork = \"Testnet\"\ninitial_testnet_peers = [\"old:1\"]\n\n[state]\ncache_dir = \"/var/zebra/state\"\n";
+        let peers = [parse_peer("203.0.113.7:18233").unwrap()];
+
+        let config: toml::Table = toml::from_str(&zebra_config(base, &peers).unwrap()).unwrap();
+        assert_eq!(config["network"]["network"].as_str(), Some("Testnet"));
+        assert_eq!(
+            config["network"]["initial_testnet_peers"].as_array().unwrap(),
+            &vec![toml::Value::String("203.0.113.7:18233".into())]
+        );
+        assert_eq!(config["state"]["cache_dir"].as_str(), Some("/var/zebra/state"));
+    }
+}
@@ -1,24 +1,102 @@
+use crate::backend::BackendTls;
+use crate::config::Config;
 use serde::{Deserialize, Serialize};
 
+/// Host-side endpoints for the running devnet
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Settings {
     pub zebra_rpc_url: String,
     pub faucet_api_url: String,
+    /// lightwalletd's gRPC endpoint
     pub backend_url: String,
+    pub zaino_url: String,
+    /// Prometheus endpoints, `<base>/metrics`
+    pub zebra_metrics_url: String,
+    pub backend_metrics_url: String,
 }
 
 impl Default for Settings {
     fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
+}
+
+impl Settings {
+    pub fn from_config(config: &Config) -> Self {
+        let scheme = BackendTls::current().scheme();
         Self {
-            zebra_rpc_url: "http://127.0.0.1:8232".to_string(),
-            faucet_api_url: "http://127.0.0.1:8080".to_string(),
-            backend_url: "http://127.0.0.1:9067".to_string(),
+            zebra_rpc_url: format!("http://127.0.0.1:{}", config.zebra_rpc_port),
+            faucet_api_url: format!("http://127.0.0.1:{}", config.faucet_port),
+            backend_url: format!("{}://127.0.0.1:{}", scheme, config.backend_port),
+            zaino_url: format!("{}://127.0.0.1:{}", scheme, config.zaino_port),
+            zebra_metrics_url: format!("http://127.0.0.1:{}", config.zebra_metrics_port),
+            backend_metrics_url: format!("http://127.0.0.1:{}", config.backend_metrics_port),
+        }
+    }
+
+    /// gRPC endpoint of a light-client backend (`lwd` or `zaino`)
+    pub fn backend_url_for(&self, backend: &str) -> &str {
+        match backend {
+            "zaino" => &self.zaino_url,
+            _ => &self.backend_url,
         }
     }
 }
 
 impl Settings {
-    pub fn new() -> Self {
-        Self::default()
+    /// Endpoint variables for the given backends, matching what `up` prints.
+    ///
+    /// `BACKEND_URL` points at the first backend; each one also gets its own
+    /// `LIGHTWALLETD_URL` or `ZAINO_URL`.
+    pub fn env_vars(&self, backends: &[&str]) -> Vec<(&'static str, String)> {
+        let mut vars = vec![
+            ("ZEBRA_RPC_URL", self.zebra_rpc_url.clone()),
+            ("FAUCET_URL", self.faucet_api_url.clone()),
+        ];
+        if let Some(first) = backends.first() {
+            vars.push(("BACKEND_URL", self.backend_url_for(first).to_string()));
+        }
+        for backend in backends {
+            match *backend {
+                "lwd" => vars.push(("LIGHTWALLETD_URL", self.backend_url.clone())),
+                "zaino" => vars.push(("ZAINO_URL", self.zaino_url.clone())),
+                _ => {}
+            }
+        }
+        vars
+    }
+    
+    /// Render `env_vars` as a dotenv file that shells can `source`
+    pub fn to_dotenv(&self, backends: &[&str]) -> String {
+        self.env_vars(backends)
+            .into_iter()
+            .map(|(key, value)| format!("{}={}\n", key, value))
+            .collect()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn dotenv_includes_backend_only_when_selected() {
+        let settings = Settings::default();
+        assert_eq!(
+            settings.to_dotenv(&[]),
+            "ZEBRA_RPC_URL=http://127.0.0.1:8232\nFAUCET_URL=http://127.0.0.1:8080\n"
+        );
+        assert!(settings
+            .to_dotenv(&["zaino"])
+            .ends_with("BACKEND_URL=http://127.0.0.1:9068\nZAINO_URL=http://127.0.0.1:9068\n"));
+    }
+    
+    #[test]
+    fn dotenv_lists_every_backend() {
+        let dotenv = Settings::default().to_dotenv(&["lwd", "zaino"]);
+        assert!(dotenv.contains("BACKEND_URL=http://127.0.0.1:9067\n"));
+        assert!(dotenv.contains("LIGHTWALLETD_URL=http://127.0.0.1:9067\n"));
+        assert!(dotenv.contains("ZAINO_URL=http://127.0.0.1:9068\n"));
+    }
+}
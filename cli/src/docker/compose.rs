@@ -1,142 +1,1927 @@
+use crate::config::{Config, Network};
+use crate::docker::data_dir::DataDir;
+use crate::services::{Service, ServiceSet};
+use crate::docker::options::ComposeOptions;
 use crate::error::{Result, ZecDevError};
-use std::process::{Command, Stdio};
-use std::io::{BufRead, BufReader};
+use crate::docker::runtime::{self, ContainerRuntime};
+use crate::utils::{command_line, is_verbose, log_command, project_root, project_state_dir};
+use colored::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
+/// Log lines included when a container dies during startup
+const CRASH_LOG_LINES: usize = 20;
+
+/// Output lines kept to explain a failed pull or log stream
+const ERROR_TAIL_LINES: usize = 20;
+
+/// Named volume holding Zebra's chain state
+pub const ZEBRA_VOLUME: &str = "zecdev-zebra-data";
+
+/// Small image used to read and write volumes
+const HELPER_IMAGE: &str = "alpine:3.20";
+
+/// Wait before the first retry of a transient failure; doubles after each
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Signs in docker's stderr that a failure is the daemon's momentary trouble,
+/// and that running the same command again may well succeed. A sign matches
+/// when all of its fragments appear.
+const TRANSIENT_ERRORS: &[&[&str]] = &[
+    &["cannot connect to the docker daemon"],
+    &["is the docker daemon running"],
+    &["error during connect"],
+    // Only on the daemon's socket; a refused connection from inside a
+    // container is the command's own failure
+    &["dial unix", "connection refused"],
+    &["connection reset by peer"],
+    &["i/o timeout"],
+    &["tls handshake timeout"],
+    &["context deadline exceeded"],
+    &["resource temporarily unavailable"],
+    &["temporary failure in name resolution"],
+    &["too many requests"],
+    &["unexpected eof"],
+];
+
+/// Start of an imported archive kept in memory, so an import the daemon
+/// failed can start over
+const REPLAY_BYTES: usize = 1 << 20;
+
+/// Stderr kept from an attached command, to tell a daemon failure from the command's own
+const TAIL_BYTES: usize = 4096;
+
+/// How compose commands that run to completion are retried and timed out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Extra attempts after a transient failure
+    pub retries: u32,
+    /// Kill a command that runs longer; `None` waits forever
+    pub timeout: Option<Duration>,
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            retries: config.docker_retries,
+            timeout: (config.docker_timeout_secs > 0).then(|| Duration::from_secs(config.docker_timeout_secs)),
+        }
+    }
+}
+
+/// Compose overrides and environment a command layered over the project,
+/// saved by `up` so `reset` recreates services the way `up` created them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Layers {
+    pub overrides: Vec<PathBuf>,
+    pub env: Vec<(String, String)>,
+}
+
+/// A single service as reported by `docker compose ps`
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceStatus {
+    pub name: String,
+    pub container_id: String,
+    pub state: String,
+    pub health: String,
+    /// Human-readable uptime, e.g. "Up 5 minutes (healthy)"
+    pub status: String,
+    pub ports: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PsEntry {
+    #[serde(rename = "ID", default)]
+    id: String,
+    service: String,
+    state: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    health: String,
+    #[serde(default)]
+    publishers: Option<Vec<Publisher>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Publisher {
+    #[serde(default)]
+    published_port: u16,
+}
+
+impl From<PsEntry> for ServiceStatus {
+    fn from(entry: PsEntry) -> Self {
+        let mut ports: Vec<String> = Vec::new();
+        for publisher in entry.publishers.unwrap_or_default() {
+            // Unpublished ports are reported as 0; IPv4/IPv6 bindings repeat
+            let port = publisher.published_port.to_string();
+            if publisher.published_port != 0 && !ports.contains(&port) {
+                ports.push(port);
+            }
+        }
+
+        let health = if entry.health.is_empty() {
+            "none".to_string()
+        } else {
+            entry.health
+        };
+
+        Self {
+            name: entry.service,
+            container_id: entry.id.chars().take(12).collect(),
+            state: entry.state,
+            health,
+            status: entry.status,
+            ports,
+        }
+    }
+}
+
+/// A running service's resource usage, as reported by `docker stats`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServiceStats {
+    pub name: String,
+    pub cpu_percent: f64,
+    /// Used and limit, e.g. "512MiB / 7.6GiB"
+    pub memory: String,
+    pub memory_percent: f64,
+    /// Received and sent, e.g. "1.2MB / 340kB"
+    pub net_io: String,
+    /// Read and written, e.g. "12MB / 3GB"
+    pub block_io: String,
+}
+
+/// A `docker stats` line; Podman spells the keys differently
+#[derive(Debug, Deserialize)]
+struct StatsEntry {
+    #[serde(rename = "ID", alias = "id", default)]
+    id: String,
+    #[serde(rename = "CPUPerc", alias = "cpu_percent", default)]
+    cpu: String,
+    #[serde(rename = "MemUsage", alias = "mem_usage", default)]
+    memory: String,
+    #[serde(rename = "MemPerc", alias = "mem_percent", default)]
+    memory_percent: String,
+    #[serde(rename = "NetIO", alias = "net_io", default)]
+    net_io: String,
+    #[serde(rename = "BlockIO", alias = "block_io", default)]
+    block_io: String,
+}
+
+/// `"12.5%"` as 12.5; `--` (a container that just stopped) as 0
+fn parse_percent(value: &str) -> f64 {
+    value.trim().trim_end_matches('%').parse().unwrap_or(0.0)
+}
+
+/// One service's container as reported by `docker inspect`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceInfo {
+    pub service: String,
+    pub container_id: String,
+    pub image: String,
+    /// Content digest the image was pulled by; locally built images have none
+    pub image_digest: Option<String>,
+    pub state: String,
+    pub started_at: String,
+    pub restart_count: u32,
+    /// Health state, or "none" when the service defines no health check
+    pub health: String,
+    pub health_log: Vec<HealthProbe>,
+    pub mounts: Vec<MountInfo>,
+    /// Published ports as "host -> container/proto"
+    pub ports: Vec<String>,
+}
+
+/// A single recorded health check run
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct HealthProbe {
+    pub start: String,
+    pub exit_code: i64,
+    pub output: String,
+}
+
+/// A volume or bind mount attached to a container
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountInfo {
+    /// Volume name, or the host path for bind mounts
+    pub source: String,
+    pub destination: String,
+    pub read_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectEntry {
+    id: String,
+    image: String,
+    #[serde(default)]
+    restart_count: u32,
+    state: InspectState,
+    config: InspectConfig,
+    #[serde(default)]
+    mounts: Vec<InspectMount>,
+    network_settings: InspectNetwork,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectState {
+    status: String,
+    #[serde(default)]
+    started_at: String,
+    #[serde(default)]
+    health: Option<InspectHealth>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectHealth {
+    status: String,
+    #[serde(default)]
+    log: Option<Vec<HealthProbe>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectConfig {
+    image: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectMount {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    source: String,
+    destination: String,
+    #[serde(rename = "RW", default = "default_true")]
+    rw: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectNetwork {
+    #[serde(default)]
+    ports: Option<HashMap<String, Option<Vec<InspectBinding>>>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectBinding {
+    #[serde(default)]
+    host_port: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ImageEntry {
+    #[serde(default)]
+    repo_digests: Option<Vec<String>>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl ServiceInfo {
+    fn from_inspect(service: &str, entry: InspectEntry, image_digest: Option<String>) -> Self {
+        let (health, health_log) = match entry.state.health {
+            Some(h) => (h.status, h.log.unwrap_or_default()),
+            None => ("none".to_string(), Vec::new()),
+        };
+
+        let mounts = entry
+            .mounts
+            .into_iter()
+            .map(|m| MountInfo {
+                source: m.name.filter(|n| !n.is_empty()).unwrap_or(m.source),
+                destination: m.destination,
+                read_only: !m.rw,
+            })
+            .collect();
+
+        // Published ports; the same port bound on IPv4 and IPv6 is listed once
+        let mut ports: Vec<String> = Vec::new();
+        let mut bindings: Vec<_> = entry.network_settings.ports.unwrap_or_default().into_iter().collect();
+        bindings.sort_by(|a, b| a.0.cmp(&b.0));
+        for (container_port, hosts) in bindings {
+            for host in hosts.unwrap_or_default() {
+                let port = format!("{} -> {}", host.host_port, container_port);
+                if !host.host_port.is_empty() && !ports.contains(&port) {
+                    ports.push(port);
+                }
+            }
+        }
+
+        Self {
+            service: service.to_string(),
+            container_id: entry.id.chars().take(12).collect(),
+            image: entry.config.image,
+            image_digest,
+            state: entry.state.status,
+            started_at: entry.state.started_at,
+            restart_count: entry.restart_count,
+            health,
+            health_log,
+            mounts,
+            ports,
+        }
+    }
+}
+
+/// Leftover resources belonging to the compose project
+#[derive(Debug, Default, PartialEq)]
+pub struct StaleResources {
+    pub containers: Vec<String>,
+    pub volumes: Vec<String>,
+    pub networks: Vec<String>,
+}
+
+impl StaleResources {
+    pub fn is_empty(&self) -> bool {
+        self.containers.is_empty() && self.volumes.is_empty() && self.networks.is_empty()
+    }
+}
+
+#[derive(Clone)]
 pub struct DockerCompose {
+    runtime: Arc<dyn ContainerRuntime>,
     project_dir: String,
+    env: Vec<(&'static str, String)>,
+    global_args: Vec<String>,
+    dry_run: bool,
+    retry: RetryPolicy,
+    data_dir: Option<DataDir>,
+    layers: Layers,
+    /// In a dry run, the `-f` args of overrides that were never written, so
+    /// only the printed commands carry them
+    planned_files: Vec<String>,
 }
 
 impl DockerCompose {
     pub fn new() -> Result<Self> {
-        // Get project root (go up from cli/ directory)
-        let current_dir = std::env::current_dir()?;
-        let project_dir = if current_dir.ends_with("cli") {
-            current_dir.parent().unwrap().to_path_buf()
-        } else {
-            current_dir
-        };
+        // Fail up front with a clear message instead of a socket error mid-command
+        let runtime = runtime::detect()?;
+        if !runtime.is_running() {
+            return Err(ZecDevError::DockerUnavailable(runtime.display_name()));
+        }
 
-        Ok(Self {
+        let project_dir = project_root()?;
+        let config = Config::load()?;
+        let options = ComposeOptions::current();
+
+        let mut compose = Self {
+            runtime,
             project_dir: project_dir.to_string_lossy().to_string(),
-        })
+            env: config.compose_env(),
+            global_args: options.global_args(&project_dir),
+            dry_run: options.dry_run,
+            retry: RetryPolicy::from_config(&config),
+            data_dir: None,
+            layers: Layers::default(),
+            planned_files: Vec::new(),
+        };
+        // Applied to every command, so `reset`, `restart` and the rest see the same mounts as `up`
+        if let Some(root) = options.data_dir {
+            let data_dir = DataDir::new(root);
+            if !compose.dry_run {
+                data_dir.create()?;
+            }
+            let path = compose.write_generated("data-dir.yml", &data_dir.to_override_yaml())?;
+            compose.add_file(&path);
+            compose.data_dir = Some(data_dir);
+        }
+        Ok(compose)
+    }
+
+    /// The `--data-dir` holding service state, if one was given
+    pub fn data_dir(&self) -> Option<&DataDir> {
+        self.data_dir.as_ref()
+    }
+
+    /// What to mount for a named volume: the volume, or the `--data-dir`
+    /// subdirectory that stands in for it
+    fn volume_source(&self, volume: &str) -> String {
+        self.data_dir
+            .as_ref()
+            .and_then(|d| d.volume_dir(volume))
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_else(|| volume.to_string())
+    }
+
+    /// Empty the `--data-dir` subdirectories of `services` from a helper
+    /// container, as their files belong to root; in a dry run, say so instead
+    fn clear_data(&self, services: &[Service]) -> Result<()> {
+        let Some(args) = self.data_dir.as_ref().and_then(|d| d.clear_args(services, HELPER_IMAGE)) else {
+            return Ok(());
+        };
+        let mut cmd = self.runtime.command();
+        cmd.args(args);
+        if self.skip(&cmd) {
+            return Ok(());
+        }
+
+        self.run_with_retry(&mut cmd)?;
+        Ok(())
+    }
+
+    /// Pass extra environment to every compose invocation
+    pub fn with_env(mut self, env: Vec<(&'static str, String)>) -> Self {
+        self.layers.env.extend(env.into_iter().map(|(k, v)| (k.to_string(), v)));
+        self
+    }
+
+    /// Layer an extra compose file over the project's own; one already
+    /// layered is left where it is
+    pub fn with_override(mut self, path: &Path) -> Self {
+        if !self.layers.overrides.iter().any(|p| p == path) {
+            self.add_file(path);
+            self.layers.overrides.push(path.to_path_buf());
+        }
+        self
+    }
+
+    /// Reapply the overrides and environment another command layered, as
+    /// `reset` does with `up`'s
+    pub fn with_layers(mut self, layers: &Layers) -> Self {
+        for path in &layers.overrides {
+            self = self.with_override(path);
+        }
+        for var in &layers.env {
+            if !self.layers.env.contains(var) {
+                self.layers.env.push(var.clone());
+            }
+        }
+        self
+    }
+
+    /// Overrides and environment layered so far, for `DevnetState`
+    pub fn layers(&self) -> &Layers {
+        &self.layers
+    }
+
+    /// Pass compose `-f path`; in a dry run, only in the printed commands.
+    ///
+    /// Passing any `-f` stops compose from finding docker-compose.yml itself,
+    /// so the base file is named explicitly unless `--file` already did.
+    fn add_file(&mut self, path: &Path) {
+        let mut args = Vec::new();
+        if !self.global_args.iter().chain(&self.planned_files).any(|a| a == "-f") {
+            let base = Path::new(&self.project_dir).join("docker-compose.yml");
+            args.push("-f".to_string());
+            args.push(base.to_string_lossy().to_string());
+        }
+        args.push("-f".to_string());
+        args.push(path.to_string_lossy().to_string());
+
+        if self.dry_run {
+            self.planned_files.extend(args);
+        } else {
+            self.global_args.extend(args);
+        }
+    }
+
+    /// Where this project keeps its state, lock and generated files
+    pub fn state_dir(&self) -> Result<PathBuf> {
+        Ok(project_state_dir(&self.project_name()?)?)
+    }
+
+    /// Write a generated file, such as a compose override, into the project's
+    /// state directory and return its path; a dry run only prints the path.
+    ///
+    /// Each project gets its own copy, so two projects starting at once can't
+    /// swap each other's overrides.
+    pub fn write_generated(&self, name: &str, contents: &str) -> Result<PathBuf> {
+        let dir = self.state_dir()?;
+        let path = dir.join(name);
+        if self.dry_run {
+            println!("{} write {}", "$".dimmed(), path.display());
+            return Ok(path);
+        }
+
+        fs::create_dir_all(&dir)?;
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    /// Write `config` as the project's generated Zebra config for `network`
+    /// (`zebra-<network>.toml`), and an override `name` mounting it in place of
+    /// the project's zebrad.toml, returning the override's path
+    pub fn write_zebra_config_override(&self, network: Network, config: &str, name: &str) -> Result<PathBuf> {
+        let config = self.write_generated(&format!("zebra-{}.toml", network), config)?;
+
+        // Single-quoted so Windows paths need no escaping
+        let source = config.to_string_lossy().replace('\'', "''");
+        self.write_generated(
+            name,
+            &format!(
+                "# Generated by zecdev up; do not edit\nservices:\n  zebra:\n    volumes:\n      - '{}:/etc/zebrad/zebrad.toml:ro'\n",
+                source
+            ),
+        )
+    }
+
+    /// Enable compose profiles, so their services can be started and queried
+    pub fn with_profiles(mut self, profiles: &[String]) -> Self {
+        for profile in profiles {
+            self.global_args.push("--profile".to_string());
+            self.global_args.push(profile.clone());
+        }
+        self
+    }
+
+    /// A `<runtime> compose` command rooted at the project with config overrides applied
+    fn compose(&self) -> Command {
+        let mut cmd = self.runtime.compose();
+        cmd.args(&self.global_args)
+            .current_dir(&self.project_dir)
+            .envs(self.env.iter().map(|(k, v)| (*k, v.as_str())))
+            .envs(self.layers.env.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        cmd
+    }
+
+    /// Whether `--dry-run` is in effect
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// With `--dry-run`, print a command that would change the devnet instead of running it.
+    ///
+    /// Returns true when the command should be skipped. Read-only queries
+    /// don't go through here, so dry runs still see the real state.
+    fn skip(&self, cmd: &Command) -> bool {
+        if self.dry_run {
+            println!("{} {}", "$".dimmed(), self.planned_line(cmd));
+        }
+        self.dry_run
+    }
+
+    /// `cmd` as a dry run prints it: a compose command gets the overrides a
+    /// real run would layer, after the usual global args
+    fn planned_line(&self, cmd: &Command) -> String {
+        let prefix = self.runtime.compose();
+        let args: Vec<&std::ffi::OsStr> = cmd.get_args().collect();
+        let is_compose =
+            cmd.get_program() == prefix.get_program() && args.iter().copied().take(prefix.get_args().len()).eq(prefix.get_args());
+        if self.planned_files.is_empty() || !is_compose {
+            return command_line(cmd);
+        }
+
+        let split = prefix.get_args().len() + self.global_args.len();
+        let mut planned = Command::new(cmd.get_program());
+        if let Some(dir) = cmd.get_current_dir() {
+            planned.current_dir(dir);
+        }
+        for (key, value) in cmd.get_envs() {
+            if let Some(value) = value {
+                planned.env(key, value);
+            }
+        }
+        planned.args(&args[..split]).args(&self.planned_files).args(&args[split..]);
+        command_line(&planned)
+    }
+
+    /// Run `cmd` to completion, turning a non-zero exit into a Docker error.
+    ///
+    /// Failures that look like the daemon's momentary trouble are retried
+    /// with backoff, and a command that outlives the timeout is killed.
+    pub fn run_with_retry(&self, cmd: &mut Command) -> Result<Output> {
+        self.run_with_policy(cmd, self.retry)
+    }
+
+    /// `run_with_retry` without the timeout, for commands that may
+    /// legitimately download for a long time
+    fn run_untimed(&self, cmd: &mut Command) -> Result<Output> {
+        self.run_with_policy(cmd, RetryPolicy { timeout: None, ..self.retry })
+    }
+
+    fn run_with_policy(&self, cmd: &mut Command, policy: RetryPolicy) -> Result<Output> {
+        self.retrying(cmd, policy.retries, |cmd| {
+            let output = output_within(cmd, policy.timeout)?;
+            if output.status.success() {
+                return Ok(Ok(output));
+            }
+
+            let error = String::from_utf8_lossy(&output.stderr).to_string();
+            tracing::debug!(status = %output.status, stderr = %error.trim_end(), "command failed");
+            Ok(Err(error))
+        })?
+        .map_err(ZecDevError::Docker)
+    }
+
+    /// Run `cmd` through `attempt` until it succeeds, retrying with backoff up
+    /// to `retries` times while the stderr a failed attempt gives back looks
+    /// transient. The last failure's stderr is returned for the caller to report.
+    fn retrying<T>(
+        &self,
+        cmd: &mut Command,
+        retries: u32,
+        mut attempt: impl FnMut(&mut Command) -> Result<std::result::Result<T, String>>,
+    ) -> Result<std::result::Result<T, String>> {
+        let _span = log_command(cmd);
+        let mut backoff = RETRY_BACKOFF;
+
+        for n in 0.. {
+            let error = match attempt(cmd)? {
+                Ok(value) => return Ok(Ok(value)),
+                Err(error) => error,
+            };
+            if n >= retries || !is_transient(&error) {
+                return Ok(Err(error));
+            }
+
+            note_retry(cmd, &error, backoff, n, retries);
+            thread::sleep(backoff);
+            backoff *= 2;
+        }
+        unreachable!("the loop only ends by returning")
+    }
+
+    /// Run `cmd` attached to this terminal and return its exit code.
+    ///
+    /// Stderr still reaches the terminal, through a pipe, so a daemon failure
+    /// can be spotted and retried; the command never ran in the container then.
+    fn run_attached(&self, cmd: &mut Command) -> Result<i32> {
+        self.retrying(cmd, self.retry.retries, |cmd| {
+            let mut child = cmd
+                .stdin(Stdio::inherit())
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::piped())
+                .spawn()?;
+
+            let mut pipe = child.stderr.take().expect("stderr is piped");
+            let mut terminal = std::io::stderr();
+            let (mut buf, mut tail) = ([0u8; 4096], Vec::new());
+            loop {
+                let n = pipe.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                terminal.write_all(&buf[..n])?;
+                tail.extend_from_slice(&buf[..n]);
+                tail.drain(..tail.len().saturating_sub(TAIL_BYTES));
+            }
+
+            let status = child.wait()?;
+            let error = String::from_utf8_lossy(&tail).to_string();
+            if status.success() || !is_transient(&error) {
+                // A missing code means the process was killed by a signal
+                return Ok(Ok(status.code().unwrap_or(1)));
+            }
+            Ok(Err(error))
+        })?
+        .map_err(ZecDevError::Docker)
     }
 
     pub fn up(&self, services: &[&str]) -> Result<()> {
-        let mut cmd = Command::new("docker");
-        cmd.arg("compose")
-            .arg("up")
-            .arg("-d")
-            .current_dir(&self.project_dir);
+        self.up_with(services, false)
+    }
+
+    /// `up`, recreating the containers even if nothing about them changed
+    pub fn recreate(&self, services: &[&str]) -> Result<()> {
+        self.up_with(services, true)
+    }
+
+    fn up_with(&self, services: &[&str], force_recreate: bool) -> Result<()> {
+        // Missing images are pulled on their own, as that can take any amount
+        // of time; `up` itself is then timed like any other command
+        let mut pull = self.compose();
+        pull.args(["pull", "--quiet", "--ignore-buildable", "--policy", "missing"]).args(services);
+
+        let mut cmd = self.compose();
+        cmd.arg("up").arg("-d");
+        if force_recreate {
+            cmd.arg("--force-recreate");
+        }
 
         for service in services {
             cmd.arg(service);
         }
 
-        let output = cmd.output()?;
+        if self.skip(&pull) {
+            self.skip(&cmd);
+            return Ok(());
+        }
+
+        self.run_untimed(&mut pull).map_err(|e| match e {
+            ZecDevError::Docker(stderr) => pull_error(services, &stderr),
+            e => e,
+        })?;
+        self.run_with_retry(&mut cmd)?;
+        Ok(())
+    }
+
+    /// Pull the latest images for `services`; locally built services are skipped
+    pub fn pull(&self, services: &[&str]) -> Result<()> {
+        let mut cmd = self.compose();
+        cmd.arg("pull").arg("--quiet").arg("--ignore-buildable");
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(ZecDevError::Docker(error.to_string()));
+        for service in services {
+            cmd.arg(service);
+        }
+        if self.skip(&cmd) {
+            return Ok(());
         }
 
+        // Downloads take as long as they take, so only the retries apply
+        self.run_untimed(&mut cmd).map_err(|e| match e {
+            ZecDevError::Docker(stderr) => pull_error(services, &stderr),
+            e => e,
+        })?;
         Ok(())
     }
 
-    pub fn down(&self, volumes: bool) -> Result<()> {
-        let mut cmd = Command::new("docker");
-        cmd.arg("compose")
-            .arg("down")
-            .current_dir(&self.project_dir);
+    /// Pull one service's image, handing each line of compose's progress
+    /// output to `on_line` as it arrives (e.g. `<layer> Downloading ...`)
+    ///
+    /// Transient failures are retried like `run_with_retry`; the layers
+    /// already downloaded are kept, so a retry picks up where it stopped.
+    pub fn pull_with_progress(&self, service: &str, mut on_line: impl FnMut(&str)) -> Result<()> {
+        let mut cmd = self.compose();
+        cmd.arg("pull").arg("--ignore-buildable").arg(service);
+        if self.skip(&cmd) {
+            return Ok(());
+        }
 
-        if volumes {
-            cmd.arg("-v");
+        self.retrying(&mut cmd, self.retry.retries, |cmd| pull_attempt(cmd, &mut on_line).map(|e| e.map_or(Ok(()), Err)))?
+            .map_err(|error| pull_error(&[service], &error))
+    }
+
+    /// Build images for the `services` that have a `build:` section.
+    ///
+    /// With `--verbose` the build output streams to the terminal; otherwise it
+    /// is captured and only shown if the build fails.
+    pub fn build(&self, services: &[&str]) -> Result<()> {
+        let mut cmd = self.compose();
+        cmd.arg("build");
+
+        for service in services {
+            cmd.arg(service);
+        }
+        if self.skip(&cmd) {
+            return Ok(());
+        }
+
+        if !is_verbose() {
+            self.run_untimed(&mut cmd)?;
+            return Ok(());
         }
 
-        let output = cmd.output()?;
+        let _span = log_command(&cmd);
+        let status = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(ZecDevError::Docker(error.to_string()));
+        if !status.success() {
+            return Err(ZecDevError::Docker(format!(
+                "docker compose build exited with {}",
+                status
+            )));
         }
 
         Ok(())
     }
 
-    pub fn ps(&self) -> Result<Vec<String>> {
-        let output = Command::new("docker")
-            .arg("compose")
-            .arg("ps")
-            .arg("--format")
-            .arg("table")
-            .current_dir(&self.project_dir)
-            .output()?;
+    pub fn stop(&self, services: &[&str]) -> Result<()> {
+        let mut cmd = self.compose();
+        cmd.arg("stop");
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(ZecDevError::Docker(error.to_string()));
+        for service in services {
+            cmd.arg(service);
         }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let lines: Vec<String> = stdout
-            .lines()
-            .skip(1) // Skip header
-            .map(|l| l.to_string())
-            .collect();
+        if self.skip(&cmd) {
+            return Ok(());
+        }
+
+        self.run_with_retry(&mut cmd)?;
+        Ok(())
+    }
+
+    pub fn start(&self, services: &[&str]) -> Result<()> {
+        let mut cmd = self.compose();
+        cmd.arg("start");
+
+        for service in services {
+            cmd.arg(service);
+        }
 
-        Ok(lines)
+        if self.skip(&cmd) {
+            return Ok(());
+        }
+
+        self.run_with_retry(&mut cmd)?;
+        Ok(())
     }
 
-    pub fn logs(&self, service: &str, tail: usize) -> Result<Vec<String>> {
-        let output = Command::new("docker")
-            .arg("compose")
-            .arg("logs")
-            .arg("--tail")
-            .arg(tail.to_string())
-            .arg(service)
-            .current_dir(&self.project_dir)
-            .output()?;
+    pub fn restart(&self, services: &[&str]) -> Result<()> {
+        let mut cmd = self.compose();
+        cmd.arg("restart");
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(ZecDevError::Docker(error.to_string()));
+        for service in services {
+            cmd.arg(service);
         }
 
+        if self.skip(&cmd) {
+            return Ok(());
+        }
+
+        self.run_with_retry(&mut cmd)?;
+        Ok(())
+    }
+
+    /// Stop and remove `services`' containers, leaving their volumes and the rest of the devnet alone
+    pub fn remove(&self, services: &[&str]) -> Result<()> {
+        let mut cmd = self.compose();
+        cmd.args(["rm", "--stop", "--force"]);
+
+        for service in services {
+            cmd.arg(service);
+        }
+
+        if self.skip(&cmd) {
+            return Ok(());
+        }
+
+        self.run_with_retry(&mut cmd)?;
+        Ok(())
+    }
+
+    /// Delete a named volume; one that doesn't exist is not an error.
+    /// With `--data-dir`, the directory standing in for it is emptied too.
+    pub fn remove_volume(&self, volume: &str) -> Result<()> {
+        let mut cmd = self.runtime.command();
+        cmd.args(["volume", "rm", "--force", volume]);
+        let services: Vec<Service> = ServiceSet::ALL.into_iter().filter(|s| s.volume == Some(volume)).collect();
+        if self.skip(&cmd) {
+            return self.clear_data(&services);
+        }
+
+        self.run_with_retry(&mut cmd)?;
+        self.clear_data(&services)
+    }
+
+    /// Stop and remove the devnet containers.
+    ///
+    /// Named volumes (Zebra chain state, faucet wallet) are kept unless
+    /// `remove_volumes` is set.
+    pub fn down(&self, remove_volumes: bool) -> Result<()> {
+        let mut cmd = self.compose();
+        cmd.args(Self::down_args(remove_volumes));
+        let skipped = self.skip(&cmd);
+        if !skipped {
+            self.run_with_retry(&mut cmd)?;
+        }
+
+        if remove_volumes {
+            self.clear_data(&DataDir::services())?;
+        }
+        Ok(())
+    }
+
+    fn down_args(remove_volumes: bool) -> Vec<&'static str> {
+        let mut args = vec!["down"];
+        if remove_volumes {
+            args.push("-v");
+        }
+        args
+    }
+
+    /// Status of every ZecKit container, including stopped ones
+    pub fn ps(&self) -> Result<Vec<ServiceStatus>> {
+        let output = self.run_with_retry(
+            self.compose()
+                .arg("ps")
+                .arg("--all")
+                .arg("--format")
+                .arg("json"),
+        )?;
+
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let lines: Vec<String> = stdout.lines().map(|l| l.to_string()).collect();
+        let entries: Vec<PsEntry> = parse_json_entries(&stdout)?;
+
+        Ok(entries.into_iter().map(ServiceStatus::from).collect())
+    }
+
+    /// CPU, memory and I/O of each running ZecKit container, in `ps` order.
+    ///
+    /// Only this project's containers are asked for, so other containers on
+    /// the host never show up.
+    pub fn stats(&self) -> Result<Vec<ServiceStats>> {
+        let running: Vec<ServiceStatus> = self.ps()?.into_iter().filter(|s| s.state == "running").collect();
+        if running.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut cmd = self.runtime.command();
+        cmd.args(["stats", "--no-stream", "--format", "json"]);
+        for service in &running {
+            cmd.arg(&service.container_id);
+        }
+        let output = self.run_with_retry(&mut cmd)?;
+        let entries: Vec<StatsEntry> = parse_json_entries(&String::from_utf8_lossy(&output.stdout))?;
+
+        Ok(match_stats(&running, entries))
+    }
+
+    /// Fail if any of `services` has exited, is crash-looping or is unhealthy.
+    ///
+    /// The error names the container and carries its last log lines.
+    pub fn check_all_running(&self, services: &[&str]) -> Result<()> {
+        let statuses = self.ps()?;
+
+        match find_failed(&statuses, services) {
+            Some((service, state)) => Err(ZecDevError::ServiceCrashed {
+                logs: self.tail_logs(&service, CRASH_LOG_LINES).unwrap_or_default(),
+                service,
+                state,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The last `lines` of a service's logs, captured rather than streamed
+    pub fn tail_logs(&self, service: &str, lines: usize) -> Result<String> {
+        let output = self.run_with_retry(
+            self.compose()
+                .arg("logs")
+                .arg("--no-color")
+                .arg("--no-log-prefix")
+                .arg(format!("--tail={}", lines))
+                .arg(service),
+        )?;
+
+        // Containers log to both streams; compose passes them through as-is
+        let mut logs = String::from_utf8_lossy(&output.stdout).to_string();
+        logs.push_str(&String::from_utf8_lossy(&output.stderr));
+        Ok(logs.trim_end().to_string())
+    }
+
+    /// Stream logs for the given services to the terminal.
+    ///
+    /// With `follow` set this blocks until compose exits (usually on Ctrl-C).
+    /// Compose's own errors pass through stderr, and transient ones are retried.
+    pub fn logs(&self, services: &[&str], follow: bool, tail: Option<usize>) -> Result<()> {
+        let mut cmd = self.compose();
+        cmd.arg("logs");
+
+        if follow {
+            cmd.arg("--follow");
+        }
+
+        if let Some(n) = tail {
+            cmd.arg(format!("--tail={}", n));
+        }
+
+        for service in services {
+            cmd.arg(service);
+        }
+
+        let _span = log_command(&cmd);
+        let mut backoff = RETRY_BACKOFF;
+        for attempt in 0.. {
+            let mut child = cmd
+                .stdin(Stdio::null())
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::piped())
+                .spawn()?;
+
+            let mut tail: Vec<String> = Vec::new();
+            for line in BufReader::new(child.stderr.take().expect("stderr is piped")).lines() {
+                let line = line?;
+                eprintln!("{}", line);
+                tail.push(line);
+                if tail.len() > ERROR_TAIL_LINES {
+                    tail.remove(0);
+                }
+            }
+
+            let status = child.wait()?;
+            if status.success() {
+                return Ok(());
+            }
+            let error = tail.join("\n");
+            if attempt >= self.retry.retries || !is_transient(&error) {
+                return Err(ZecDevError::Docker(format!(
+                    "docker compose logs exited with {}",
+                    status
+                )));
+            }
+            note_retry(&cmd, &error, backoff, attempt, self.retry.retries);
+            thread::sleep(backoff);
+            backoff *= 2;
+        }
+        unreachable!("the loop only ends by returning")
+    }
+
+    /// `logs --follow` for the given services, for the caller to spawn and
+    /// stop, e.g. to pause tailing while a service restarts.
+    ///
+    /// With `since` (a Unix timestamp), only lines logged from then on are shown.
+    pub fn follow_logs_command(&self, services: &[&str], since: Option<u64>) -> Command {
+        let mut cmd = self.compose();
+        cmd.arg("logs").arg("--follow");
+
+        if let Some(since) = since {
+            cmd.arg(format!("--since={}", since));
+        }
+
+        for service in services {
+            cmd.arg(service);
+        }
+        cmd
+    }
+
+    /// All services defined in the compose file, including profile-gated ones
+    pub fn services(&self) -> Result<Vec<String>> {
+        let output = self.run_with_retry(
+            self.compose()
+                .arg("--profile")
+                .arg("*")
+                .arg("config")
+                .arg("--services"),
+        )?;
+
+        Ok(parse_lines(&output.stdout))
+    }
+
+    /// Each service's resolved image, or `None` for services only built locally.
+    ///
+    /// Profile-gated services are included, like `services()`.
+    pub fn service_images(&self) -> Result<Vec<(String, Option<String>)>> {
+        let output = self.run_with_retry(
+            self.compose()
+                .args(["--profile", "*"])
+                .args(["config", "--format", "json"]),
+        )?;
+        let config: serde_json::Value = serde_json::from_slice(&output.stdout)?;
 
-        Ok(lines)
+        Ok(parse_service_images(&config))
     }
 
-    pub fn exec(&self, service: &str, command: &[&str]) -> Result<String> {
-        let mut cmd = Command::new("docker");
-        cmd.arg("compose")
-            .arg("exec")
-            .arg("-T") // Non-interactive
-            .arg(service)
-            .current_dir(&self.project_dir);
+    /// Services enabled by default or by the profiles from `with_profiles`
+    pub fn enabled_services(&self) -> Result<Vec<String>> {
+        let output = self.run_with_retry(self.compose().arg("config").arg("--services"))?;
+        Ok(parse_lines(&output.stdout))
+    }
+
+    /// Every profile named in the compose file
+    pub fn profiles(&self) -> Result<Vec<String>> {
+        let output = self.run_with_retry(self.compose().arg("config").arg("--profiles"))?;
+        Ok(parse_lines(&output.stdout))
+    }
+
+    /// Services that currently have a running container
+    pub fn running_services(&self) -> Result<Vec<String>> {
+        let output = self.run_with_retry(
+            self.compose()
+                .arg("ps")
+                .arg("--services")
+                .arg("--filter")
+                .arg("status=running"),
+        )?;
+
+        Ok(parse_lines(&output.stdout))
+    }
+
+    /// Run a command inside a service container, attached to this terminal.
+    ///
+    /// Returns the inner command's exit code.
+    pub fn exec(&self, service: &str, command: &[&str], interactive: bool) -> Result<i32> {
+        let mut cmd = self.compose();
+        cmd.arg("exec");
+
+        if !interactive {
+            cmd.arg("-T"); // No TTY
+        }
+
+        cmd.arg(service);
 
         for arg in command {
             cmd.arg(arg);
         }
 
-        let output = cmd.output()?;
+        self.run_attached(&mut cmd)
+    }
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(ZecDevError::Docker(error.to_string()));
+    /// Open an interactive shell in a service container, starting in `workdir`
+    /// with a prompt that names the service.
+    ///
+    /// Uses bash when the image has it, else sh. Returns the shell's exit code.
+    pub fn shell(&self, service: &str, workdir: Option<&str>) -> Result<i32> {
+        // bash's rc files would replace the prompt; sh has no \w escape
+        let script = format!(
+            "if command -v bash >/dev/null 2>&1; then \
+             PS1='[zeckit:{0}] \\w \\$ ' exec bash --norc -i; \
+             else PS1='[zeckit:{0}] $ ' exec sh -i; fi",
+            service
+        );
+
+        let mut cmd = self.compose();
+        cmd.arg("exec");
+        if let Some(dir) = workdir {
+            cmd.arg("--workdir").arg(dir);
         }
+        cmd.arg(service).args(["sh", "-c", &script]);
 
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        self.run_attached(&mut cmd)
+    }
+
+    /// Stream a tar archive of a named volume's contents into `writer`.
+    ///
+    /// Returns the number of uncompressed bytes written.
+    pub fn export_volume(&self, volume: &str, writer: &mut impl Write) -> Result<u64> {
+        let mut cmd = self.runtime.command();
+        cmd.arg("run")
+            .arg("--rm")
+            .arg("-v")
+            .arg(format!("{}:/data:ro", self.volume_source(volume)))
+            .arg(HELPER_IMAGE)
+            .args(["tar", "-C", "/data", "-cf", "-", "."]);
+
+        self.retrying(&mut cmd, self.retry.retries, |cmd| {
+            let mut child = cmd
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()?;
+
+            let mut stdout = child.stdout.take().expect("stdout is piped");
+            let bytes = std::io::copy(&mut stdout, writer)?;
+
+            let output = child.wait_with_output()?;
+            if output.status.success() {
+                return Ok(Ok(bytes));
+            }
+            let error = String::from_utf8_lossy(&output.stderr).to_string();
+            // Once part of the archive is out, a retry would write it twice
+            if bytes > 0 {
+                return Err(ZecDevError::Docker(error));
+            }
+            Ok(Err(error))
+        })?
+        .map_err(ZecDevError::Docker)
+    }
+
+    /// Replace a named volume's contents with the tar archive read from `reader`
+    pub fn import_volume(&self, volume: &str, reader: &mut impl Read) -> Result<()> {
+        let mut cmd = self.runtime.command();
+        cmd.arg("run")
+            .arg("--rm")
+            .arg("-i")
+            .arg("-v")
+            .arg(format!("{}:/data", self.volume_source(volume)))
+            .arg(HELPER_IMAGE)
+            .args([
+                "sh",
+                "-c",
+                "find /data -mindepth 1 -delete && tar -C /data -xf -",
+            ]);
+
+        let mut archive = Replay::new(reader, REPLAY_BYTES);
+        self.retrying(&mut cmd, self.retry.retries, |cmd| {
+            let mut child = cmd
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .spawn()?;
+
+            // A daemon failure closes the pipe early; its stderr says more than the write error
+            let copied = {
+                let mut stdin = child.stdin.take().expect("stdin is piped");
+                std::io::copy(&mut archive, &mut stdin)
+            };
+
+            let output = child.wait_with_output()?;
+            if output.status.success() {
+                copied?;
+                return Ok(Ok(()));
+            }
+            let error = String::from_utf8_lossy(&output.stderr).to_string();
+            if !archive.rewind() {
+                return Err(ZecDevError::Docker(error));
+            }
+            Ok(Err(error))
+        })?
+        .map_err(ZecDevError::Docker)
+    }
+
+    /// Details of a service's container, stopped or not, from `docker inspect`
+    pub fn inspect(&self, service: &str) -> Result<ServiceInfo> {
+        let output = self.run_with_retry(self.compose().args(["ps", "--all", "--quiet", service]))?;
+        let container = match parse_lines(&output.stdout).into_iter().next() {
+            Some(id) => id,
+            None => return Err(ZecDevError::ServiceNotRunning(service.to_string())),
+        };
+
+        let output = self.run_with_retry(self.runtime.command().arg("inspect").arg(&container))?;
+        let entry: InspectEntry = serde_json::from_slice::<Vec<InspectEntry>>(&output.stdout)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ZecDevError::Docker(format!("no container {} to inspect", container)))?;
+
+        // Best effort: a digest is nice to have but not worth failing over
+        let image_digest = self.run_with_retry(self.runtime.command().args(["image", "inspect", &entry.image]))
+            .ok()
+            .and_then(|output| serde_json::from_slice::<Vec<ImageEntry>>(&output.stdout).ok())
+            .and_then(|images| images.into_iter().next())
+            .and_then(|image| image.repo_digests.unwrap_or_default().into_iter().next());
+
+        Ok(ServiceInfo::from_inspect(service, entry, image_digest))
+    }
+
+    /// The compose model with every file merged and variables interpolated, as YAML
+    pub fn resolved_config(&self) -> Result<String> {
+        let output = self.run_with_retry(self.compose().arg("config"))?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// The compose project name; every resource compose creates is labelled with it
+    pub fn project_name(&self) -> Result<String> {
+        let output = self.run_with_retry(self.compose().args(["config", "--format", "json"]))?;
+        let config: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+        config
+            .get("name")
+            .and_then(|n| n.as_str())
+            .map(|n| n.to_string())
+            .ok_or_else(|| ZecDevError::Docker("compose config did not report a project name".into()))
+    }
+
+    /// Stopped containers, and the volumes and networks nothing running still uses.
+    ///
+    /// Only resources carrying this project's compose label are considered.
+    pub fn stale_resources(&self) -> Result<StaleResources> {
+        let label = format!("label={}", Self::project_label(&self.project_name()?));
+
+        let containers = self.run_with_retry(self.runtime.command().args([
+            "ps",
+            "--all",
+            "--no-trunc",
+            "--filter",
+            &label,
+            "--format",
+            "{{.State}}\t{{.Names}}\t{{.Mounts}}",
+        ]))?;
+        let volumes = self.run_with_retry(self.runtime.command().args([
+            "volume", "ls", "--filter", &label, "--format", "{{.Name}}",
+        ]))?;
+        let networks = self.run_with_retry(self.runtime.command().args([
+            "network", "ls", "--filter", &label, "--format", "{{.Name}}",
+        ]))?;
+
+        Ok(classify_resources(
+            &String::from_utf8_lossy(&containers.stdout),
+            parse_lines(&volumes.stdout),
+            parse_lines(&networks.stdout),
+        ))
+    }
+
+    /// Prune the project's stopped containers, unused volumes and networks.
+    ///
+    /// Returns the disk space Docker reports as reclaimed.
+    pub fn prune(&self) -> Result<u64> {
+        let label = format!("label={}", Self::project_label(&self.project_name()?));
+        let mut reclaimed = 0;
+
+        // Containers first so the volumes and networks they held become unused
+        for args in [
+            vec!["container", "prune", "--force"],
+            [&["volume", "prune"][..], self.runtime.volume_prune_args(), &["--force"]].concat(),
+            vec!["network", "prune", "--force"],
+        ] {
+            let mut cmd = self.runtime.command();
+            cmd.args(args).arg("--filter").arg(&label);
+            if self.skip(&cmd) {
+                continue;
+            }
+
+            let output = self.run_with_retry(&mut cmd)?;
+            reclaimed += parse_reclaimed(&String::from_utf8_lossy(&output.stdout));
+        }
+
+        Ok(reclaimed)
+    }
+
+    fn project_label(project: &str) -> String {
+        format!("com.docker.compose.project={}", project)
     }
 
     pub fn is_running(&self) -> bool {
-        Command::new("docker")
-            .arg("compose")
-            .arg("ps")
-            .arg("-q")
-            .current_dir(&self.project_dir)
-            .output()
+        let mut cmd = self.compose();
+        cmd.arg("ps").arg("-q");
+
+        self.run_with_retry(&mut cmd)
             .map(|output| !output.stdout.is_empty())
             .unwrap_or(false)
     }
-}
\ No newline at end of file
+}
+
+/// One `pull_with_progress` attempt: `None` on success, else the tail of
+/// compose's output to explain the failure
+fn pull_attempt(cmd: &mut Command, on_line: &mut impl FnMut(&str)) -> Result<Option<String>> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Keep the tail for the error message; progress lines end in \r or \n
+    let mut tail: Vec<String> = Vec::new();
+    let stderr = BufReader::new(child.stderr.take().expect("stderr is piped"));
+    for chunk in stderr.split(b'\n') {
+        let chunk = chunk?;
+        for line in String::from_utf8_lossy(&chunk).split('\r').map(str::trim).filter(|l| !l.is_empty()) {
+            on_line(line);
+            tail.push(line.to_string());
+            if tail.len() > ERROR_TAIL_LINES {
+                tail.remove(0);
+            }
+        }
+    }
+
+    let status = child.wait()?;
+    Ok((!status.success()).then(|| tail.join("\n")))
+}
+
+/// Reads through to `inner`, keeping what it read up to `limit` bytes, so
+/// the stream can start over as long as no more than that was read
+struct Replay<'a, R> {
+    inner: &'a mut R,
+    kept: Vec<u8>,
+    limit: usize,
+    /// Position within `kept` while replaying it
+    pos: usize,
+    overflowed: bool,
+}
+
+impl<'a, R: Read> Replay<'a, R> {
+    fn new(inner: &'a mut R, limit: usize) -> Self {
+        Self { inner, kept: Vec::new(), limit, pos: 0, overflowed: false }
+    }
+
+    /// Start over from the first byte; false once more than `limit` was read
+    fn rewind(&mut self) -> bool {
+        self.pos = 0;
+        !self.overflowed
+    }
+}
+
+impl<R: Read> Read for Replay<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pos < self.kept.len() {
+            let n = (self.kept.len() - self.pos).min(buf.len());
+            buf[..n].copy_from_slice(&self.kept[self.pos..self.pos + n]);
+            self.pos += n;
+            return Ok(n);
+        }
+
+        let n = self.inner.read(buf)?;
+        if !self.overflowed {
+            if self.kept.len() + n > self.limit {
+                self.overflowed = true;
+                self.kept = Vec::new();
+            } else {
+                self.kept.extend_from_slice(&buf[..n]);
+                self.pos = self.kept.len();
+            }
+        }
+        Ok(n)
+    }
+}
+
+/// With `--verbose`, say that `cmd` failed transiently and is about to be retried
+fn note_retry(cmd: &Command, error: &str, backoff: Duration, attempt: u32, retries: u32) {
+    if is_verbose() {
+        eprintln!(
+            "{} {} failed ({}); retrying in {:?} ({}/{})",
+            "⚠".yellow(),
+            command_line(cmd),
+            error.lines().next().unwrap_or_default().trim(),
+            backoff,
+            attempt + 1,
+            retries
+        );
+    }
+}
+
+/// Whether docker's stderr blames a passing daemon or network problem
+fn is_transient(stderr: &str) -> bool {
+    let stderr = stderr.to_ascii_lowercase();
+    TRANSIENT_ERRORS
+        .iter()
+        .any(|sign| sign.iter().all(|fragment| stderr.contains(fragment)))
+}
+
+/// `cmd.output()`, but killing the process if it runs past `timeout`
+fn output_within(cmd: &mut Command, timeout: Option<Duration>) -> Result<Output> {
+    let Some(timeout) = timeout else {
+        return Ok(cmd.output()?);
+    };
+
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Drain both pipes as the command runs, so a chatty one can't block on a full pipe
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = drain(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(ZecDevError::DockerTimeout { command: command_line(cmd), timeout });
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// Older compose releases print a JSON array, newer ones one object per line
+fn parse_json_entries<T: DeserializeOwned>(stdout: &str) -> Result<Vec<T>> {
+    let trimmed = stdout.trim();
+
+    if trimmed.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if trimmed.starts_with('[') {
+        return Ok(serde_json::from_str(trimmed)?);
+    }
+
+    trimmed
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).map_err(ZecDevError::from))
+        .collect()
+}
+
+/// Pair each running service with its `docker stats` entry. IDs are
+/// compared by prefix, since either side may be truncated.
+fn match_stats(running: &[ServiceStatus], entries: Vec<StatsEntry>) -> Vec<ServiceStats> {
+    running
+        .iter()
+        .filter_map(|service| {
+            let entry = entries.iter().find(|e| {
+                !e.id.is_empty() && (e.id.starts_with(&service.container_id) || service.container_id.starts_with(&e.id))
+            })?;
+            Some(ServiceStats {
+                name: service.name.clone(),
+                cpu_percent: parse_percent(&entry.cpu),
+                memory: entry.memory.clone(),
+                memory_percent: parse_percent(&entry.memory_percent),
+                net_io: entry.net_io.clone(),
+                block_io: entry.block_io.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Registry replies meaning the image or tag doesn't exist (or isn't visible to us)
+const MISSING_IMAGE_ERRORS: &[&str] = &["manifest unknown", "pull access denied", "repository does not exist"];
+
+/// A failed pull, singling out images that don't exist upstream
+fn pull_error(services: &[&str], stderr: &str) -> ZecDevError {
+    let lower = stderr.to_ascii_lowercase();
+    if MISSING_IMAGE_ERRORS.iter().any(|sign| lower.contains(sign)) {
+        return ZecDevError::ImageNotFound(services.join(", "));
+    }
+    ZecDevError::Docker(stderr.to_string())
+}
+
+/// First of `services` that is missing, stopped, restarting or unhealthy
+fn find_failed(statuses: &[ServiceStatus], services: &[&str]) -> Option<(String, String)> {
+    for service in services {
+        let state = match statuses.iter().find(|s| s.name == *service) {
+            None => "was not created".to_string(),
+            Some(s) if matches!(s.state.as_str(), "exited" | "dead" | "restarting") => {
+                format!("is {} ({})", s.state, s.status)
+            }
+            Some(s) if s.health == "unhealthy" => "is unhealthy".to_string(),
+            Some(_) => continue,
+        };
+        return Some((service.to_string(), state));
+    }
+    None
+}
+
+/// Split `docker ps` rows (state, name, mounts) into what is safe to remove
+fn classify_resources(ps: &str, volumes: Vec<String>, networks: Vec<String>) -> StaleResources {
+    let mut stale = StaleResources::default();
+    let mut in_use: Vec<&str> = Vec::new();
+    let mut any_active = false;
+
+    for line in ps.lines().filter(|l| !l.trim().is_empty()) {
+        let mut fields = line.split('\t');
+        let state = fields.next().unwrap_or_default();
+        let name = fields.next().unwrap_or_default();
+        let mounts = fields.next().unwrap_or_default();
+
+        if matches!(state, "created" | "exited" | "dead") {
+            stale.containers.push(name.to_string());
+        } else {
+            any_active = true;
+            in_use.extend(mounts.split(',').map(|m| m.trim()).filter(|m| !m.is_empty()));
+        }
+    }
+
+    stale.volumes = volumes
+        .into_iter()
+        .filter(|v| !in_use.contains(&v.as_str()))
+        .collect();
+
+    // The project network stays attached for as long as any container lives
+    if !any_active {
+        stale.networks = networks;
+    }
+
+    stale
+}
+
+/// Sum the "Total reclaimed space: 1.5GB" line printed by `docker * prune`
+fn parse_reclaimed(stdout: &str) -> u64 {
+    stdout
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("Total reclaimed space:"))
+        .filter_map(|size| parse_size(size.trim()))
+        .sum()
+}
+
+/// Parse Docker's human-readable sizes ("0B", "12.5kB", "1.2GB")
+fn parse_size(size: &str) -> Option<u64> {
+    let split = size
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(size.len());
+    let (value, unit) = size.split_at(split);
+    let value: f64 = value.parse().ok()?;
+
+    let multiplier = match unit.trim() {
+        "B" | "" => 1.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        _ => return None,
+    };
+
+    Some((value * multiplier) as u64)
+}
+
+/// `(service, image)` pairs from `compose config --format json`, sorted by service
+fn parse_service_images(config: &serde_json::Value) -> Vec<(String, Option<String>)> {
+    let mut images: Vec<_> = config
+        .get("services")
+        .and_then(|services| services.as_object())
+        .map(|services| {
+            services
+                .iter()
+                .map(|(name, service)| {
+                    let image = service.get("image").and_then(|i| i.as_str()).map(|i| i.to_string());
+                    (name.clone(), image)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    images.sort();
+    images
+}
+
+fn parse_lines(stdout: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|l| l.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compose_at(project_dir: &str) -> DockerCompose {
+        DockerCompose {
+            runtime: Arc::new(runtime::Docker),
+            project_dir: project_dir.to_string(),
+            env: Vec::new(),
+            global_args: Vec::new(),
+            dry_run: false,
+            retry: RetryPolicy { retries: 0, timeout: None },
+            data_dir: None,
+            layers: Layers::default(),
+            planned_files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn saved_layers_are_reapplied_once() {
+        let up = compose_at("/src")
+            .with_override(Path::new("/home/dev/.zeckit/zecdev/resource-limits.yml"))
+            .with_env(vec![("FAUCET_COOLDOWN_SECS", "5".to_string())]);
+        // As `up` saves them in state.json and `reset` loads them
+        let saved: Layers = serde_json::from_str(&serde_json::to_string(up.layers()).unwrap()).unwrap();
+
+        let reset = compose_at("/src").with_layers(&saved).with_layers(&saved);
+        assert_eq!(reset.layers(), up.layers());
+        assert_eq!(
+            command_line(&reset.compose()),
+            "cd /src && FAUCET_COOLDOWN_SECS=5 docker compose -f /src/docker-compose.yml -f /home/dev/.zeckit/zecdev/resource-limits.yml"
+        );
+    }
+
+    #[test]
+    fn dry_run_overrides_only_appear_in_the_plan() {
+        let compose = DockerCompose { dry_run: true, ..compose_at("/src") }
+            .with_override(Path::new("/home/dev/.zeckit/zecdev/mounts.yml"));
+        let mut cmd = compose.compose();
+        cmd.args(["up", "-d", "zebra"]);
+
+        // Queries still run against what exists
+        assert_eq!(command_line(&cmd), "cd /src && docker compose up -d zebra");
+        assert_eq!(
+            compose.planned_line(&cmd),
+            "cd /src && docker compose -f /src/docker-compose.yml -f /home/dev/.zeckit/zecdev/mounts.yml up -d zebra"
+        );
+        let mut helper = compose.runtime.command();
+        helper.args(["run", "--rm", "alpine:3.20"]);
+        assert_eq!(compose.planned_line(&helper), "docker run --rm alpine:3.20");
+    }
+
+    #[test]
+    fn only_daemon_trouble_is_transient() {
+        assert!(is_transient(
+            "Cannot connect to the Docker daemon at unix:///var/run/docker.sock. Is the docker daemon running?"
+        ));
+        assert!(is_transient("Error response from daemon: Get \"https://registry-1.docker.io/v2/\": net/http: TLS handshake timeout"));
+        assert!(is_transient("toomanyrequests: Too Many Requests"));
+        assert!(is_transient(
+            "Cannot connect to Podman. Please verify your connection to the Linux system using `podman system connection list`: \
+             unable to connect to Podman socket: dial unix /run/user/1000/podman/podman.sock: connect: connection refused"
+        ));
+
+        assert!(!is_transient("no such service: postgres"));
+        assert!(!is_transient("yaml: line 3: mapping values are not allowed in this context"));
+        assert!(!is_transient("Error response from daemon: pull access denied for zecdev/nope"));
+        // The service's own failure, from `zecdev exec`
+        assert!(!is_transient("curl: (7) Failed to connect to 127.0.0.1 port 8232: Connection refused"));
+    }
+
+    #[test]
+    fn a_stream_replays_only_what_it_kept() {
+        let mut source: &[u8] = b"0123456789";
+        let mut stream = Replay::new(&mut source, 6);
+        let mut buf = [0u8; 4];
+        assert_eq!(stream.read(&mut buf).unwrap(), 4);
+        assert!(stream.rewind());
+
+        let mut all = Vec::new();
+        stream.read_to_end(&mut all).unwrap();
+        assert_eq!(all, b"0123456789");
+        assert!(!stream.rewind());
+    }
+
+    #[test]
+    fn only_missing_images_are_reported_as_not_found() {
+        let missing = [
+            "Error response from daemon: manifest for zfnd/zebra:9.9.9 not found: manifest unknown: manifest unknown",
+            "Error response from daemon: pull access denied for zecdev/nope, repository does not exist or may require 'docker login'",
+        ];
+        for stderr in missing {
+            assert!(matches!(pull_error(&["zebra"], stderr), ZecDevError::ImageNotFound(_)), "{}", stderr);
+        }
+        for stderr in ["open /src/docker-compose.yml: no such file or directory", "no such service: zaino: not found"] {
+            assert!(matches!(pull_error(&["zebra"], stderr), ZecDevError::Docker(_)), "{}", stderr);
+        }
+    }
+
+    #[test]
+    fn pull_attempt_keeps_the_progress_tail() {
+        let mut lines = Vec::new();
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "printf 'layer Downloading\\rlayer Retrying\\ntoomanyrequests: Too Many Requests\\n' >&2; exit 1"]);
+        let error = pull_attempt(&mut cmd, &mut |line: &str| lines.push(line.to_string())).unwrap();
+        assert_eq!(lines, vec!["layer Downloading", "layer Retrying", "toomanyrequests: Too Many Requests"]);
+        assert!(is_transient(&error.unwrap()));
+
+        let mut cmd = Command::new("true");
+        assert_eq!(pull_attempt(&mut cmd, &mut |_: &str| {}).unwrap(), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn slow_commands_are_killed_at_the_timeout() {
+        let mut cmd = Command::new("sleep");
+        cmd.arg("5");
+        let started = Instant::now();
+        let e = output_within(&mut cmd, Some(Duration::from_millis(100))).unwrap_err();
+        assert!(matches!(e, ZecDevError::DockerTimeout { .. }));
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let output = output_within(Command::new("echo").arg("done"), Some(Duration::from_secs(5))).unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "done\n");
+    }
+
+    fn status(name: &str, state: &str, health: &str) -> ServiceStatus {
+        ServiceStatus {
+            name: name.into(),
+            container_id: String::new(),
+            state: state.into(),
+            health: health.into(),
+            status: "Exited (1) 2 seconds ago".into(),
+            ports: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn service_images_come_from_the_resolved_config() {
+        let config = serde_json::json!({
+            "name": "zeckit",
+            "services": {
+                "zebra": { "image": "zfnd/zebra:1.9.0" },
+                "faucet": { "build": { "context": "./faucet" } },
+            }
+        });
+
+        assert_eq!(
+            parse_service_images(&config),
+            vec![
+                ("faucet".to_string(), None),
+                ("zebra".to_string(), Some("zfnd/zebra:1.9.0".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn crashed_and_missing_services_are_reported() {
+        let statuses = vec![
+            status("zebra", "running", "healthy"),
+            status("faucet", "exited", "none"),
+        ];
+
+        assert_eq!(find_failed(&statuses, &["zebra"]), None);
+        assert_eq!(
+            find_failed(&statuses, &["zebra", "faucet"]),
+            Some(("faucet".into(), "is exited (Exited (1) 2 seconds ago)".into()))
+        );
+        assert_eq!(
+            find_failed(&statuses, &["lightwalletd"]),
+            Some(("lightwalletd".into(), "was not created".into()))
+        );
+    }
+
+    #[test]
+    fn only_stopped_containers_and_unused_resources_are_stale() {
+        let ps = "exited\tzecdev-lightwalletd\tzecdev-lightwalletd-data\n\
+                  running\tzecdev-zebra\tzecdev-zebra-data\n";
+        let stale = classify_resources(
+            ps,
+            vec!["zecdev-zebra-data".into(), "zecdev-lightwalletd-data".into()],
+            vec!["zecdev-network".into()],
+        );
+
+        assert_eq!(stale.containers, vec!["zecdev-lightwalletd"]);
+        assert_eq!(stale.volumes, vec!["zecdev-lightwalletd-data"]);
+        assert!(stale.networks.is_empty());
+    }
+
+    #[test]
+    fn stats_are_matched_to_services_by_container_id() {
+        let mut zebra = status("zebra", "running", "healthy");
+        zebra.container_id = "0123456789ab".into();
+        let mut faucet = status("faucet", "running", "healthy");
+        faucet.container_id = "ba9876543210".into();
+
+        let docker = r#"{"BlockIO":"1MB / 2GB","CPUPerc":"87.25%","Container":"0123456789ab","ID":"0123456789ab","MemPerc":"6.70%","MemUsage":"512MiB / 7.6GiB","Name":"zeckit-zebra-1","NetIO":"3MB / 1MB","PIDs":"12"}
+{"BlockIO":"0B / 0B","CPUPerc":"1.00%","ID":"ffffffffffff","MemPerc":"0.1%","MemUsage":"8MiB / 7.6GiB","NetIO":"0B / 0B"}"#;
+        let stats = match_stats(&[zebra.clone(), faucet.clone()], parse_json_entries(docker).unwrap());
+        assert_eq!(
+            stats,
+            vec![ServiceStats {
+                name: "zebra".into(),
+                cpu_percent: 87.25,
+                memory: "512MiB / 7.6GiB".into(),
+                memory_percent: 6.7,
+                net_io: "3MB / 1MB".into(),
+                block_io: "1MB / 2GB".into(),
+            }]
+        );
+
+        let podman = r#"[{"id":"ba9876543210fedc","name":"zeckit-faucet-1","cpu_percent":"--","mem_usage":"40MB / 8GB","mem_percent":"0.50%","net_io":"1kB / 2kB","block_io":"0B / 0B"}]"#;
+        let stats = match_stats(&[zebra, faucet], parse_json_entries(podman).unwrap());
+        assert_eq!(stats.len(), 1);
+        assert_eq!((stats[0].name.as_str(), stats[0].cpu_percent), ("faucet", 0.0));
+    }
+
+    #[test]
+    fn networks_are_stale_once_nothing_runs() {
+        let stale = classify_resources("", Vec::new(), vec!["zecdev-network".into()]);
+        assert_eq!(stale.networks, vec!["zecdev-network"]);
+    }
+
+    #[test]
+    fn reclaimed_space_is_parsed_from_prune_output() {
+        let output = "Deleted Volumes:\nzecdev-zebra-data\n\nTotal reclaimed space: 1.5GB\n";
+        assert_eq!(parse_reclaimed(output), 1_500_000_000);
+        assert_eq!(parse_reclaimed("Total reclaimed space: 0B"), 0);
+        assert_eq!(parse_size("12.5kB"), Some(12_500));
+    }
+
+    #[test]
+    fn down_keeps_volumes_by_default() {
+        assert_eq!(DockerCompose::down_args(false), vec!["down"]);
+    }
+
+    #[test]
+    fn down_removes_volumes_when_requested() {
+        let keep = DockerCompose::down_args(false);
+        let purge = DockerCompose::down_args(true);
+
+        assert_ne!(keep, purge);
+        assert_eq!(purge, vec!["down", "-v"]);
+    }
+
+    #[test]
+    fn inspect_json_is_summarized() {
+        let json = r#"[{
+            "Id": "0123456789abcdef0123",
+            "Image": "sha256:feedface",
+            "RestartCount": 3,
+            "State": {
+                "Status": "running",
+                "StartedAt": "2026-01-02T03:04:05Z",
+                "Health": {"Status": "unhealthy", "Log": [{"Start": "t1", "End": "t2", "ExitCode": 1, "Output": "refused"}]}
+            },
+            "Config": {"Image": "zfnd/zebra:latest"},
+            "Mounts": [{"Type": "volume", "Name": "zecdev-zebra-data", "Source": "/var/lib/docker/volumes/x", "Destination": "/var/zebra", "RW": true}],
+            "NetworkSettings": {"Ports": {
+                "8232/tcp": [{"HostIp": "0.0.0.0", "HostPort": "8232"}, {"HostIp": "::", "HostPort": "8232"}],
+                "8233/tcp": null
+            }}
+        }]"#;
+        let entry = serde_json::from_str::<Vec<InspectEntry>>(json).unwrap().remove(0);
+        let info = ServiceInfo::from_inspect("zebra", entry, None);
+
+        assert_eq!(info.container_id, "0123456789ab");
+        assert_eq!(info.image, "zfnd/zebra:latest");
+        assert_eq!(info.restart_count, 3);
+        assert_eq!(info.health, "unhealthy");
+        assert_eq!(info.health_log[0].output, "refused");
+        assert_eq!(info.mounts[0].source, "zecdev-zebra-data");
+        assert!(!info.mounts[0].read_only);
+        assert_eq!(info.ports, vec!["8232 -> 8232/tcp"]);
+    }
+
+    #[test]
+    fn ps_json_accepts_array_and_lines() {
+        let line = r#"{"ID":"0123456789abcdef","Service":"zebra","State":"running","Health":"healthy","Publishers":[{"PublishedPort":8232},{"PublishedPort":8232},{"PublishedPort":0}]}"#;
+        let array = format!("[{}]", line);
+
+        for output in [line.to_string(), array] {
+            let statuses: Vec<ServiceStatus> = parse_json_entries::<PsEntry>(&output)
+                .unwrap()
+                .into_iter()
+                .map(ServiceStatus::from)
+                .collect();
+
+            assert_eq!(statuses.len(), 1);
+            assert_eq!(statuses[0].name, "zebra");
+            assert_eq!(statuses[0].container_id, "0123456789ab");
+            assert_eq!(statuses[0].health, "healthy");
+            assert_eq!(statuses[0].ports, vec!["8232"]);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::error::Result;
+use crate::services::{Service, ServiceSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Host directory holding the services' state in place of their named
+/// volumes, from `--data-dir`.
+///
+/// Each service that keeps state gets a subdirectory named after it
+/// (`zebra/`, `faucet/`, `lightwalletd/`, `zaino/`), bind-mounted where the volume was.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataDir {
+    root: PathBuf,
+}
+
+impl DataDir {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Services whose state lives in the data directory
+    pub fn services() -> Vec<Service> {
+        ServiceSet::ALL.into_iter().filter(|s| s.data_path.is_some()).collect()
+    }
+
+    /// Host directory for `service`'s state
+    pub fn service_dir(&self, service: &Service) -> PathBuf {
+        self.root.join(service.name)
+    }
+
+    /// Host directory standing in for the named volume `volume`, if it is one of ours
+    pub fn volume_dir(&self, volume: &str) -> Option<PathBuf> {
+        Self::services()
+            .into_iter()
+            .find(|s| s.volume == Some(volume))
+            .map(|s| self.service_dir(&s))
+    }
+
+    /// Create the directory and each service's subdirectory.
+    ///
+    /// Done here rather than left to the runtime, which would create them owned by root.
+    pub fn create(&self) -> Result<()> {
+        for service in Self::services() {
+            fs::create_dir_all(self.service_dir(&service))?;
+        }
+        Ok(())
+    }
+
+    /// Container runtime arguments that empty the subdirectories of `services`
+    /// from a throwaway `image` container, keeping the directories themselves;
+    /// `None` when none of them exists. Nothing else under the data directory
+    /// is touched.
+    ///
+    /// The services write their state as root, which the host user usually
+    /// can't delete; a container can. Missing subdirectories are left out, as
+    /// the runtime would create them owned by root.
+    pub fn clear_args(&self, services: &[Service], image: &str) -> Option<Vec<String>> {
+        let mut args = vec!["run".to_string(), "--rm".to_string()];
+        for service in services.iter().filter(|s| s.data_path.is_some()) {
+            let dir = self.service_dir(service);
+            if dir.is_dir() {
+                args.push("-v".to_string());
+                args.push(format!("{}:/data/{}", dir.display(), service.name));
+            }
+        }
+        if args.len() == 2 {
+            return None;
+        }
+
+        // Depth 2 is everything inside the mounts, not the mount points
+        args.extend([image, "find", "/data", "-mindepth", "2", "-delete"].map(String::from));
+        Some(args)
+    }
+
+    /// Compose override mounting each subdirectory over its service's volume.
+    ///
+    /// Compose merges a service's `volumes` by container path, so these
+    /// replace the named volumes rather than adding to them.
+    pub fn to_override_yaml(&self) -> String {
+        let mut yaml = String::from("# Generated by zecdev from --data-dir; do not edit\nservices:\n");
+        for service in Self::services() {
+            let source = self.service_dir(&service).to_string_lossy().replace('\'', "''");
+            yaml.push_str(&format!(
+                "  {}:\n    volumes:\n      - '{}:{}'\n",
+                service.name,
+                source,
+                service.data_path.unwrap_or_default()
+            ));
+        }
+        yaml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{FAUCET, LIGHTWALLETD, ZEBRA};
+
+    #[test]
+    fn override_binds_each_stateful_service() {
+        let data = DataDir::new(PathBuf::from("/mnt/nvme/zeckit"));
+        assert_eq!(
+            data.to_override_yaml(),
+            "# Generated by zecdev from --data-dir; do not edit\n\
+             services:\n  \
+             zebra:\n    \
+             volumes:\n      \
+             - '/mnt/nvme/zeckit/zebra:/var/zebra/state'\n  \
+             faucet:\n    \
+             volumes:\n      \
+             - '/mnt/nvme/zeckit/faucet:/var/faucet'\n  \
+             lightwalletd:\n    \
+             volumes:\n      \
+             - '/mnt/nvme/zeckit/lightwalletd:/var/lib/lightwalletd'\n  \
+             zaino:\n    \
+             volumes:\n      \
+             - '/mnt/nvme/zeckit/zaino:/var/lib/zaino'\n"
+        );
+        assert_eq!(
+            data.volume_dir("zecdev-zebra-data"),
+            Some(PathBuf::from("/mnt/nvme/zeckit/zebra"))
+        );
+        assert_eq!(data.volume_dir("someone-elses"), None);
+    }
+
+    #[test]
+    fn clearing_mounts_only_the_named_services_that_exist() {
+        let root = std::env::temp_dir().join(format!("zecdev-data-dir-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let data = DataDir::new(root.clone());
+        fs::create_dir_all(root.join("zebra")).unwrap();
+        let zebra = root.join("zebra").display().to_string();
+
+        assert_eq!(
+            data.clear_args(&[ZEBRA, FAUCET, LIGHTWALLETD], "alpine:3.20"),
+            Some(
+                ["run", "--rm", "-v", &format!("{}:/data/zebra", zebra), "alpine:3.20", "find", "/data", "-mindepth", "2", "-delete"]
+                    .map(String::from)
+                    .to_vec()
+            )
+        );
+        // Nothing to clear: no container at all
+        assert_eq!(data.clear_args(&[FAUCET], "alpine:3.20"), None);
+
+        data.create().unwrap();
+        let args = data.clear_args(&DataDir::services(), "alpine:3.20").unwrap();
+        assert_eq!(args.iter().filter(|a| a.as_str() == "-v").count(), 4);
+        assert!(args.contains(&format!("{}:/data/zaino", root.join("zaino").display())));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
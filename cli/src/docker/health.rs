@@ -1,100 +1,331 @@
+use crate::backend::BackendClient;
+use crate::config::Network;
+use crate::config::health::Probe;
+use crate::config::settings::Settings;
 use crate::error::{Result, ZecDevError};
+use crate::faucet::{FaucetClient, ZATS_PER_ZEC};
+use crate::rpc::{BlockchainInfo, ZebraRpc};
+use crate::services::{ServiceSet, LIGHTWALLETD};
+use crate::utils::spinner;
+use colored::*;
+use futures::future::{join_all, try_join_all};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use reqwest::Client;
-use indicatif::ProgressBar;
-use tokio::time::{sleep, Duration};
 use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::sync::Mutex;
+use tokio::net::TcpStream;
+use tokio::time::{sleep, Duration, Instant};
 
 pub struct HealthChecker {
     client: Client,
-    max_retries: u32,
-    retry_delay: Duration,
-    backend_max_retries: u32, // Longer timeout for backends
+    timeout: Duration,
+    backend_timeout: Duration, // Longer timeout for backends
+    min_poll: Duration,
+    max_poll: Duration,
+    max_attempts: Option<u32>,
+    /// How long Zebra's height may stand still while behind the header tip
+    stall_window: Duration,
+    /// Faucet balance, in zatoshis, needed before the faucet counts as ready
+    min_faucet_balance: Option<u64>,
+    settings: Settings,
+    /// Network the devnet runs, which lightwalletd must be following
+    network: Option<Network>,
+    /// Checks from zeckit.toml, used instead of the built-in ones
+    probes: BTreeMap<String, Probe>,
+    /// Services that have passed a check in `wait_for_all`, in order, and when
+    ready: Mutex<Vec<(String, Instant)>>,
 }
 
 impl HealthChecker {
     pub fn new() -> Self {
         Self {
             client: Client::new(),
-            max_retries: 60, // 60 retries * 2s = 2 minutes max
-            retry_delay: Duration::from_secs(2),
-            backend_max_retries: 90, // 90 retries * 2s = 3 minutes for backends
+            timeout: Duration::from_secs(120),
+            backend_timeout: Duration::from_secs(180),
+            min_poll: Duration::from_millis(250),
+            max_poll: Duration::from_secs(2),
+            max_attempts: None,
+            stall_window: DEFAULT_STALL_WINDOW,
+            min_faucet_balance: None,
+            settings: Settings::default(),
+            network: None,
+            probes: BTreeMap::new(),
+            ready: Mutex::new(Vec::new()),
         }
     }
 
-    pub async fn wait_for_zebra(&self, pb: &ProgressBar) -> Result<()> {
-        for i in 0..self.max_retries {
-            pb.tick();
-            
-            match self.check_zebra().await {
-                Ok(_) => return Ok(()),
-                Err(_) if i < self.max_retries - 1 => {
-                    sleep(self.retry_delay).await;
-                }
-                Err(e) => return Err(e),
-            }
-        }
+    /// Probe the endpoints described by `settings` instead of the defaults
+    pub fn with_settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
 
-        Err(ZecDevError::ServiceNotReady("Zebra".into()))
+    /// Fail lightwalletd's check, rather than retry it, if it follows another network's chain
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = Some(network);
+        self
     }
 
-    pub async fn wait_for_faucet(&self, pb: &ProgressBar) -> Result<()> {
-        for i in 0..self.max_retries {
+    /// Check these services with the given probes (see `Config::health_probes`)
+    pub fn with_probes(mut self, probes: BTreeMap<String, Probe>) -> Self {
+        self.probes = probes;
+        self
+    }
+
+    /// Give up on every service once `timeout` has elapsed
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self.backend_timeout = timeout;
+        self
+    }
+
+    /// Give up on a service after `attempts` failed checks, even if time remains
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = Some(attempts.max(1));
+        self
+    }
+
+    /// Call Zebra's sync stalled once its height stands still this long behind the header tip
+    pub fn with_stall_window(mut self, window: Duration) -> Self {
+        self.stall_window = window;
+        self
+    }
+
+    /// Only call the faucet ready once it holds at least `min_zat`, not just when its API answers
+    pub fn with_faucet_funds(mut self, min_zat: u64) -> Self {
+        self.min_faucet_balance = Some(min_zat);
+        self
+    }
+
+    /// Poll interval bounds; the delay doubles from `min` up to `max`
+    pub fn with_poll_interval(mut self, min: Duration, max: Duration) -> Self {
+        self.min_poll = min;
+        self.max_poll = max.max(min);
+        self
+    }
+
+    /// Wait until Zebra answers RPC and has caught up with the chain.
+    ///
+    /// While it syncs the spinner becomes a progress bar with an ETA. The
+    /// timeout and attempt limit still bound the whole wait, sync included; a
+    /// height that stands still for the stall window while headers are ahead
+    /// of it fails earlier, as a stalled sync.
+    pub async fn wait_for_zebra(&self, pb: &ProgressBar) -> Result<()> {
+        let rpc = ZebraRpc::new(self.settings.zebra_rpc_url.clone()).with_timeout(Duration::from_secs(5));
+        let start = Instant::now();
+        let mut last_advance = start;
+        let mut height = None;
+        let mut eta = SyncEta::default();
+        let mut delay = self.min_poll;
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
             pb.tick();
-            
-            match self.check_faucet().await {
-                Ok(_) => return Ok(()),
-                Err(_) if i < self.max_retries - 1 => {
-                    sleep(self.retry_delay).await;
+
+            match rpc.get_blockchain_info().await {
+                Ok(info) if info.is_synced() => return Ok(()),
+                Ok(info) => {
+                    let progress = info.sync_progress().unwrap_or_default();
+                    if height.is_none_or(|h| info.blocks > h) {
+                        height = Some(info.blocks);
+                        last_advance = Instant::now();
+                    }
+                    tracing::debug!(blocks = info.blocks, progress, "zebra syncing");
+                    eta.record(Instant::now(), progress);
+                    show_sync(pb, &info, progress, eta.remaining());
+                    if is_stalled(&info, last_advance.elapsed(), self.stall_window) {
+                        return Err(ZecDevError::SyncStalled {
+                            height: info.blocks,
+                            headers: info.headers.unwrap_or_default(),
+                            stalled: last_advance.elapsed(),
+                        });
+                    }
+                    // Sync is slow; there is no point polling quickly
+                    delay = self.max_poll;
                 }
-                Err(e) => return Err(e),
+                Err(e) => {
+                    tracing::debug!(service = "zebra", attempt = attempts, error = %e, "health check failed");
+                    if service_answered(&e) {
+                        delay = self.min_poll;
+                    } else if attempts > 1 {
+                        pb.set_message(format!("Waiting for zebra... attempt {}", attempts));
+                    }
+                }
+            }
+
+            let elapsed = start.elapsed();
+            let out_of_attempts = self.max_attempts.is_some_and(|max| attempts >= max);
+            if elapsed >= self.timeout || out_of_attempts {
+                return Err(ZecDevError::HealthTimeout {
+                    service: "zebra".to_string(),
+                    attempts,
+                    elapsed,
+                });
             }
+
+            sleep(with_jitter(delay).min(self.timeout - elapsed)).await;
+            delay = next_delay(delay, self.max_poll);
         }
+    }
 
-        Err(ZecDevError::ServiceNotReady("Faucet".into()))
+    pub async fn wait_for_faucet(&self, pb: &ProgressBar) -> Result<()> {
+        self.poll("faucet", self.timeout, pb, || self.check_faucet()).await
     }
 
     pub async fn wait_for_backend(&self, backend: &str, pb: &ProgressBar) -> Result<()> {
         // Use longer timeout for backends since they need to sync with Zebra
-        for i in 0..self.backend_max_retries {
-            pb.tick();
-            
-            match self.check_backend(backend).await {
-                Ok(_) => return Ok(()),
-                Err(_) if i < self.backend_max_retries - 1 => {
-                    sleep(self.retry_delay).await;
+        let service = ServiceSet::backend(backend).map_or(backend, |s| s.name);
+        self.poll(service, self.backend_timeout, pb, || self.check_backend(backend)).await
+    }
+
+    /// Wait until something accepts TCP connections on `host:port`.
+    ///
+    /// A generic probe for services ZecKit has no dedicated check for.
+    pub async fn wait_for_port(&self, host: &str, port: u16, pb: &ProgressBar) -> Result<()> {
+        let address = format!("{}:{}", host, port);
+        self.poll(&address, self.timeout, pb, || check_port(&address))
+            .await
+            .map_err(|e| match e {
+                // There is no service name to point `zecdev logs` at
+                ZecDevError::HealthTimeout { .. } => e.with_hint(format!(
+                    "Check that the service publishes port {} and listens on it.",
+                    port
+                )),
+                e => e,
+            })
+    }
+
+    /// Wait on a service by its compose name.
+    ///
+    /// A probe from zeckit.toml wins over the built-in check. Services with
+    /// neither are considered ready immediately.
+    pub async fn wait_for_service(&self, service: &str, pb: &ProgressBar) -> Result<()> {
+        let known = ServiceSet::get(service);
+        let backend = known.and_then(|s| s.backend);
+        if let Some(probe) = self.probes.get(service) {
+            let timeout = if backend.is_some() { self.backend_timeout } else { self.timeout };
+            return self.poll(service, timeout, pb, || self.check_probe(probe)).await;
+        }
+
+        match (known.map(|s| s.name), backend) {
+            (Some("zebra"), _) => self.wait_for_zebra(pb).await,
+            (Some("faucet"), _) => self.wait_for_faucet(pb).await,
+            (_, Some(backend)) => self.wait_for_backend(backend, pb).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Wait on several services at once, with one spinner each.
+    ///
+    /// Returns as soon as any service fails; the remaining checks are dropped.
+    pub async fn wait_for_all(&self, services: &[&str], multi: &MultiProgress) -> Result<()> {
+        try_join_all(services.iter().map(|service| self.wait_with_spinner(service, multi, false))).await?;
+        Ok(())
+    }
+
+    /// Like `wait_for_all`, but a service that fails doesn't stop the others.
+    /// Returns the failed services together with their errors.
+    pub async fn wait_for_optional(&self, services: &[&str], multi: &MultiProgress) -> Vec<(String, ZecDevError)> {
+        let waits = services.iter().map(|service| async move {
+            let result = self.wait_with_spinner(service, multi, true).await;
+            result.err().map(|e| (service.to_string(), e))
+        });
+        join_all(waits).await.into_iter().flatten().collect()
+    }
+
+    async fn wait_with_spinner(&self, service: &str, multi: &MultiProgress, optional: bool) -> Result<()> {
+        let pb = multi.add(spinner());
+        pb.set_message(format!("Waiting for {}...", service));
+
+        match self.wait_for_service(service, &pb).await {
+            Ok(()) => {
+                pb.finish_with_message(format!("✓ {} ready", service).green().to_string());
+                if let Ok(mut ready) = self.ready.lock() {
+                    ready.push((service.to_string(), Instant::now()));
                 }
-                Err(e) => return Err(e),
+                Ok(())
+            }
+            Err(e) if optional => {
+                pb.abandon_with_message(format!("⚠ {} failed (optional)", service).yellow().to_string());
+                Err(e)
+            }
+            Err(e) => {
+                pb.abandon_with_message(format!("✗ {} failed", service).red().to_string());
+                Err(e)
             }
         }
+    }
 
-        Err(ZecDevError::ServiceNotReady(format!("{} (may need more sync time)", backend)))
+    /// Services `wait_for_all` has seen become ready so far
+    pub fn ready_services(&self) -> Vec<String> {
+        self.ready_times().into_iter().map(|(service, _)| service).collect()
     }
 
-    async fn check_zebra(&self) -> Result<()> {
-        let resp = self
-            .client
-            .post("http://127.0.0.1:8232")
-            .json(&serde_json::json!({
-                "jsonrpc": "2.0",
-                "id": "health",
-                "method": "getblockcount",
-                "params": []
-            }))
-            .timeout(Duration::from_secs(5))
-            .send()
-            .await?;
+    /// When each service in `ready_services` became ready
+    pub fn ready_times(&self) -> Vec<(String, Instant)> {
+        self.ready.lock().map(|ready| ready.clone()).unwrap_or_default()
+    }
+
+    /// Run `check` until it succeeds, `timeout` elapses or the attempt cap is hit.
+    ///
+    /// The delay between attempts backs off exponentially while the service
+    /// refuses connections, and drops back to the minimum once it answers.
+    async fn poll<F, Fut>(
+        &self,
+        service: &str,
+        timeout: Duration,
+        pb: &ProgressBar,
+        check: F,
+    ) -> Result<()>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let start = Instant::now();
+        let mut delay = self.min_poll;
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+            if attempts > 1 {
+                pb.set_message(format!("Waiting for {}... attempt {}", service, attempts));
+            }
+            pb.tick();
+
+            match check().await {
+                Ok(()) => return Ok(()),
+                // Retrying won't fix a misconfiguration
+                Err(e @ ZecDevError::BackendChainMismatch { .. }) => return Err(e),
+                Err(e) => {
+                    tracing::debug!(service, attempt = attempts, error = %e, "health check failed");
+                    if service_answered(&e) {
+                        delay = self.min_poll;
+                    }
+                }
+            }
+
+            let elapsed = start.elapsed();
+            let out_of_attempts = self.max_attempts.is_some_and(|max| attempts >= max);
+            if elapsed >= timeout || out_of_attempts {
+                return Err(ZecDevError::HealthTimeout {
+                    service: service.to_string(),
+                    attempts,
+                    elapsed,
+                });
+            }
 
-        if resp.status().is_success() {
-            Ok(())
-        } else {
-            Err(ZecDevError::HealthCheck("Zebra not ready".into()))
+            sleep(with_jitter(delay).min(timeout - elapsed)).await;
+            delay = next_delay(delay, self.max_poll);
         }
     }
 
     async fn check_faucet(&self) -> Result<()> {
         let resp = self
             .client
-            .get("http://127.0.0.1:8080/health")
+            .get(format!("{}/health", self.settings.faucet_api_url))
             .timeout(Duration::from_secs(5))
             .send()
             .await?;
@@ -110,41 +341,244 @@ impl HealthChecker {
             return Err(ZecDevError::HealthCheck("Faucet unhealthy".into()));
         }
 
+        // A wallet that hasn't been funded yet would fail the first `faucet fund`
+        if let Some(min_zat) = self.min_faucet_balance {
+            let balance = FaucetClient::new(&self.settings).get_stats().await?.balance_zat;
+            if balance < min_zat {
+                return Err(ZecDevError::HealthCheck(format!(
+                    "Faucet balance {} ZEC, waiting for {} ZEC",
+                    balance as f64 / ZATS_PER_ZEC as f64,
+                    min_zat as f64 / ZATS_PER_ZEC as f64
+                )));
+            }
+        }
+
         Ok(())
     }
 
-    async fn check_backend(&self, backend: &str) -> Result<()> {
-        // For lightwalletd, check gRPC port
-        // For zaino, check its port
-        let port = match backend {
-            "lwd" => 9067,
-            "zaino" => 9067,
-            _ => return Err(ZecDevError::HealthCheck("Unknown backend".into())),
-        };
-        
-        let url = format!("http://127.0.0.1:{}", port);
-        
-        // Simple TCP check - just see if port responds
-        // Note: gRPC won't return valid HTTP, but connection is what matters
-        let resp = self
-            .client
-            .get(&url)
-            .timeout(Duration::from_secs(5))
-            .send()
-            .await;
-
-        // For gRPC services, any response (even errors) means it's listening
-        match resp {
-            Ok(_) => Ok(()),
-            Err(e) => {
-                // Check if it's a connection error or just HTTP error
-                if e.is_connect() || e.is_timeout() {
-                    Err(ZecDevError::HealthCheck(format!("{} not ready", backend)))
-                } else {
-                    // Got a response (even if HTTP error), means service is up
-                    Ok(())
+    async fn check_probe(&self, probe: &Probe) -> Result<()> {
+        match probe {
+            Probe::Tcp { address } => check_port(address).await,
+            Probe::Http { url, expect_status } => {
+                let status = self
+                    .client
+                    .get(url)
+                    .timeout(Duration::from_secs(5))
+                    .send()
+                    .await?
+                    .status();
+                if status.as_u16() != *expect_status {
+                    return Err(ZecDevError::HealthCheck(format!(
+                        "{} answered {}, expected {}",
+                        url, status, expect_status
+                    )));
                 }
+                Ok(())
             }
         }
     }
-}
\ No newline at end of file
+
+    /// lightwalletd must answer `GetLightdInfo` on the devnet's chain and be
+    /// within `BACKEND_MAX_LAG` blocks of Zebra; Zaino need only answer
+    async fn check_backend(&self, backend: &str) -> Result<()> {
+        let client = BackendClient::new(self.settings.backend_url_for(backend).to_string())?;
+        if backend != "lwd" {
+            return client.probe(backend).await;
+        }
+
+        let info = client.lightd_info().await?;
+        if let Some(network) = self.network {
+            info.check_chain(LIGHTWALLETD.name, network)?;
+        }
+        let zebra = ZebraRpc::new(self.settings.zebra_rpc_url.clone())
+            .with_timeout(Duration::from_secs(5))
+            .get_block_count()
+            .await?;
+        check_caught_up(info.block_height, zebra)
+    }
+}
+
+/// Blocks lightwalletd may trail Zebra by and still count as ready
+const BACKEND_MAX_LAG: u64 = 5;
+
+/// Whether a backend at `height` has caught up with Zebra at `zebra`
+fn check_caught_up(height: u64, zebra: u64) -> Result<()> {
+    if height + BACKEND_MAX_LAG >= zebra {
+        return Ok(());
+    }
+    Err(ZecDevError::HealthCheck(format!(
+        "lightwalletd is at height {}, {} blocks behind Zebra",
+        height,
+        zebra - height
+    )))
+}
+
+/// How far back sync samples count towards the ETA
+const ETA_WINDOW: Duration = Duration::from_secs(60);
+
+/// Estimates time left in a sync from recent progress samples
+#[derive(Debug, Default)]
+struct SyncEta {
+    samples: VecDeque<(Instant, f64)>,
+}
+
+impl SyncEta {
+    fn record(&mut self, at: Instant, progress: f64) {
+        self.samples.push_back((at, progress));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(first, _)| at.duration_since(*first) > ETA_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Time to 100% at the recent rate, once there is a rate to go on
+    fn remaining(&self) -> Option<Duration> {
+        let (first_at, first) = self.samples.front()?;
+        let (last_at, last) = self.samples.back()?;
+        let elapsed = last_at.duration_since(*first_at).as_secs_f64();
+        if elapsed <= 0.0 || last <= first {
+            return None;
+        }
+        let rate = (last - first) / elapsed;
+        Some(Duration::from_secs_f64((1.0 - last) / rate))
+    }
+}
+
+/// Turn Zebra's spinner into a sync progress bar
+fn show_sync(pb: &ProgressBar, info: &BlockchainInfo, progress: f64, eta: Option<Duration>) {
+    if pb.length() != Some(SYNC_BAR_LENGTH) {
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{spinner:.green} {msg} [{bar:30.green}] {percent}%")
+                .unwrap(),
+        );
+        pb.set_length(SYNC_BAR_LENGTH);
+    }
+    pb.set_position((progress * SYNC_BAR_LENGTH as f64) as u64);
+
+    let blocks = match info.headers {
+        Some(headers) => format!("{} / {}", info.blocks, headers),
+        None => info.blocks.to_string(),
+    };
+    let eta = eta.map(|d| format!(", ~{} left", format_eta(d))).unwrap_or_default();
+    pb.set_message(format!("Syncing zebra... block {}{}", blocks, eta));
+}
+
+/// Bar resolution: one step per 0.1%
+const SYNC_BAR_LENGTH: u64 = 1000;
+
+/// Coarse human ETA: "45s", "12m", "2h 5m"
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h {}m", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+/// Whether `address` accepts a TCP connection; nothing is sent over it
+async fn check_port(address: &str) -> Result<()> {
+    tokio::time::timeout(Duration::from_secs(2), TcpStream::connect(address))
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+    Ok(())
+}
+
+/// Default for `with_stall_window`
+pub const DEFAULT_STALL_WINDOW: Duration = Duration::from_secs(120);
+
+/// Whether a sync has made no progress for `window` although Zebra knows of
+/// later blocks. Without headers ahead there is nothing to fetch yet, which
+/// is waiting, not stalling.
+fn is_stalled(info: &BlockchainInfo, since_advance: Duration, window: Duration) -> bool {
+    info.headers.is_some_and(|headers| headers > info.blocks) && since_advance >= window
+}
+
+/// Whether the error came from a service that is listening but not ready yet
+fn service_answered(e: &ZecDevError) -> bool {
+    matches!(e, ZecDevError::HealthCheck(_) | ZecDevError::Rpc { .. })
+}
+
+/// Spread `delay` by up to ±20% so parallel waits don't poll in lockstep
+fn with_jitter(delay: Duration) -> Duration {
+    delay.mul_f64(0.8 + fastrand::f64() * 0.4)
+}
+
+/// The poll delay after `delay`: doubled, up to `max`
+fn next_delay(delay: Duration, max: Duration) -> Duration {
+    (delay * 2).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polling_backs_off_to_the_cap_with_jitter() {
+        let max = Duration::from_secs(5);
+        let mut delay = Duration::from_millis(500);
+        let mut delays = Vec::new();
+        for _ in 0..6 {
+            delays.push(delay);
+            delay = next_delay(delay, max);
+        }
+        assert_eq!(delays, [500, 1000, 2000, 4000, 5000, 5000].map(Duration::from_millis));
+
+        let base = Duration::from_secs(1);
+        let jittered: Vec<Duration> = (0..200).map(|_| with_jitter(base)).collect();
+        assert!(jittered.iter().all(|d| (base.mul_f64(0.8)..=base.mul_f64(1.2)).contains(d)));
+        assert!(jittered.iter().any(|d| *d != jittered[0]), "jitter should vary");
+    }
+
+    #[test]
+    fn eta_follows_the_recent_sync_rate() {
+        let start = Instant::now();
+        let mut eta = SyncEta::default();
+        eta.record(start, 0.50);
+        assert_eq!(eta.remaining(), None);
+
+        // 10% in 10 seconds leaves 40% for another 40 seconds
+        eta.record(start + Duration::from_secs(10), 0.60);
+        let remaining = eta.remaining().unwrap().as_secs_f64();
+        assert!((remaining - 40.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn only_a_sync_behind_its_headers_stalls() {
+        let info = |blocks, headers| BlockchainInfo {
+            chain: "test".into(),
+            blocks,
+            headers,
+            verification_progress: Some(0.5),
+        };
+        let window = Duration::from_secs(120);
+        assert!(is_stalled(&info(100, Some(5000)), Duration::from_secs(120), window));
+        assert!(!is_stalled(&info(100, Some(5000)), Duration::from_secs(119), window));
+        assert!(!is_stalled(&info(100, Some(100)), Duration::from_secs(600), window));
+        assert!(!is_stalled(&info(100, None), Duration::from_secs(600), window));
+    }
+
+    #[test]
+    fn lightwalletd_may_trail_zebra_by_a_few_blocks() {
+        assert!(check_caught_up(100, 100).is_ok());
+        assert!(check_caught_up(95, 100).is_ok());
+        // Zebra's answer can lag behind lightwalletd's
+        assert!(check_caught_up(101, 100).is_ok());
+
+        let e = check_caught_up(12, 380).unwrap_err();
+        assert_eq!(e.to_string(), "Health check failed: lightwalletd is at height 12, 368 blocks behind Zebra");
+    }
+
+    #[test]
+    fn eta_is_formatted_coarsely() {
+        assert_eq!(format_eta(Duration::from_secs(45)), "45s");
+        assert_eq!(format_eta(Duration::from_secs(720)), "12m");
+        assert_eq!(format_eta(Duration::from_secs(7500)), "2h 5m");
+    }
+}
@@ -0,0 +1,178 @@
+/// Service a bare `--memory-limit 4g` / `--cpu-limit 2` applies to
+pub const DEFAULT_LIMITED_SERVICE: &str = "zebra";
+
+/// A limit for one service, as given on the command line (`zebra=4g`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceLimit {
+    pub service: String,
+    pub value: String,
+}
+
+/// Per-service memory and CPU caps, applied through a generated compose override
+#[derive(Debug, Clone, Default)]
+pub struct ResourceLimits {
+    pub memory: Vec<ServiceLimit>,
+    pub cpus: Vec<ServiceLimit>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.memory.is_empty() && self.cpus.is_empty()
+    }
+
+    /// Services that have at least one limit, in first-mentioned order
+    pub fn services(&self) -> Vec<&str> {
+        let mut services: Vec<&str> = Vec::new();
+        for limit in self.memory.iter().chain(&self.cpus) {
+            if !services.contains(&limit.service.as_str()) {
+                services.push(&limit.service);
+            }
+        }
+        services
+    }
+
+    /// One line per limited service, e.g. `zebra: memory=4g cpus=2`
+    pub fn summary(&self) -> Vec<String> {
+        self.services()
+            .into_iter()
+            .map(|service| {
+                let mut parts = vec![format!("{}:", service)];
+                if let Some(memory) = self.memory_for(service) {
+                    parts.push(format!("memory={}", memory));
+                }
+                if let Some(cpus) = self.cpus_for(service) {
+                    parts.push(format!("cpus={}", cpus));
+                }
+                parts.join(" ")
+            })
+            .collect()
+    }
+
+    /// Compose override setting `deploy.resources` for every limited service.
+    ///
+    /// The memory reservation is lowered to the limit as well, since Docker
+    /// refuses a reservation above the limit and the base file reserves 2G
+    /// for Zebra.
+    pub fn to_override_yaml(&self) -> String {
+        let mut yaml = String::from("# Generated by zecdev up; do not edit\nservices:\n");
+
+        for service in self.services() {
+            yaml.push_str(&format!("  {}:\n    deploy:\n      resources:\n        limits:\n", service));
+            if let Some(memory) = self.memory_for(service) {
+                yaml.push_str(&format!("          memory: {}\n", memory));
+            }
+            if let Some(cpus) = self.cpus_for(service) {
+                yaml.push_str(&format!("          cpus: \"{}\"\n", cpus));
+            }
+            if let Some(memory) = self.memory_for(service) {
+                yaml.push_str(&format!("        reservations:\n          memory: {}\n", memory));
+            }
+        }
+
+        yaml
+    }
+
+    // The last value given for a service wins
+    fn memory_for(&self, service: &str) -> Option<&str> {
+        self.memory.iter().rev().find(|l| l.service == service).map(|l| l.value.as_str())
+    }
+
+    fn cpus_for(&self, service: &str) -> Option<&str> {
+        self.cpus.iter().rev().find(|l| l.service == service).map(|l| l.value.as_str())
+    }
+}
+
+/// clap parser for `--memory-limit [SERVICE=]SIZE`, e.g. `4g` or `zebra=512m`
+pub fn parse_memory_limit(input: &str) -> std::result::Result<ServiceLimit, String> {
+    let limit = split_limit(input);
+    let value = limit.value.to_ascii_lowercase();
+
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+
+    let valid_amount = amount.parse::<f64>().is_ok_and(|a| a > 0.0);
+    let valid_unit = matches!(unit, "" | "b" | "k" | "kb" | "m" | "mb" | "g" | "gb");
+    if !valid_amount || !valid_unit {
+        return Err(format!(
+            "invalid memory limit '{}' (expected e.g. 4g, 512m or zebra=4g)",
+            limit.value
+        ));
+    }
+
+    Ok(ServiceLimit { value, ..limit })
+}
+
+/// clap parser for `--cpu-limit [SERVICE=]CPUS`, e.g. `2` or `zebra=1.5`
+pub fn parse_cpu_limit(input: &str) -> std::result::Result<ServiceLimit, String> {
+    let limit = split_limit(input);
+
+    if !limit.value.parse::<f64>().is_ok_and(|c| c > 0.0) {
+        return Err(format!(
+            "invalid CPU limit '{}' (expected e.g. 2, 0.5 or zebra=2)",
+            limit.value
+        ));
+    }
+
+    Ok(limit)
+}
+
+fn split_limit(input: &str) -> ServiceLimit {
+    match input.split_once('=') {
+        Some((service, value)) => ServiceLimit {
+            service: service.trim().to_string(),
+            value: value.trim().to_string(),
+        },
+        None => ServiceLimit {
+            service: DEFAULT_LIMITED_SERVICE.to_string(),
+            value: input.trim().to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_limits_apply_to_zebra() {
+        let limit = parse_memory_limit("4G").unwrap();
+        assert_eq!(limit.service, "zebra");
+        assert_eq!(limit.value, "4g");
+
+        let limit = parse_cpu_limit("lightwalletd=0.5").unwrap();
+        assert_eq!(limit.service, "lightwalletd");
+        assert_eq!(limit.value, "0.5");
+    }
+
+    #[test]
+    fn malformed_limits_are_rejected() {
+        assert!(parse_memory_limit("lots").is_err());
+        assert!(parse_memory_limit("zebra=4x").is_err());
+        assert!(parse_cpu_limit("0").is_err());
+    }
+
+    #[test]
+    fn override_sets_limits_and_reservation() {
+        let limits = ResourceLimits {
+            memory: vec![parse_memory_limit("1g").unwrap()],
+            cpus: vec![parse_cpu_limit("2").unwrap()],
+        };
+
+        assert_eq!(
+            limits.to_override_yaml(),
+            "# Generated by zecdev up; do not edit\n\
+             services:\n  \
+             zebra:\n    \
+             deploy:\n      \
+             resources:\n        \
+             limits:\n          \
+             memory: 1g\n          \
+             cpus: \"2\"\n        \
+             reservations:\n          \
+             memory: 1g\n"
+        );
+        assert_eq!(limits.summary(), vec!["zebra: memory=1g cpus=2"]);
+    }
+}
@@ -1,2 +1,7 @@
 pub mod compose;
-pub mod health;
\ No newline at end of file
+pub mod data_dir;
+pub mod health;
+pub mod limits;
+pub mod mounts;
+pub mod options;
+pub mod runtime;
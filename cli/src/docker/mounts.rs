@@ -0,0 +1,171 @@
+use crate::error::{Result, ZecDevError};
+use std::path::{self, PathBuf};
+
+/// Service a bare `--mount HOST:CONTAINER` applies to
+pub const DEFAULT_MOUNT_SERVICE: &str = "zebra";
+
+/// A bind mount for one service, as given on the command line
+/// (`zebra=./zebra.toml:/etc/zebrad/zebrad.toml:ro`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindMount {
+    pub service: String,
+    pub host: PathBuf,
+    pub container: String,
+    pub read_only: bool,
+}
+
+impl BindMount {
+    /// Compose's short volume syntax, e.g. `/home/me/zebra.toml:/etc/zebrad/zebrad.toml:ro`
+    pub fn spec(&self) -> String {
+        let mut spec = format!("{}:{}", self.host.display(), self.container);
+        if self.read_only {
+            spec.push_str(":ro");
+        }
+        spec
+    }
+}
+
+/// Services that have at least one mount, in first-mentioned order
+pub fn services(mounts: &[BindMount]) -> Vec<&str> {
+    let mut services: Vec<&str> = Vec::new();
+    for mount in mounts {
+        if !services.contains(&mount.service.as_str()) {
+            services.push(&mount.service);
+        }
+    }
+    services
+}
+
+/// `mounts` with host paths made absolute, failing if one doesn't exist.
+///
+/// Compose resolves relative paths against the project directory, so they
+/// are fixed here against the directory `up` was run from.
+pub fn resolve(mounts: &[BindMount]) -> Result<Vec<BindMount>> {
+    mounts
+        .iter()
+        .map(|mount| {
+            if !mount.host.exists() {
+                return Err(ZecDevError::Config(format!(
+                    "mount source {} does not exist",
+                    mount.host.display()
+                ))
+                .with_hint("Relative --mount paths are resolved from the current directory."));
+            }
+            Ok(BindMount {
+                host: path::absolute(&mount.host)?,
+                ..mount.clone()
+            })
+        })
+        .collect()
+}
+
+/// Compose override adding every mount to its service's `volumes`
+pub fn to_override_yaml(mounts: &[BindMount]) -> String {
+    let mut yaml = String::from("# Generated by zecdev up; do not edit\nservices:\n");
+
+    for service in services(mounts) {
+        yaml.push_str(&format!("  {}:\n    volumes:\n", service));
+        for mount in mounts.iter().filter(|m| m.service == service) {
+            // Single-quoted so Windows paths need no escaping
+            yaml.push_str(&format!("      - '{}'\n", mount.spec().replace('\'', "''")));
+        }
+    }
+
+    yaml
+}
+
+/// clap parser for `--mount [SERVICE=]HOST:CONTAINER[:ro]`
+pub fn parse_mount(input: &str) -> std::result::Result<BindMount, String> {
+    let invalid = || {
+        format!(
+            "invalid mount '{}' (expected HOST:CONTAINER, e.g. ./zebra.toml:/etc/zebrad/zebrad.toml or faucet=./scripts:/scripts:ro)",
+            input
+        )
+    };
+
+    // A service name never holds a path separator, so `=` inside a path is left alone
+    let (service, spec) = match input.split_once('=') {
+        Some((service, spec)) if is_service_name(service) => (service.to_string(), spec),
+        _ => (DEFAULT_MOUNT_SERVICE.to_string(), input),
+    };
+
+    let (spec, read_only) = match spec.strip_suffix(":ro") {
+        Some(spec) => (spec, true),
+        None => (spec.strip_suffix(":rw").unwrap_or(spec), false),
+    };
+
+    // Split at the last `:/`, so Windows drive letters stay with the host path
+    let split = spec.rfind(":/").ok_or_else(invalid)?;
+    let (host, container) = (&spec[..split], &spec[split + 1..]);
+    if host.is_empty() || container.len() < 2 {
+        return Err(invalid());
+    }
+
+    Ok(BindMount {
+        service,
+        host: PathBuf::from(host),
+        container: container.to_string(),
+        read_only,
+    })
+}
+
+fn is_service_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_mounts_apply_to_zebra() {
+        let mount = parse_mount("./zebra.toml:/etc/zebrad/zebrad.toml:ro").unwrap();
+        assert_eq!(mount.service, "zebra");
+        assert_eq!(mount.host, PathBuf::from("./zebra.toml"));
+        assert_eq!(mount.container, "/etc/zebrad/zebrad.toml");
+        assert!(mount.read_only);
+
+        let mount = parse_mount("faucet=C:/work/scripts:/scripts").unwrap();
+        assert_eq!(mount.service, "faucet");
+        assert_eq!(mount.host, PathBuf::from("C:/work/scripts"));
+        assert_eq!(mount.container, "/scripts");
+        assert!(!mount.read_only);
+
+        let mount = parse_mount("./a=b:/data").unwrap();
+        assert_eq!(mount.service, "zebra");
+        assert_eq!(mount.host, PathBuf::from("./a=b"));
+    }
+
+    #[test]
+    fn malformed_mounts_are_rejected() {
+        assert!(parse_mount("./zebra.toml").is_err());
+        assert!(parse_mount("./zebra.toml:relative/path").is_err());
+        assert!(parse_mount(":/data").is_err());
+        assert!(parse_mount("zebra=./x:/").is_err());
+    }
+
+    #[test]
+    fn override_groups_mounts_by_service() {
+        let mounts = [
+            parse_mount("/srv/zebra.toml:/etc/zebrad/zebrad.toml:ro").unwrap(),
+            parse_mount("faucet=/srv/it's:/scripts").unwrap(),
+            parse_mount("/srv/data:/data").unwrap(),
+        ];
+
+        assert_eq!(
+            to_override_yaml(&mounts),
+            "# Generated by zecdev up; do not edit\n\
+             services:\n  \
+             zebra:\n    \
+             volumes:\n      \
+             - '/srv/zebra.toml:/etc/zebrad/zebrad.toml:ro'\n      \
+             - '/srv/data:/data'\n  \
+             faucet:\n    \
+             volumes:\n      \
+             - '/srv/it''s:/scripts'\n"
+        );
+    }
+}
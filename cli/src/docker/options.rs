@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static OPTIONS: OnceLock<ComposeOptions> = OnceLock::new();
+
+/// Global settings applied to every `docker compose` invocation.
+///
+/// Set once from the command line in `main` and read by `DockerCompose::new()`.
+#[derive(Debug, Clone, Default)]
+pub struct ComposeOptions {
+    /// Compose file to use instead of the project's docker-compose.yml
+    pub file: Option<PathBuf>,
+    /// Files merged over the compose file, in order, from `--compose-override`
+    pub overrides: Vec<PathBuf>,
+    /// Compose project name, so several stacks can coexist
+    pub project_name: Option<String>,
+    /// Host directory for service state instead of named volumes, from `--data-dir`
+    pub data_dir: Option<PathBuf>,
+    /// Print commands that change the devnet instead of running them
+    pub dry_run: bool,
+}
+
+impl ComposeOptions {
+    /// Make these the options used for the rest of the process
+    pub fn install(self) {
+        let _ = OPTIONS.set(self);
+    }
+
+    /// The installed options, or defaults if none were installed
+    pub fn current() -> ComposeOptions {
+        OPTIONS.get().cloned().unwrap_or_default()
+    }
+
+    /// Arguments that go between `docker compose` and the subcommand.
+    ///
+    /// Compose merges later `-f` files over earlier ones, so overrides come
+    /// after the base file. Naming any file stops compose from finding
+    /// docker-compose.yml in `project_dir` itself, so it is then named too.
+    pub fn global_args(&self, project_dir: &Path) -> Vec<String> {
+        let mut args = Vec::new();
+
+        let base = match &self.file {
+            Some(file) => Some(file.clone()),
+            None if !self.overrides.is_empty() => Some(project_dir.join("docker-compose.yml")),
+            None => None,
+        };
+        for file in base.iter().chain(&self.overrides) {
+            args.push("-f".to_string());
+            args.push(file.to_string_lossy().to_string());
+        }
+
+        if let Some(project) = &self.project_name {
+            args.push("-p".to_string());
+            args.push(project.clone());
+        }
+
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_add_no_arguments() {
+        assert!(ComposeOptions::default().global_args(Path::new("/repo")).is_empty());
+    }
+
+    #[test]
+    fn file_and_project_are_passed_through() {
+        let options = ComposeOptions {
+            file: Some(PathBuf::from("/tmp/custom.yml")),
+            project_name: Some("devnet-2".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            options.global_args(Path::new("/repo")),
+            vec!["-f", "/tmp/custom.yml", "-p", "devnet-2"]
+        );
+    }
+
+    #[test]
+    fn overrides_follow_the_base_file_so_their_keys_win() {
+        let options = ComposeOptions {
+            overrides: vec![PathBuf::from("/tmp/env.yml"), PathBuf::from("/tmp/mounts.yml")],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            options.global_args(Path::new("/repo")),
+            vec!["-f", "/repo/docker-compose.yml", "-f", "/tmp/env.yml", "-f", "/tmp/mounts.yml"]
+        );
+    }
+
+    #[test]
+    fn overrides_layer_over_a_custom_file() {
+        let options = ComposeOptions {
+            file: Some(PathBuf::from("/tmp/custom.yml")),
+            overrides: vec![PathBuf::from("/tmp/env.yml")],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            options.global_args(Path::new("/repo")),
+            vec!["-f", "/tmp/custom.yml", "-f", "/tmp/env.yml"]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use crate::error::{Result, ZecDevError};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+/// Environment variable that forces a runtime instead of auto-detecting one
+pub const RUNTIME_ENV: &str = "ZECKIT_RUNTIME";
+
+/// A container engine able to run compose files.
+///
+/// `DockerCompose` drives the devnet through whichever runtime is detected;
+/// this trait captures where Docker and Podman differ.
+pub trait ContainerRuntime: Send + Sync {
+    /// Executable on PATH, e.g. `docker`
+    fn program(&self) -> &'static str;
+
+    /// Name used in messages, e.g. `Docker`
+    fn display_name(&self) -> &'static str;
+
+    /// `info --format` template for the directory holding volumes
+    fn storage_root_format(&self) -> &'static str;
+
+    /// Extra `volume prune` flags needed to include named volumes
+    fn volume_prune_args(&self) -> &'static [&'static str];
+
+    /// A bare runtime command
+    fn command(&self) -> Command {
+        Command::new(self.program())
+    }
+
+    /// `<runtime> compose`
+    fn compose(&self) -> Command {
+        let mut cmd = self.command();
+        cmd.arg("compose");
+        cmd
+    }
+
+    /// Whether the CLI is on PATH
+    fn is_installed(&self) -> bool {
+        succeeds(self.command().arg("--version"))
+    }
+
+    /// Whether the daemon (or Podman service) answers
+    fn is_running(&self) -> bool {
+        succeeds(self.command().arg("info"))
+    }
+
+    /// Directory holding images and volumes, if the runtime reports it
+    fn storage_root(&self) -> Option<PathBuf> {
+        let output = self.command().args(["info", "--format", self.storage_root_format()]).output().ok()?;
+        let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !root.is_empty()).then(|| PathBuf::from(root))
+    }
+
+    /// Whether `<runtime> compose` is available
+    fn has_compose(&self) -> bool {
+        succeeds(self.compose().arg("version"))
+    }
+}
+
+pub struct Docker;
+
+impl ContainerRuntime for Docker {
+    fn program(&self) -> &'static str {
+        "docker"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Docker"
+    }
+
+    fn storage_root_format(&self) -> &'static str {
+        "{{.DockerRootDir}}"
+    }
+
+    fn volume_prune_args(&self) -> &'static [&'static str] {
+        // Since Docker 23 plain `volume prune` only removes anonymous volumes
+        &["--all"]
+    }
+}
+
+pub struct Podman;
+
+impl ContainerRuntime for Podman {
+    fn program(&self) -> &'static str {
+        "podman"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Podman"
+    }
+
+    fn storage_root_format(&self) -> &'static str {
+        "{{.Store.GraphRoot}}"
+    }
+
+    fn volume_prune_args(&self) -> &'static [&'static str] {
+        // Podman prunes named volumes already and has no --all flag
+        &[]
+    }
+}
+
+/// The runtime named by `ZECKIT_RUNTIME`, else Docker, else Podman
+pub fn detect() -> Result<Arc<dyn ContainerRuntime>> {
+    if let Some(name) = std::env::var_os(RUNTIME_ENV).filter(|v| !v.is_empty()) {
+        let name = name.to_string_lossy();
+        return from_name(&name).ok_or_else(|| {
+            ZecDevError::Config(format!(
+                "{} must be docker or podman, not '{}'",
+                RUNTIME_ENV, name
+            ))
+        });
+    }
+
+    let candidates: [Arc<dyn ContainerRuntime>; 2] = [Arc::new(Docker), Arc::new(Podman)];
+    candidates
+        .into_iter()
+        .find(|runtime| runtime.is_installed())
+        .ok_or(ZecDevError::RuntimeNotFound)
+}
+
+fn from_name(name: &str) -> Option<Arc<dyn ContainerRuntime>> {
+    match name.to_ascii_lowercase().as_str() {
+        "docker" => Some(Arc::new(Docker)),
+        "podman" => Some(Arc::new(Podman)),
+        _ => None,
+    }
+}
+
+fn succeeds(cmd: &mut Command) -> bool {
+    cmd.stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_names_are_case_insensitive() {
+        assert_eq!(from_name("Podman").map(|r| r.program()), Some("podman"));
+        assert_eq!(from_name("docker").map(|r| r.program()), Some("docker"));
+        assert!(from_name("containerd").is_none());
+    }
+}
@@ -1,3 +1,8 @@
+use crate::config::Network;
+use crate::lock::Holder;
+use crate::utils::format_bytes;
+use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, ZecDevError>;
@@ -7,11 +12,100 @@ pub enum ZecDevError {
     #[error("Docker error: {0}")]
     Docker(String),
     
+    #[error("`{command}` did not finish within {timeout:.0?}")]
+    DockerTimeout { command: String, timeout: Duration },
+    
+    #[error("{0} does not appear to be running")]
+    DockerUnavailable(&'static str),
+    
+    #[error("Neither docker nor podman was found on PATH")]
+    RuntimeNotFound,
+    
+    #[error("Image for {0} not found upstream")]
+    ImageNotFound(String),
+    
+    #[error("Port {port} is in use (needed by {service})")]
+    PortInUse { port: u16, service: String },
+    
+    #[error("Not enough free disk space at {}: {} available, {} required", .path.display(), format_bytes(*.available), format_bytes(*.required))]
+    InsufficientDisk { path: PathBuf, required: u64, available: u64 },
+    
     #[error("Health check failed: {0}")]
     HealthCheck(String),
     
-    #[error("Service not ready: {0}")]
-    ServiceNotReady(String),
+    #[error("{service} not ready after {attempts} attempts over {elapsed:.0?}")]
+    HealthTimeout { service: String, attempts: u32, elapsed: Duration },
+    
+    #[error("Zebra did not reach height {target} within {elapsed:.0?} (last seen: {})", .height.map_or("no answer".to_string(), |h| h.to_string()))]
+    HeightTimeout { target: u64, height: Option<u64>, elapsed: Duration },
+    
+    #[error("Startup did not finish within {limit:.0?} (while {phase}); ready: {}; not ready: {}", list_or_none(.ready), list_or_none(.pending))]
+    StartupTimeout { limit: Duration, phase: &'static str, ready: Vec<String>, pending: Vec<String> },
+    
+    #[error("Sync appears stalled at height {height} (headers at {headers}); no new blocks for {stalled:.0?}")]
+    SyncStalled { height: u64, headers: u64, stalled: Duration },
+    
+    #[error("{backend} is following chain '{reported}', but the devnet runs {expected} ('{}')", .expected.chain_name())]
+    BackendChainMismatch { backend: String, reported: String, expected: Network },
+    
+    #[error("{service} {state} - last log lines:\n{logs}")]
+    ServiceCrashed { service: String, state: String, logs: String },
+    
+    #[error("Unknown service '{name}' (available: {})", .available.join(", "))]
+    UnknownService { name: String, available: Vec<String> },
+    
+    #[error("Service '{0}' is not running")]
+    ServiceNotRunning(String),
+    
+    #[error("{service} is running but serves no metrics at {url}")]
+    MetricsDisabled { service: String, url: String },
+    
+    #[error("A log line matched --fail-on '{pattern}': {line}")]
+    LogFailure { pattern: String, line: String },
+    
+    #[error("No log line matched '{pattern}' within {elapsed:.0?}")]
+    LogTimeout { pattern: String, elapsed: Duration },
+    
+    #[error("{}", locked_message(.holder.as_ref(), *.running))]
+    Locked {
+        holder: Option<Holder>,
+        running: bool,
+        /// The refused command's flag for taking over the lock
+        flag: &'static str,
+    },
+    
+    #[error("No devnet running")]
+    DevnetNotRunning,
+    
+    #[error("No devnet found - run zecdev up first")]
+    DevnetNotFound,
+    
+    #[error("RPC error {code}: {message}")]
+    Rpc { code: i64, message: String },
+    
+    #[error("This only works on regtest (active chain: {0})")]
+    NotRegtest(String),
+    
+    #[error("Faucet error: {0}")]
+    Faucet(String),
+    
+    #[error("Faucet out of funds")]
+    FaucetEmpty,
+    
+    #[error("Zebra does not support wallet RPCs ({0} is not available)")]
+    WalletUnsupported(&'static str),
+    
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    
+    #[error("Snapshot error: {0}")]
+    Snapshot(String),
+    
+    #[error("Bundle error: {0}")]
+    Bundle(String),
     
     #[error("Configuration error: {0}")]
     Config(String),
@@ -24,4 +118,156 @@ pub enum ZecDevError {
     
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
-}
\ No newline at end of file
+    
+    /// Any error with remediation advice attached by the caller
+    #[error("{source}")]
+    Hinted { source: Box<ZecDevError>, hint: String },
+}
+
+impl ZecDevError {
+    /// Attach advice for fixing this error, replacing the default hint
+    pub fn with_hint(self, hint: impl Into<String>) -> Self {
+        ZecDevError::Hinted {
+            source: Box::new(self),
+            hint: hint.into(),
+        }
+    }
+    
+    /// What the user can do about this error, shown below it as "Hint: ..."
+    pub fn hint(&self) -> Option<String> {
+        let hint = match self {
+            ZecDevError::Hinted { hint, .. } => hint.clone(),
+            ZecDevError::DockerUnavailable(runtime) => {
+                format!("Start {} and retry. zecdev doctor checks the rest of your setup.", runtime)
+            }
+            ZecDevError::DockerTimeout { .. } => {
+                "The container runtime may be hung; check docker info. Raise docker_timeout_secs in zeckit.toml if the command is just slow.".into()
+            }
+            ZecDevError::RuntimeNotFound => {
+                "Install Docker (https://docs.docker.com/get-docker/) or Podman, or point ZECKIT_RUNTIME at one.".into()
+            }
+            ZecDevError::ImageNotFound(_) => {
+                "Its pinned tag may no longer exist upstream; check the image tags in docker-compose.yml.".into()
+            }
+            ZecDevError::PortInUse { .. } => {
+                "Stop whatever holds the port, or move the devnet with zeckit.toml.".into()
+            }
+            ZecDevError::InsufficientDisk { .. } => {
+                "Free some space (zecdev clean removes leftover containers and volumes), or lower regtest_min_disk_gb / testnet_min_disk_gb in zeckit.toml.".into()
+            }
+            ZecDevError::HealthTimeout { service, .. } => {
+                format!("Run zecdev logs {} to see what went wrong.", service)
+            }
+            ZecDevError::StartupTimeout { pending, .. } => match pending.first() {
+                Some(service) => format!(
+                    "Services were left as they are. Run zecdev logs {} to see why it was slow, or raise --startup-timeout.",
+                    service
+                ),
+                None => "Services were left as they are. Raise --startup-timeout if this is expected.".into(),
+            },
+            ZecDevError::SyncStalled { .. } => {
+                "Zebra may have lost its peers: check zecdev rpc getpeerinfo and zecdev logs zebra. Raise --sync-stall-timeout if the network is just slow.".into()
+            }
+            ZecDevError::BackendChainMismatch { backend, expected, .. } => format!(
+                "{} reports the chain of the node it is connected to. Check that docker/configs/{} points at this devnet's Zebra, then run zecdev restart {}.",
+                backend,
+                expected.lightwalletd_config(),
+                backend
+            ),
+            ZecDevError::ServiceCrashed { service, .. } => {
+                format!("Fix the problem above, then run zecdev up again. zecdev logs {} shows the full output.", service)
+            }
+            ZecDevError::UnknownService { .. } => "Run zecdev ps to see the devnet's services.".into(),
+            ZecDevError::ServiceNotRunning(_) => "Start it with zecdev up.".into(),
+            ZecDevError::MetricsDisabled { service, .. } => match service.as_str() {
+                "zebra" => "Uncomment endpoint_addr under [metrics] in docker/configs/zebra.toml, then run zecdev restart zebra.".into(),
+                _ => format!("Check that {} publishes its metrics port in docker-compose.yml, then run zecdev up again.", service),
+            },
+            ZecDevError::LogTimeout { .. } => {
+                "Run zecdev logs to see what the services printed, or raise --timeout.".into()
+            }
+            ZecDevError::Locked { running: true, flag, .. } => {
+                format!("Wait for it to finish. If it is stuck, stop it or pass {} to take over the lock.", flag)
+            }
+            ZecDevError::Locked { running: false, flag, .. } => {
+                format!("It probably crashed; pass {} to take over the lock.", flag)
+            }
+            ZecDevError::DevnetNotRunning => "Is the devnet running? Try zecdev up.".into(),
+            ZecDevError::NotRegtest(_) => "Start the devnet with zecdev up --network regtest.".into(),
+            ZecDevError::FaucetEmpty => "Mine blocks to the faucet with zecdev mine, then retry.".into(),
+            ZecDevError::WalletUnsupported(_) => {
+                "Generate an address with your own wallet and pass it to zecdev faucet fund.".into()
+            }
+            _ => return None,
+        };
+        Some(hint)
+    }
+}
+
+fn locked_message(holder: Option<&Holder>, running: bool) -> String {
+    match holder {
+        Some(holder) if running => format!(
+            "Another zecdev operation is in progress (pid {}: zecdev {})",
+            holder.pid, holder.command
+        ),
+        Some(holder) => format!(
+            "A lock from zecdev {} (pid {}) remains, but that process has exited",
+            holder.command, holder.pid
+        ),
+        None => "Another zecdev operation is in progress".to_string(),
+    }
+}
+
+pub fn list_or_none(items: &[String]) -> String {
+    if items.is_empty() {
+        "none".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+/// A refused connection almost always means the devnet is not up
+pub fn connection_error(e: reqwest::Error) -> ZecDevError {
+    if e.is_connect() {
+        ZecDevError::DevnetNotRunning
+    } else {
+        ZecDevError::Http(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn common_errors_carry_default_hints() {
+        assert_eq!(
+            ZecDevError::DevnetNotRunning.hint().as_deref(),
+            Some("Is the devnet running? Try zecdev up.")
+        );
+        assert!(ZecDevError::Config("bad".into()).hint().is_none());
+    }
+    
+    #[test]
+    fn startup_timeout_lists_ready_and_pending_services() {
+        let e = ZecDevError::StartupTimeout {
+            limit: Duration::from_secs(600),
+            phase: "waiting for health checks",
+            ready: vec!["zebra".into()],
+            pending: vec!["faucet".into(), "lightwalletd".into()],
+        };
+        
+        assert_eq!(
+            e.to_string(),
+            "Startup did not finish within 600s (while waiting for health checks); ready: zebra; not ready: faucet, lightwalletd"
+        );
+        assert!(e.hint().unwrap().contains("zecdev logs faucet"));
+    }
+    
+    #[test]
+    fn explicit_hint_keeps_the_message() {
+        let e = ZecDevError::Faucet("wallet locked".into()).with_hint("Restart the faucet.");
+        assert_eq!(e.to_string(), "Faucet error: wallet locked");
+        assert_eq!(e.hint().as_deref(), Some("Restart the faucet."));
+    }
+}
@@ -0,0 +1,268 @@
+use crate::config::settings::Settings;
+use crate::error::{connection_error, Result, ZecDevError};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use tokio::time::Duration;
+
+pub const ZATS_PER_ZEC: u64 = 100_000_000;
+
+/// Client for the bundled faucet's HTTP API
+pub struct FaucetClient {
+    client: Client,
+    base_url: String,
+}
+
+/// Successful response from `POST /request`
+#[derive(Debug, Deserialize)]
+pub struct FundingReceipt {
+    pub txid: String,
+    pub address: String,
+    pub amount: f64,
+    #[serde(default)]
+    pub new_balance: Option<f64>,
+}
+
+/// Faucet balance and usage, from `GET /stats`
+#[derive(Debug, Clone, Serialize)]
+pub struct FaucetStats {
+    pub address: String,
+    pub balance_zat: u64,
+    pub total_dispensed: u64,
+    pub requests_served: u64,
+    pub uptime: String,
+}
+
+/// `/stats` as the faucet sends it, amounts in ZEC
+#[derive(Debug, Deserialize)]
+struct StatsResponse {
+    faucet_address: String,
+    current_balance: f64,
+    #[serde(default)]
+    total_sent: f64,
+    #[serde(default)]
+    total_requests: u64,
+    #[serde(default)]
+    uptime: String,
+}
+
+impl From<StatsResponse> for FaucetStats {
+    fn from(stats: StatsResponse) -> Self {
+        Self {
+            address: stats.faucet_address,
+            balance_zat: zec_to_zat(stats.current_balance),
+            total_dispensed: zec_to_zat(stats.total_sent),
+            requests_served: stats.total_requests,
+            uptime: stats.uptime,
+        }
+    }
+}
+
+/// One `address amount` line of a `faucet fund-batch` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchEntry {
+    /// 1-based line number, for reporting
+    pub line: usize,
+    pub address: String,
+    pub amount_zat: u64,
+}
+
+/// Read a funding file: one `address amount` pair (amount in ZEC) per line.
+///
+/// Blank lines and `#` comments are skipped. Addresses are not checked here,
+/// so one bad address fails only its own transfer.
+pub fn load_batch(path: &Path) -> Result<Vec<BatchEntry>> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        ZecDevError::Config(format!("could not read funding file {}: {}", path.display(), e))
+    })?;
+    parse_batch(&contents).map_err(|e| ZecDevError::Config(format!("{}: {}", path.display(), e)))
+}
+
+fn parse_batch(contents: &str) -> std::result::Result<Vec<BatchEntry>, String> {
+    let mut entries = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [address, amount] = fields[..] else {
+            return Err(format!("line {}: expected 'address amount', got '{}'", line_number, line));
+        };
+        let amount: f64 = amount
+            .parse()
+            .ok()
+            .filter(|a: &f64| a.is_finite() && *a > 0.0)
+            .ok_or_else(|| format!("line {}: '{}' is not a positive ZEC amount", line_number, amount))?;
+
+        entries.push(BatchEntry {
+            line: line_number,
+            address: address.to_string(),
+            amount_zat: zec_to_zat(amount),
+        });
+    }
+
+    if entries.is_empty() {
+        return Err("no addresses to fund".into());
+    }
+    Ok(entries)
+}
+
+/// Convert a ZEC amount from the faucet API to zatoshis
+pub fn zec_to_zat(zec: f64) -> u64 {
+    (zec * ZATS_PER_ZEC as f64).round().max(0.0) as u64
+}
+
+impl FaucetClient {
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: settings.faucet_api_url.clone(),
+        }
+    }
+
+    /// The faucet's own receiving address
+    pub async fn get_address(&self) -> Result<String> {
+        let resp = self
+            .client
+            .get(format!("{}/address", self.base_url))
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(connection_error)?;
+
+        if !resp.status().is_success() {
+            return Err(ZecDevError::Faucet(format!(
+                "could not get faucet address (HTTP {})",
+                resp.status()
+            )));
+        }
+
+        let json: Value = resp.json().await?;
+        json.get("address")
+            .and_then(|a| a.as_str())
+            .map(|a| a.to_string())
+            .ok_or_else(|| ZecDevError::Faucet("address response missing 'address'".into()))
+    }
+
+    /// The faucet's version, from its `/` endpoint
+    pub async fn version(&self) -> Result<String> {
+        let resp = self
+            .client
+            .get(format!("{}/", self.base_url))
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(connection_error)?;
+
+        let json: Value = resp.json().await?;
+        json.get("version")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+            .ok_or_else(|| ZecDevError::Faucet("root response missing 'version'".into()))
+    }
+
+    /// Balance and usage counters
+    pub async fn get_stats(&self) -> Result<FaucetStats> {
+        let resp = self
+            .client
+            .get(format!("{}/stats", self.base_url))
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(connection_error)?;
+
+        if !resp.status().is_success() {
+            return Err(ZecDevError::Faucet(format!(
+                "could not get faucet stats (HTTP {})",
+                resp.status()
+            )));
+        }
+
+        let stats: StatsResponse = resp.json().await?;
+        Ok(stats.into())
+    }
+
+    /// Ask the faucet to send `amount_zat` zatoshis to `address`, or its
+    /// configured default amount when `None`
+    pub async fn request_funds(&self, address: &str, amount_zat: Option<u64>) -> Result<FundingReceipt> {
+        let mut body = serde_json::json!({ "address": address });
+        if let Some(amount_zat) = amount_zat {
+            // The faucet API works in ZEC
+            body["amount"] = (amount_zat as f64 / ZATS_PER_ZEC as f64).into();
+        }
+
+        let resp = self
+            .client
+            .post(format!("{}/request", self.base_url))
+            .json(&body)
+            .timeout(Duration::from_secs(30))
+            .send()
+            .await
+            .map_err(connection_error)?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body: Value = resp.json().await.unwrap_or(Value::Null);
+            let message = body
+                .get("error")
+                .and_then(|e| e.as_str())
+                .map(|e| e.to_string())
+                .unwrap_or_else(|| format!("request failed with HTTP {}", status));
+            return Err(ZecDevError::Faucet(message));
+        }
+
+        Ok(resp.json().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_file_skips_comments_and_blank_lines() {
+        let entries = parse_batch("# alice and bob\ntmAlice 2.5\n\n  tmBob 10 # change\n").unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                BatchEntry { line: 2, address: "tmAlice".into(), amount_zat: 250_000_000 },
+                BatchEntry { line: 4, address: "tmBob".into(), amount_zat: 1_000_000_000 },
+            ]
+        );
+    }
+
+    #[test]
+    fn batch_file_rejects_malformed_lines() {
+        assert_eq!(
+            parse_batch("tmAlice 1\ntmBob\n").unwrap_err(),
+            "line 2: expected 'address amount', got 'tmBob'"
+        );
+        assert_eq!(
+            parse_batch("tmAlice -1\n").unwrap_err(),
+            "line 1: '-1' is not a positive ZEC amount"
+        );
+        assert!(parse_batch("# nothing\n").is_err());
+    }
+
+    #[test]
+    fn stats_amounts_are_converted_to_zats() {
+        let json = r#"{
+            "faucet_address": "tmFaucet",
+            "current_balance": 12.5,
+            "total_requests": 3,
+            "total_sent": 30.00000001,
+            "total_funded": 42.5,
+            "uptime": "1h 2m"
+        }"#;
+        let stats: FaucetStats = serde_json::from_str::<StatsResponse>(json).unwrap().into();
+
+        assert_eq!(stats.balance_zat, 1_250_000_000);
+        assert_eq!(stats.total_dispensed, 3_000_000_001);
+        assert_eq!(stats.requests_served, 3);
+    }
+}
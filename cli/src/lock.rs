@@ -0,0 +1,198 @@
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecDevError};
+use crate::utils::project_state_dir;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Lock file of the operation this process holds, for exits that skip `Drop`
+static HELD: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Held while a command changes the devnet, so an overlapping `up`, `down`,
+/// `clean` or `reset` on the same project fails fast instead of racing it.
+///
+/// The lock is `~/.zeckit/<project>/lock` and is released when dropped.
+pub struct OperationLock {
+    path: PathBuf,
+}
+
+/// Who holds a lock, as written into the lock file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Holder {
+    pub pid: u32,
+    /// Subcommand, e.g. `up`
+    pub command: String,
+    /// Unix timestamp (seconds)
+    pub started_at: u64,
+}
+
+impl OperationLock {
+    /// Take the project's lock for `command`.
+    ///
+    /// With `force`, a lock left by a crashed run (or a stuck live one) is
+    /// taken over instead of refused. `flag` is the command's flag for that,
+    /// named in the error's hint.
+    pub fn acquire(project: &str, command: &str, force: bool, flag: &'static str) -> Result<Self> {
+        let dir = project_state_dir(project)?;
+        fs::create_dir_all(&dir)?;
+        Self::acquire_at(dir.join("lock"), command, force, flag)
+    }
+
+    fn acquire_at(path: PathBuf, command: &str, force: bool, flag: &'static str) -> Result<Self> {
+        let holder = Holder {
+            pid: std::process::id(),
+            command: command.to_string(),
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        };
+
+        // Creating the file is the atomic step; a second try follows a takeover
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(&serde_json::to_vec(&holder)?)?;
+                    *HELD.lock().unwrap_or_else(|e| e.into_inner()) = Some(path.clone());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let current = read_holder(&path);
+                    let running = current.as_ref().is_none_or(|h| process_alive(h.pid));
+                    if !force {
+                        return Err(ZecDevError::Locked { holder: current, running, flag });
+                    }
+                    if let Some(current) = &current {
+                        eprintln!(
+                            "{} Taking over the lock from zecdev {} (pid {})",
+                            "⚠".yellow(),
+                            current.command,
+                            current.pid
+                        );
+                    }
+                    match fs::remove_file(&path) {
+                        Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+                        _ => {}
+                    }
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Err(ZecDevError::Locked { holder: read_holder(&path), running: true, flag })
+    }
+}
+
+/// Lock `compose`'s project for `command`, taken over with `force` (the
+/// command's `flag`). Dry runs change nothing, so they take no lock.
+pub fn lock_project(
+    compose: &DockerCompose,
+    command: &str,
+    force: bool,
+    flag: &'static str,
+) -> Result<Option<OperationLock>> {
+    if compose.is_dry_run() {
+        return Ok(None);
+    }
+    OperationLock::acquire(&compose.project_name()?, command, force, flag).map(Some)
+}
+
+impl Drop for OperationLock {
+    fn drop(&mut self) {
+        release(&self.path);
+    }
+}
+
+/// Release this process's lock, if it holds one. For paths that end in
+/// `process::exit`, which skips destructors.
+pub fn release_held() {
+    let held = HELD.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(path) = held {
+        release(&path);
+    }
+}
+
+fn release(path: &Path) {
+    // After a takeover the file is someone else's; leave it to them
+    if read_holder(path).is_some_and(|h| h.pid == std::process::id()) {
+        let _ = fs::remove_file(path);
+    }
+    let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+    if held.as_deref() == Some(path) {
+        *held = None;
+    }
+}
+
+/// The lock's holder; `None` if the file is gone or still being written
+fn read_holder(path: &Path) -> Option<Holder> {
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+/// Whether a process with this id still exists
+fn process_alive(pid: u32) -> bool {
+    if cfg!(target_os = "linux") {
+        Path::new("/proc").join(pid.to_string()).exists()
+    } else if cfg!(windows) {
+        std::process::Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+            .is_ok_and(|out| String::from_utf8_lossy(&out.stdout).contains(&pid.to_string()))
+    } else {
+        std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("zecdev-lock-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn a_second_run_is_refused_until_the_first_releases() {
+        let path = lock_path("held");
+        let lock = OperationLock::acquire_at(path.clone(), "up", false, "--force").unwrap();
+
+        let e = OperationLock::acquire_at(path.clone(), "reset", false, "--force-unlock").err().unwrap();
+        match &e {
+            ZecDevError::Locked { holder: Some(holder), running: true, .. } => {
+                assert_eq!(holder.pid, std::process::id());
+                assert_eq!(holder.command, "up");
+            }
+            other => panic!("expected the lock to be held, got {:?}", other),
+        }
+        // The hint names the refused command's own takeover flag
+        assert!(e.hint().unwrap().contains("pass --force-unlock to"));
+
+        drop(lock);
+        assert!(!path.exists());
+        drop(OperationLock::acquire_at(path, "down", false, "--force").unwrap());
+    }
+
+    #[test]
+    fn stale_locks_are_reported_and_can_be_taken_over() {
+        let path = lock_path("stale");
+        let stale = Holder { pid: u32::MAX, command: "up".into(), started_at: 0 };
+        fs::write(&path, serde_json::to_vec(&stale).unwrap()).unwrap();
+
+        let e = OperationLock::acquire_at(path.clone(), "up", false, "--force").err().unwrap();
+        assert!(matches!(e, ZecDevError::Locked { running: false, .. }));
+        assert!(e.to_string().contains("pid 4294967295"));
+
+        let lock = OperationLock::acquire_at(path.clone(), "up", true, "--force").unwrap();
+        assert_eq!(read_holder(&path).unwrap().pid, std::process::id());
+        drop(lock);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,27 @@
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::Level;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::prelude::*;
+
+/// Append structured logs, one JSON object per line, to `path`.
+///
+/// Only ZecKit's own events are recorded, at debug level and up; HTTP client
+/// internals would drown them out. Console output is not affected, and
+/// without a log file nothing is recorded at all.
+pub fn init(path: &Path) -> std::io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    
+    let layer = tracing_subscriber::fmt::layer()
+        .json()
+        .with_writer(Mutex::new(file))
+        .with_span_events(FmtSpan::CLOSE) // Span close events carry durations
+        .with_current_span(true)
+        .with_span_list(true)
+        .with_filter(Targets::new().with_target(env!("CARGO_CRATE_NAME"), Level::DEBUG));
+    
+    tracing_subscriber::registry().with(layer).init();
+    Ok(())
+}
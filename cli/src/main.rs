@@ -1,72 +1,915 @@
-use clap::{Parser, Subcommand};
-use colored::*;
+use backend::BackendTls;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::Shell;
+use commands::completions::ServiceNameParser;
+use commands::connect_config::ConnectFormat;
+use commands::up::Backend;
+use config::chain::{self, ActivationHeight};
+use config::peers::{self, Peer};
+use config::{LogLevel, Network};
+use docker::limits::{self, ResourceLimits, ServiceLimit};
+use docker::mounts::{self, BindMount};
+use docker::options::ComposeOptions;
+use error::{Result, ZecDevError};
+use regex::Regex;
+use rpc::RpcAuth;
+use std::path::PathBuf;
+use wallet::AddressKind;
 use std::process;
+use std::time::Duration;
+use tracing::Instrument;
 
+mod address;
+mod backend;
+mod bundle;
 mod commands;
 mod docker;
 mod config;
 mod error;
+mod faucet;
+mod lock;
+mod logging;
+mod preflight;
+mod rpc;
+mod services;
+mod state;
 mod utils;
-
-use error::Result;
+mod wallet;
 
 #[derive(Parser)]
 #[command(name = "zecdev")]
 #[command(about = "ZecKit - Developer toolkit for Zcash on Zebra", long_about = None)]
 #[command(version)]
 struct Cli {
+    /// Disable colored output (also honors the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    no_color: bool,
+    
+    /// Compose file to drive instead of the bundled docker-compose.yml
+    #[arg(long, global = true, env = "ZECKIT_COMPOSE_FILE")]
+    file: Option<PathBuf>,
+    
+    /// Extra compose file merged over the base one; repeat to layer several
+    #[arg(long = "compose-override", global = true, value_name = "PATH")]
+    compose_overrides: Vec<PathBuf>,
+    
+    /// Compose project name, to keep several stacks apart
+    #[arg(long, global = true, env = "ZECKIT_PROJECT")]
+    project_name: Option<String>,
+    
+    /// Keep chain and wallet data in this host directory instead of docker volumes
+    #[arg(long, global = true, value_name = "PATH", env = "ZECKIT_DATA_DIR")]
+    data_dir: Option<PathBuf>,
+    
+    /// Username for Zebra's RPC endpoint, when it requires auth
+    #[arg(long, global = true, env = "ZECKIT_RPC_USER", requires = "rpc_password")]
+    rpc_user: Option<String>,
+    
+    /// Password for --rpc-user
+    #[arg(long, global = true, env = "ZECKIT_RPC_PASSWORD", hide_env_values = true)]
+    rpc_password: Option<String>,
+    
+    /// Authenticate to Zebra's RPC endpoint with its cookie file
+    #[arg(long, global = true, value_name = "PATH", env = "ZECKIT_RPC_COOKIE", conflicts_with = "rpc_user")]
+    rpc_cookie: Option<PathBuf>,
+    
+    /// Dial lightwalletd and Zaino over TLS (gRPC over HTTPS)
+    #[arg(long, global = true, env = "ZECKIT_BACKEND_TLS")]
+    backend_tls: bool,
+    
+    /// CA certificate (PEM) to verify the backend against; without it, --backend-tls accepts any certificate
+    #[arg(long, global = true, value_name = "PATH", env = "ZECKIT_BACKEND_CA", requires = "backend_tls")]
+    backend_ca: Option<PathBuf>,
+    
+    /// Also write structured (JSON lines) debug logs to this file
+    #[arg(long, global = true, value_name = "PATH", env = "ZECKIT_LOG_FILE")]
+    log_file: Option<PathBuf>,
+    
+    /// Print the docker commands up, down or clean would run, without running them
+    #[arg(long, global = true)]
+    dry_run: bool,
+    
+    /// Echo every docker command before running it (long form only; `-v` is `down --volumes`)
+    #[arg(long, global = true, conflicts_with = "quiet")]
+    verbose: bool,
+    
+    /// Suppress banners and progress; print only errors and final results
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    
     #[command(subcommand)]
     command: Commands,
 }
 
+// Parsed once per run, so `up`'s many flags are not worth boxing
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Start the ZecKit devnet
     Up {
-        /// Light-client backend: lwd (lightwalletd) or zaino
-        #[arg(short, long, default_value = "none")]
-        backend: String,
+        /// Light-client backends: lwd (lightwalletd), zaino, or both as lwd,zaino (default: zeckit.toml, else none)
+        #[arg(short, long, env = "ZECKIT_BACKEND", value_delimiter = ',', value_enum)]
+        backend: Vec<Backend>,
+        
+        /// Zcash network for Zebra (default: zeckit.toml, else regtest)
+        #[arg(short, long, env = "ZECKIT_NETWORK", value_enum)]
+        network: Option<Network>,
         
         /// Force fresh start (remove volumes)
         #[arg(short, long)]
         fresh: bool,
+        
+        /// Recreate every container, instead of leaving healthy ones running
+        #[arg(long)]
+        force_recreate: bool,
+        
+        /// Take over the project's lock from a crashed or stuck zecdev run
+        #[arg(long)]
+        force: bool,
+        
+        /// Pull the latest images before starting
+        #[arg(long)]
+        pull: bool,
+        
+        /// Show --pull as one summary line instead of a progress bar per image
+        #[arg(long, requires = "pull")]
+        quiet_pull: bool,
+        
+        /// Rebuild locally built images (e.g. the faucet) before starting
+        #[arg(long)]
+        build: bool,
+        
+        /// Maximum time to wait for each service to become healthy, Zebra's sync included (e.g. 120s, 2h)
+        #[arg(long, value_parser = utils::parse_duration)]
+        timeout: Option<Duration>,
+        
+        /// Give up if the whole startup (pull, start, health checks) takes longer (e.g. 10m)
+        #[arg(long, value_name = "DURATION", value_parser = utils::parse_duration)]
+        startup_timeout: Option<Duration>,
+        
+        /// Fail if Zebra's sync stays at one height this long while behind the header tip (default: 120s)
+        #[arg(long, value_name = "DURATION", value_parser = utils::parse_duration)]
+        sync_stall_timeout: Option<Duration>,
+        
+        /// Give up on a service after this many health checks, polls during Zebra's sync included
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        max_attempts: Option<u32>,
+        
+        /// Only count the faucet ready once its wallet holds funds (at least ZEC, if given)
+        #[arg(long, value_name = "ZEC", num_args = 0..=1, default_missing_value = "0")]
+        require_faucet_funds: Option<f64>,
+        
+        /// Log level for Zebra and lightwalletd (default: the compose file's settings)
+        #[arg(long, value_enum)]
+        log_level: Option<LogLevel>,
+        
+        /// Memory cap, for Zebra or as SERVICE=SIZE (e.g. 4g, lightwalletd=512m); repeatable
+        #[arg(long, value_name = "[SERVICE=]SIZE", value_parser = limits::parse_memory_limit)]
+        memory_limit: Vec<ServiceLimit>,
+        
+        /// CPU cap, for Zebra or as SERVICE=CPUS (e.g. 2, faucet=0.5); repeatable
+        #[arg(long, value_name = "[SERVICE=]CPUS", value_parser = limits::parse_cpu_limit)]
+        cpu_limit: Vec<ServiceLimit>,
+        
+        /// Bind-mount a host file or directory, into Zebra or as SERVICE=HOST:CONTAINER (append :ro for read-only); repeatable
+        #[arg(long = "mount", value_name = "[SERVICE=]HOST:CONTAINER", value_parser = mounts::parse_mount)]
+        mounts: Vec<BindMount>,
+        
+        /// Regtest activation height as UPGRADE=HEIGHT (e.g. nu5=100); repeatable, overrides zeckit.toml
+        #[arg(long, value_name = "UPGRADE=HEIGHT", value_parser = chain::parse_activation_height)]
+        activation_height: Vec<ActivationHeight>,
+        
+        /// Testnet peers for Zebra to dial first, as HOST:PORT[,HOST:PORT...]; repeatable
+        #[arg(long, value_name = "HOST:PORT", value_delimiter = ',', value_parser = peers::parse_peer)]
+        peers: Vec<Peer>,
+        
+        /// Stop partially-started services if interrupted with Ctrl-C
+        #[arg(long)]
+        cleanup_on_interrupt: bool,
+        
+        /// Start every service at once instead of waiting for Zebra before the rest
+        #[arg(long)]
+        no_ordered_start: bool,
+        
+        /// Write the endpoint URLs to this file in dotenv format
+        #[arg(long, value_name = "PATH")]
+        env_out: Option<PathBuf>,
+        
+        /// Stream combined logs while health checks run, then return once everything is healthy
+        #[arg(long, conflicts_with = "attach")]
+        follow_until_healthy: bool,
+        
+        /// Follow combined service logs once everything is healthy
+        #[arg(long)]
+        attach: bool,
+        
+        /// While attached, restart a crashed service up to MAX times (default 3), then exit
+        #[arg(long, value_name = "MAX", num_args = 0..=1, default_missing_value = "3", requires = "attach")]
+        restart_on_failure: Option<u32>,
+        
+        /// Keep running and re-apply compose file and config changes
+        #[arg(long, conflicts_with = "attach")]
+        watch: bool,
+        
+        /// Also start the services of this compose profile (repeatable)
+        #[arg(long = "profile", value_name = "NAME")]
+        profiles: Vec<String>,
+        
+        /// Warn and carry on if this service fails to come up, instead of aborting (repeatable; not zebra or faucet)
+        #[arg(long, value_name = "SERVICE", value_delimiter = ',')]
+        optional: Vec<String>,
+        
+        /// ZEC the faucet sends when a request names no amount (overrides zeckit.toml)
+        #[arg(long, value_name = "ZEC")]
+        faucet_amount: Option<f64>,
+        
+        /// How long an address must wait between payouts, e.g. 30s (overrides zeckit.toml)
+        #[arg(long, value_name = "DURATION", value_parser = utils::parse_duration)]
+        faucet_cooldown: Option<Duration>,
+        
+        /// Generate a wallet address once the devnet is ready (see `wallet new`)
+        #[arg(long)]
+        create_wallet: bool,
+        
+        /// Fund the `address amount` pairs in this file once the devnet is ready (see `faucet fund-batch`)
+        #[arg(long, value_name = "FILE")]
+        fund: Option<PathBuf>,
+        
+        /// Write a JSON summary of what was started to this file (`-` for stdout)
+        #[arg(long, value_name = "PATH")]
+        summary_json: Option<PathBuf>,
     },
     
     /// Stop the ZecKit devnet
     Down {
-        /// Remove volumes (clean slate)
-        #[arg(short, long)]
-        purge: bool,
+        /// Also remove volumes, discarding chain state (clean slate)
+        #[arg(short = 'v', long, alias = "purge", short_alias = 'p')]
+        volumes: bool,
+        
+        /// Take over the project's lock from a crashed or stuck zecdev run
+        #[arg(long)]
+        force: bool,
+    },
+    
+    /// Pause services, keeping containers and volumes for `start`
+    Stop {
+        /// Services to stop (default: all running services)
+        #[arg(value_parser = ServiceNameParser)]
+        services: Vec<String>,
+    },
+    
+    /// Resume stopped services and wait until they are healthy
+    Start {
+        /// Services to start (default: every ZecKit container)
+        #[arg(value_parser = ServiceNameParser)]
+        services: Vec<String>,
+        
+        /// Maximum time to wait for each service to become healthy
+        #[arg(long, value_parser = utils::parse_duration)]
+        timeout: Option<Duration>,
+    },
+    
+    /// Remove stopped ZecKit containers and unused volumes and networks
+    Clean {
+        /// Take over the project's lock from a crashed or stuck zecdev run
+        #[arg(long)]
+        force: bool,
     },
     
     /// Show devnet status
-    Status,
+    Status {
+        /// Print machine-readable JSON instead of the human summary
+        #[arg(long)]
+        json: bool,
+        
+        /// Refresh every INTERVAL (default 2s) until Ctrl-C
+        #[arg(
+            long,
+            value_name = "INTERVAL",
+            num_args = 0..=1,
+            default_missing_value = "2",
+            value_parser = utils::parse_duration,
+            conflicts_with = "json"
+        )]
+        watch: Option<Duration>,
+    },
+    
+    /// List ZecKit containers with IDs, uptime and ports
+    Ps,
+    
+    /// Show CPU, memory and I/O use per ZecKit container
+    Top {
+        /// Refresh every INTERVAL (default 2s) until Ctrl-C
+        #[arg(
+            long,
+            value_name = "INTERVAL",
+            num_args = 0..=1,
+            default_missing_value = "2",
+            value_parser = utils::parse_duration
+        )]
+        watch: Option<Duration>,
+    },
+    
+    /// Show one service's image, restarts, health checks, mounts and ports
+    Info {
+        /// Service name (e.g. zebra, faucet, lightwalletd)
+        service: String,
+    },
+    
+    /// Save Zebra's chain state to ~/.zeckit/snapshots/<NAME>.tar.zst
+    Snapshot {
+        /// Snapshot name
+        name: String,
+    },
+    
+    /// Restore Zebra's chain state from a snapshot
+    Restore {
+        /// Snapshot name
+        name: String,
+        
+        /// Restore even if the devnet is running (Zebra is stopped and restarted)
+        #[arg(long)]
+        force: bool,
+    },
+    
+    /// Package the devnet's compose files, config and flags into a portable bundle
+    Export {
+        /// Bundle to write, e.g. devnet.tar.zst
+        bundle: PathBuf,
+        
+        /// Include Zebra's chain state (Zebra is stopped while it is saved)
+        #[arg(long)]
+        with_snapshot: bool,
+        
+        /// Keep passwords and tokens instead of replacing them with REDACTED
+        #[arg(long)]
+        include_secrets: bool,
+    },
+    
+    /// Unpack a bundle from `zecdev export` into this project
+    Import {
+        /// Bundle to read
+        bundle: PathBuf,
+        
+        /// Overwrite project files that differ from the bundle's
+        #[arg(long)]
+        force: bool,
+    },
     
     /// Run smoke tests
-    Test,
+    Test {
+        /// Light-client backends to check: none, lwd, zaino or lwd,zaino (default: the ones `up` started)
+        #[arg(short, long, value_delimiter = ',', value_enum)]
+        backend: Vec<Backend>,
+    },
+    
+    /// Diagnose common environment problems
+    Doctor,
+    
+    /// Print the CLI version, and optionally the devnet's component versions
+    Version {
+        /// Also list each service's pinned image and the version it reports when running
+        #[arg(long)]
+        components: bool,
+    },
+    
+    /// Print the devnet endpoint URLs in dotenv format
+    Env {
+        /// Backends to describe: none, lwd, zaino or lwd,zaino (default: the ones `up` started)
+        #[arg(short, long, value_delimiter = ',', value_enum)]
+        backend: Vec<Backend>,
+        
+        /// Write to this file instead of stdout
+        #[arg(short, long, value_name = "PATH")]
+        out: Option<PathBuf>,
+    },
+    
+    /// Print a config snippet that connects a wallet tool to the devnet
+    ConnectConfig {
+        /// Tool to write the config for
+        #[arg(short, long, value_enum)]
+        format: ConnectFormat,
+        
+        /// Backends to connect to: none, lwd, zaino or lwd,zaino (default: the ones `up` started)
+        #[arg(short, long, value_delimiter = ',', value_enum)]
+        backend: Vec<Backend>,
+    },
+    
+    /// Pull the latest component images
+    Pull {
+        /// Light-client backends whose images to include: lwd, zaino or lwd,zaino (default: zeckit.toml, else none)
+        #[arg(short, long, env = "ZECKIT_BACKEND", value_delimiter = ',', value_enum)]
+        backend: Vec<Backend>,
+        
+        /// One summary line instead of a progress bar per image
+        #[arg(long)]
+        quiet_pull: bool,
+    },
+    
+    /// Restart services without tearing down the devnet
+    Restart {
+        /// Services to restart (e.g. faucet)
+        #[arg(required = true, value_parser = ServiceNameParser)]
+        services: Vec<String>,
+        
+        /// Maximum time to wait for each service to become healthy again
+        #[arg(long, value_parser = utils::parse_duration)]
+        timeout: Option<Duration>,
+    },
+    
+    /// Recreate one service with a fresh container and an empty volume
+    Reset {
+        /// Service to reset (e.g. faucet)
+        #[arg(value_parser = ServiceNameParser)]
+        service: String,
+        
+        /// Allow resetting zebra, which discards the chain
+        #[arg(long)]
+        force: bool,
+        
+        /// Take over the project's lock from a crashed or stuck zecdev run
+        #[arg(long)]
+        force_unlock: bool,
+        
+        /// Maximum time to wait for the service to become healthy again
+        #[arg(long, value_parser = utils::parse_duration)]
+        timeout: Option<Duration>,
+    },
+    
+    /// Run a command inside a service container
+    Exec {
+        /// Service to run in (e.g. zebra)
+        #[arg(value_parser = ServiceNameParser)]
+        service: String,
+        
+        /// Keep stdin open and attach a TTY (use -it for a shell)
+        #[arg(short, long)]
+        interactive: bool,
+        
+        /// Allocate a TTY (implied by --interactive)
+        #[arg(short, long)]
+        tty: bool,
+        
+        /// Command to run (default: /bin/sh)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+    
+    /// Open an interactive shell (bash, else sh) in a service container
+    Shell {
+        /// Service to open the shell in
+        #[arg(default_value = "zebra", value_parser = ServiceNameParser)]
+        service: String,
+    },
+    
+    /// Check an address is well-formed for the devnet's network and print its kind
+    ValidateAddress {
+        /// Transparent, Sapling or unified address
+        address: String,
+        
+        /// Network to check against (default: the one `up` started, else zeckit.toml)
+        #[arg(short, long, value_enum)]
+        network: Option<Network>,
+    },
+    
+    /// Query the light-client backend
+    Backend {
+        #[command(subcommand)]
+        command: BackendCommands,
+    },
+    
+    /// Interact with the bundled faucet
+    Faucet {
+        #[command(subcommand)]
+        command: FaucetCommands,
+    },
+    
+    /// Manage the devnet's test wallet
+    Wallet {
+        #[command(subcommand)]
+        command: WalletCommands,
+    },
+    
+    /// Mine blocks on a regtest devnet
+    Mine {
+        /// Number of blocks to mine
+        #[arg(default_value_t = 1)]
+        blocks: u64,
+        
+        /// Address to receive the coinbase (default: the faucet's address)
+        #[arg(short, long)]
+        address: Option<String>,
+    },
+    
+    /// Invalidate recent blocks and mine a replacement branch on a regtest devnet
+    Reorg {
+        /// Invalidate the block DEPTH below the tip, then mine DEPTH + 1 new blocks
+        #[arg(long, default_value_t = 1)]
+        depth: u64,
+        
+        /// Address to receive the new coinbases (default: the faucet's address)
+        #[arg(short, long)]
+        address: Option<String>,
+    },
+    
+    /// Print Zebra's (and lightwalletd's) Prometheus metrics, labelled by component
+    Metrics {
+        /// Keep serving the merged metrics at http://ADDR/metrics for Prometheus to scrape
+        #[arg(long, value_name = "ADDR")]
+        serve: Option<String>,
+    },
+    
+    /// Block until Zebra reaches a block height, or a TCP port accepts connections
+    Wait {
+        /// Block height to wait for
+        #[arg(required_unless_present = "port")]
+        height: Option<u64>,
+        
+        /// Wait for a TCP port to accept connections instead of a block height
+        #[arg(long, conflicts_with = "height")]
+        port: Option<u16>,
+        
+        /// Host to probe with --port [default: 127.0.0.1]
+        #[arg(long, requires = "port", conflicts_with = "height")]
+        host: Option<String>,
+        
+        /// Give up after this long (e.g. 90s, 10m)
+        #[arg(long, default_value = "5m", value_parser = utils::parse_duration)]
+        timeout: Duration,
+    },
+    
+    /// Call a Zebra JSON-RPC method
+    Rpc {
+        /// RPC method name (e.g. getblockchaininfo)
+        method: String,
+        
+        /// Positional parameters; JSON values are passed through, anything else as a string
+        params: Vec<String>,
+        
+        /// Params as one JSON array, sent verbatim (e.g. '[{"addresses": ["tm..."]}]')
+        #[arg(long, value_name = "JSON", conflicts_with = "params")]
+        raw: Option<String>,
+    },
+    
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    
+    /// Show service logs
+    Logs {
+        /// Services to show (default: all running services)
+        #[arg(value_parser = ServiceNameParser)]
+        services: Vec<String>,
+        
+        /// Follow log output until Ctrl-C
+        #[arg(short, long)]
+        follow: bool,
+        
+        /// Number of lines to show from the end of the logs
+        #[arg(short = 'n', long)]
+        tail: Option<usize>,
+        
+        /// Follow the logs until a line matches PATTERN, then exit 0
+        #[arg(long, value_name = "PATTERN")]
+        grep: Option<Regex>,
+        
+        /// Exit non-zero if a line matches PATTERN before --grep does
+        #[arg(long, value_name = "PATTERN", requires = "grep")]
+        fail_on: Option<Regex>,
+        
+        /// Exit non-zero if nothing matches --grep within this long
+        #[arg(long, value_name = "DURATION", value_parser = utils::parse_duration, requires = "grep")]
+        timeout: Option<Duration>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BackendCommands {
+    /// Show what the backend reports through GetLightdInfo
+    Info {
+        /// Backend to ask
+        #[arg(short, long, value_enum, default_value = "lwd")]
+        backend: Backend,
+        
+        /// Print the reply as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum FaucetCommands {
+    /// Send test funds to an address
+    Fund {
+        /// Recipient t-address, z-address or unified address
+        /// (default: the address from `zecdev wallet new`)
+        address: Option<String>,
+        
+        /// Amount of ZEC to send (default: the faucet's `faucet_default_amount`)
+        #[arg(short, long)]
+        amount: Option<f64>,
+        
+        /// Block until the transfer has this many confirmations
+        #[arg(long, value_name = "N")]
+        wait_confirmations: Option<u64>,
+        
+        /// On regtest, mine the blocks the confirmations need
+        #[arg(long, requires = "wait_confirmations")]
+        auto_mine: bool,
+        
+        /// Give up waiting for confirmations after this long (e.g. 90s, 10m)
+        #[arg(long, default_value = "5m", value_parser = utils::parse_duration, requires = "wait_confirmations")]
+        timeout: Duration,
+    },
+    
+    /// Send funds to every `address amount` line of a file
+    FundBatch {
+        /// File with one `address amount` pair (amount in ZEC) per line; `#` starts a comment
+        file: PathBuf,
+        
+        /// Blocks each transfer must be buried under before returning (0 to not wait)
+        #[arg(long, visible_alias = "wait-confirmations", default_value_t = 1)]
+        confirmations: u64,
+        
+        /// On regtest, mine the blocks the confirmations need
+        #[arg(long)]
+        auto_mine: bool,
+        
+        /// Give up waiting for confirmations after this long (e.g. 90s, 10m)
+        #[arg(long, default_value = "5m", value_parser = utils::parse_duration)]
+        timeout: Duration,
+    },
+    
+    /// Show the faucet's balance and how much it has handed out
+    Stats {
+        /// Print machine-readable JSON (amounts in zatoshis)
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum WalletCommands {
+    /// Generate a fresh address and make it the default for `faucet fund`
+    New {
+        /// Kind of address to generate
+        #[arg(long = "type", value_enum, default_value_t = AddressKind::Transparent)]
+        kind: AddressKind,
+    },
 }
 
 #[tokio::main]
 async fn main() {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
     
-    let result = match cli.command {
-        Commands::Up { backend, fresh } => {
-            commands::up::execute(backend, fresh).await
-        }
-        Commands::Down { purge } => {
-            commands::down::execute(purge).await
-        }
-        Commands::Status => {
-            commands::status::execute().await
-        }
-        Commands::Test => {
-            commands::test::execute().await
+    // Other commands would act on the devnet as if their docker calls had run
+    if cli.dry_run && !matches!(cli.command, Commands::Up { .. } | Commands::Down { .. } | Commands::Clean { .. }) {
+        Cli::command()
+            .error(ErrorKind::ArgumentConflict, "--dry-run is only supported by up, down and clean")
+            .exit();
+    }
+    
+    if utils::color_disabled(cli.no_color, std::env::var_os("NO_COLOR").as_deref()) {
+        utils::disable_color();
+    }
+    
+    match &cli.command {
+        Commands::Up { summary_json, .. } => commands::up::verbosity(cli.quiet, cli.verbose, summary_json.as_deref()),
+        _ => utils::Verbosity::from_flags(cli.quiet, cli.verbose),
+    }
+    .install();
+    
+    // Compose runs from the project root, so resolve against the caller's cwd now
+    ComposeOptions {
+        file: cli.file.map(|f| std::path::absolute(&f).unwrap_or(f)),
+        overrides: cli
+            .compose_overrides
+            .into_iter()
+            .map(|f| std::path::absolute(&f).unwrap_or(f))
+            .collect(),
+        project_name: cli.project_name,
+        data_dir: cli.data_dir.map(|d| std::path::absolute(&d).unwrap_or(d)),
+        dry_run: cli.dry_run,
+    }
+    .install();
+    
+    RpcAuth::from_flags(cli.rpc_user, cli.rpc_password, cli.rpc_cookie).install();
+    BackendTls::from_flags(cli.backend_tls, cli.backend_ca).install();
+    
+    if let Some(path) = &cli.log_file {
+        if let Err(e) = logging::init(path) {
+            let e = ZecDevError::Config(format!("could not open log file {}: {}", path.display(), e));
+            utils::print_error(&e);
+            process::exit(1);
         }
-    };
+    }
+    
+    let span = tracing::info_span!("command", subcommand = matches.subcommand_name().unwrap_or_default());
+    let result = run(cli.command).instrument(span.clone()).await;
     
     if let Err(e) = result {
-        eprintln!("{} {}", "Error:".red().bold(), e);
+        span.in_scope(|| tracing::error!(error = %e, "command failed"));
+        utils::print_error(&e);
         process::exit(1);
     }
+}
+
+async fn run(command: Commands) -> Result<()> {
+    match command {
+        Commands::Up {
+            backend,
+            network,
+            force,
+            fresh,
+            force_recreate,
+            pull,
+            quiet_pull,
+            build,
+            timeout,
+            startup_timeout,
+            sync_stall_timeout,
+            max_attempts,
+            require_faucet_funds,
+            log_level,
+            memory_limit,
+            cpu_limit,
+            mounts,
+            activation_height,
+            peers,
+            cleanup_on_interrupt,
+            no_ordered_start,
+            env_out,
+            follow_until_healthy,
+            attach,
+            restart_on_failure,
+            watch,
+            profiles,
+            optional,
+            faucet_amount,
+            faucet_cooldown,
+            create_wallet,
+            fund,
+            summary_json,
+        } => {
+            let options = commands::up::UpOptions {
+                fresh,
+                force_recreate,
+                force,
+                pull,
+                quiet_pull,
+                build,
+                timeout,
+                startup_timeout,
+                sync_stall_timeout,
+                max_attempts,
+                require_faucet_funds,
+                log_level,
+                limits: ResourceLimits {
+                    memory: memory_limit,
+                    cpus: cpu_limit,
+                },
+                mounts,
+                activation_heights: activation_height,
+                peers,
+                cleanup_on_interrupt,
+                ordered_start: !no_ordered_start,
+                env_out,
+                follow_until_healthy,
+                attach,
+                restart_on_failure,
+                watch,
+                profiles,
+                optional,
+                faucet_amount,
+                faucet_cooldown,
+                create_wallet,
+                fund,
+                summary_json,
+            };
+            commands::up::execute(backend, network, options).await
+        }
+        Commands::Down { volumes, force } => {
+            commands::down::execute(volumes, force).await
+        }
+        Commands::Stop { services } => {
+            commands::stop::execute(services).await
+        }
+        Commands::Start { services, timeout } => {
+            commands::start::execute(services, timeout).await
+        }
+        Commands::Clean { force } => {
+            commands::clean::execute(force).await
+        }
+        Commands::Status { json, watch } => {
+            commands::status::execute(json, watch).await
+        }
+        Commands::Ps => {
+            commands::ps::execute().await
+        }
+        Commands::Top { watch } => {
+            commands::top::execute(watch).await
+        }
+        Commands::Info { service } => {
+            commands::info::execute(service).await
+        }
+        Commands::Snapshot { name } => {
+            commands::snapshot::snapshot(name).await
+        }
+        Commands::Restore { name, force } => {
+            commands::snapshot::restore(name, force).await
+        }
+        Commands::Export { bundle, with_snapshot, include_secrets } => {
+            commands::bundle::export(bundle, with_snapshot, include_secrets).await
+        }
+        Commands::Import { bundle, force } => {
+            commands::bundle::import(bundle, force).await
+        }
+        Commands::Test { backend } => {
+            commands::test::execute(backend).await
+        }
+        Commands::Doctor => {
+            commands::doctor::execute().await
+        }
+        Commands::Env { backend, out } => {
+            commands::env::execute(backend, out).await
+        }
+        Commands::ConnectConfig { format, backend } => {
+            commands::connect_config::execute(format, backend).await
+        }
+        Commands::Pull { backend, quiet_pull } => {
+            commands::pull::execute(backend, quiet_pull).await
+        }
+        Commands::Restart { services, timeout } => {
+            commands::restart::execute(services, timeout).await
+        }
+        Commands::Reset { service, force, force_unlock, timeout } => {
+            commands::reset::execute(service, force, force_unlock, timeout).await
+        }
+        Commands::Exec { service, interactive, tty, command } => {
+            commands::exec::execute(service, command, interactive || tty).await
+        }
+        Commands::Shell { service } => {
+            commands::shell::execute(service).await
+        }
+        Commands::ValidateAddress { address, network } => {
+            commands::validate_address::execute(address, network).await
+        }
+        Commands::Backend { command } => match command {
+            BackendCommands::Info { backend, json } => {
+                commands::backend::info(backend, json).await
+            }
+        },
+        Commands::Faucet { command } => match command {
+            FaucetCommands::Fund { address, amount, wait_confirmations, auto_mine, timeout } => {
+                let confirm = wait_confirmations.map(|confirmations| commands::faucet::ConfirmOptions {
+                    confirmations,
+                    timeout,
+                    auto_mine,
+                });
+                commands::faucet::fund(address, amount, confirm).await
+            }
+            FaucetCommands::FundBatch { file, confirmations, auto_mine, timeout } => {
+                let confirm = commands::faucet::ConfirmOptions { confirmations, timeout, auto_mine };
+                commands::faucet::fund_batch(&file, confirm).await
+            }
+            FaucetCommands::Stats { json } => {
+                commands::faucet::stats(json).await
+            }
+        },
+        Commands::Wallet { command } => match command {
+            WalletCommands::New { kind } => {
+                commands::wallet::new(kind).await
+            }
+        },
+        Commands::Mine { blocks, address } => {
+            commands::mine::execute(blocks, address).await
+        }
+        Commands::Reorg { depth, address } => {
+            commands::reorg::execute(depth, address).await
+        }
+        Commands::Metrics { serve } => {
+            commands::metrics::execute(serve).await
+        }
+        Commands::Wait { height, port, host, timeout } => {
+            commands::wait::execute(height, port, host, timeout).await
+        }
+        Commands::Rpc { method, params, raw } => {
+            commands::rpc::execute(method, params, raw).await
+        }
+        Commands::Version { components } => {
+            commands::version::execute(components).await
+        }
+        Commands::Completions { shell } => {
+            commands::completions::execute(shell).await
+        }
+        Commands::Logs { services, follow, tail, grep, fail_on, timeout } => {
+            let watch = grep.map(|grep| commands::logs::LogWatch { grep, fail_on, timeout });
+            commands::logs::execute(services, follow, tail, watch).await
+        }
+    }
 }
\ No newline at end of file
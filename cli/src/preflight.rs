@@ -0,0 +1,72 @@
+use crate::docker::compose::DockerCompose;
+use crate::docker::runtime;
+use crate::error::{Result, ZecDevError};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::Command;
+
+/// Make sure every host port the devnet publishes is free.
+///
+/// Each entry pairs a port with the service that publishes it, so the error
+/// can say who needs it.
+pub fn check_ports(ports: &[(u16, &str)]) -> Result<()> {
+    for (port, service) in ports {
+        // Compose publishes on 127.0.0.1 only
+        if TcpListener::bind(("127.0.0.1", *port)).is_err() {
+            return Err(ZecDevError::PortInUse {
+                port: *port,
+                service: service.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Make sure the container runtime's storage has at least `min_bytes` free.
+///
+/// Zebra corrupts its state when the disk fills mid-sync, so this runs
+/// before anything writes chain state or images. When the free space can't
+/// be determined (Docker Desktop keeps volumes inside a VM), it passes.
+pub fn check_disk(min_bytes: u64) -> Result<()> {
+    match runtime::detect()?.storage_root() {
+        Some(root) => check_disk_at(&root, min_bytes),
+        None => Ok(()),
+    }
+}
+
+/// `check_disk` for where chain state is written: the `--data-dir`, if one
+/// was given, else the runtime's volume storage
+pub fn check_state_disk(compose: &DockerCompose, min_bytes: u64) -> Result<()> {
+    match compose.data_dir() {
+        Some(data_dir) => check_disk_at(data_dir.root(), min_bytes),
+        None => check_disk(min_bytes),
+    }
+}
+
+/// Make sure the filesystem holding `path` has at least `min_bytes` free
+pub fn check_disk_at(path: &Path, min_bytes: u64) -> Result<()> {
+    match free_disk_space(path) {
+        Some(available) if available < min_bytes => Err(ZecDevError::InsufficientDisk {
+            path: path.to_path_buf(),
+            required: min_bytes,
+            available,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Free space in bytes on the filesystem holding `path`, if it can be determined
+pub fn free_disk_space(path: &Path) -> Option<u64> {
+    // `df -Pk` prints POSIX-format output in 1K blocks on Linux and macOS
+    let output = Command::new("df").arg("-Pk").arg(path).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.lines().nth(1)?;
+    let available_kb: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+
+    Some(available_kb * 1024)
+}
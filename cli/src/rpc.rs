@@ -0,0 +1,360 @@
+use crate::error::{connection_error, Result, ZecDevError};
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tokio::time::Duration;
+
+static AUTH: OnceLock<RpcAuth> = OnceLock::new();
+
+/// Minimal JSON-RPC client for Zebra
+pub struct ZebraRpc {
+    client: Client,
+    url: String,
+    timeout: Duration,
+    auth: RpcAuth,
+}
+
+/// How to authenticate to Zebra's RPC endpoint.
+///
+/// Set once from `--rpc-user`/`--rpc-password`/`--rpc-cookie` in `main` and
+/// picked up by every `ZebraRpc::new()`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum RpcAuth {
+    /// The bundled devnet's Zebra accepts unauthenticated RPC
+    #[default]
+    None,
+    Basic { user: String, password: String },
+    /// Zebra's cookie file, re-read per request since Zebra rewrites it on restart
+    Cookie(PathBuf),
+}
+
+impl RpcAuth {
+    pub fn from_flags(user: Option<String>, password: Option<String>, cookie: Option<PathBuf>) -> Self {
+        match (user, cookie) {
+            (Some(user), _) => RpcAuth::Basic {
+                user,
+                password: password.unwrap_or_default(),
+            },
+            (None, Some(path)) => RpcAuth::Cookie(path),
+            (None, None) => RpcAuth::None,
+        }
+    }
+
+    /// Make this the auth used for the rest of the process
+    pub fn install(self) {
+        let _ = AUTH.set(self);
+    }
+
+    /// The installed auth, or none if nothing was installed
+    pub fn current() -> RpcAuth {
+        AUTH.get().cloned().unwrap_or_default()
+    }
+
+    /// One-line description for connection info, if auth is in use
+    pub fn describe(&self) -> Option<String> {
+        match self {
+            RpcAuth::None => None,
+            RpcAuth::Basic { user, .. } => Some(format!("user {}", user)),
+            RpcAuth::Cookie(path) => Some(format!("cookie {}", path.display())),
+        }
+    }
+
+    /// Username and password to send as HTTP basic auth
+    fn credentials(&self) -> Result<Option<(String, String)>> {
+        match self {
+            RpcAuth::None => Ok(None),
+            RpcAuth::Basic { user, password } => Ok(Some((user.clone(), password.clone()))),
+            RpcAuth::Cookie(path) => {
+                let contents = std::fs::read_to_string(path).map_err(|e| {
+                    ZecDevError::Config(format!("could not read RPC cookie {}: {}", path.display(), e))
+                })?;
+                parse_cookie(&contents).map(Some).ok_or_else(|| {
+                    ZecDevError::Config(format!("RPC cookie {} is not in user:password form", path.display()))
+                })
+            }
+        }
+    }
+}
+
+/// Split a cookie file's `__cookie__:<secret>` line
+fn parse_cookie(contents: &str) -> Option<(String, String)> {
+    let (user, password) = contents.trim().split_once(':')?;
+    Some((user.to_string(), password.to_string()))
+}
+
+/// Verification progress at which a syncing node counts as caught up
+const SYNCED_PROGRESS: f64 = 0.9999;
+
+/// Subset of `getblockchaininfo` that ZecKit cares about
+#[derive(Debug, Deserialize)]
+pub struct BlockchainInfo {
+    pub chain: String,
+    pub blocks: u64,
+    #[serde(default)]
+    pub headers: Option<u64>,
+    #[serde(default, rename = "verificationprogress")]
+    pub verification_progress: Option<f64>,
+}
+
+impl BlockchainInfo {
+    /// How far sync has got, from 0.0 to 1.0; `None` when there is nothing to sync
+    pub fn sync_progress(&self) -> Option<f64> {
+        if self.chain == "regtest" {
+            return None;
+        }
+        self.verification_progress
+            .or_else(|| {
+                self.headers
+                    .filter(|headers| *headers > 0)
+                    .map(|headers| self.blocks as f64 / headers as f64)
+            })
+            .map(|progress| progress.clamp(0.0, 1.0))
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.sync_progress().is_none_or(|progress| progress >= SYNCED_PROGRESS)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    id: Value,
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    error: Option<RpcErrorObject>,
+}
+
+impl RpcResponse {
+    fn into_result(self) -> Result<Value> {
+        match self.error {
+            Some(error) => Err(ZecDevError::Rpc {
+                code: error.code,
+                message: error.message,
+            }),
+            None => Ok(self.result),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcErrorObject {
+    code: i64,
+    message: String,
+}
+
+impl ZebraRpc {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(),
+            url: url.into(),
+            timeout: Duration::from_secs(30),
+            auth: RpcAuth::current(),
+        }
+    }
+
+    /// Per-request timeout (default 30s)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Issue a raw JSON-RPC call and return its `result`
+    pub async fn call(&self, method: &str, params: Vec<Value>) -> Result<Value> {
+        let body: RpcResponse = self
+            .post(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": "zecdev",
+                "method": method,
+                "params": params
+            }))
+            .await?;
+
+        body.into_result()
+    }
+
+    /// Send several calls as one JSON-RPC batch.
+    ///
+    /// Results come back in the order of `calls`; a call that fails only
+    /// fails its own slot.
+    pub async fn batch(&self, calls: Vec<(&str, Vec<Value>)>) -> Result<Vec<Result<Value>>> {
+        let count = calls.len();
+        let requests: Vec<Value> = calls
+            .into_iter()
+            .enumerate()
+            .map(|(id, (method, params))| {
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "method": method,
+                    "params": params
+                })
+            })
+            .collect();
+
+        let responses: Vec<RpcResponse> = self.post(Value::Array(requests)).await?;
+        Ok(order_batch(responses, count))
+    }
+
+    /// POST a JSON-RPC document and decode the reply
+    async fn post<T: DeserializeOwned>(&self, body: Value) -> Result<T> {
+        let mut request = self.client.post(&self.url).json(&body).timeout(self.timeout);
+        if let Some((user, password)) = self.auth.credentials()? {
+            request = request.basic_auth(user, Some(password));
+        }
+        let resp = request.send().await.map_err(connection_error)?;
+
+        let status = resp.status();
+        if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+            let advice = match self.auth {
+                RpcAuth::None => "Zebra requires RPC auth; pass --rpc-user/--rpc-password or --rpc-cookie.",
+                _ => "Check --rpc-user/--rpc-password, or that --rpc-cookie points at Zebra's current cookie file.",
+            };
+            return Err(ZecDevError::Config(format!("Zebra rejected the RPC request (HTTP {})", status))
+                .with_hint(advice));
+        }
+
+        // Zebra reports RPC failures in the body, sometimes with a non-2xx status
+        resp.json().await.map_err(|_| {
+            ZecDevError::HealthCheck(format!("Zebra returned HTTP {} without a JSON-RPC body", status))
+        })
+    }
+
+    pub async fn get_block_count(&self) -> Result<u64> {
+        let result = self.call("getblockcount", vec![]).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn get_blockchain_info(&self) -> Result<BlockchainInfo> {
+        let result = self.call("getblockchaininfo", vec![]).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// The node's version string from `getinfo`, e.g. `Zebra:1.9.0`
+    pub async fn get_subversion(&self) -> Result<String> {
+        let result = self.call("getinfo", vec![]).await?;
+        result
+            .get("subversion")
+            .and_then(Value::as_str)
+            .map(|v| v.trim_matches('/').to_string())
+            .ok_or_else(|| ZecDevError::HealthCheck("getinfo did not report a subversion".into()))
+    }
+
+    pub async fn get_best_block_hash(&self) -> Result<String> {
+        let result = self.call("getbestblockhash", vec![]).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub async fn get_block_hash(&self, height: u64) -> Result<String> {
+        let result = self.call("getblockhash", vec![height.into()]).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Mark `hash` and its descendants invalid, rolling the tip back to its parent
+    pub async fn invalidate_block(&self, hash: &str) -> Result<()> {
+        self.call("invalidateblock", vec![hash.into()]).await?;
+        Ok(())
+    }
+
+    /// Mine `blocks` blocks paying the coinbase to `address` (regtest only)
+    pub async fn generate_to_address(&self, blocks: u64, address: &str) -> Result<Vec<String>> {
+        let result = self
+            .call("generatetoaddress", vec![blocks.into(), address.into()])
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Height of the block that mined `txid`, or `None` while it is in the mempool
+    pub async fn transaction_height(&self, txid: &str) -> Result<Option<u64>> {
+        let result = self.call("getrawtransaction", vec![txid.into(), 1.into()]).await?;
+        // Mempool transactions have no height, or -1 depending on the Zebra version
+        Ok(result.get("height").and_then(Value::as_u64))
+    }
+
+    /// Fail unless Zebra is running a regtest chain
+    pub async fn require_regtest(&self) -> Result<BlockchainInfo> {
+        let info = self.get_blockchain_info().await?;
+        if info.chain != "regtest" {
+            return Err(ZecDevError::NotRegtest(info.chain));
+        }
+        Ok(info)
+    }
+}
+
+/// Servers may answer a batch in any order; put results back by request id
+fn order_batch(responses: Vec<RpcResponse>, count: usize) -> Vec<Result<Value>> {
+    let mut slots: Vec<Option<Result<Value>>> = (0..count).map(|_| None).collect();
+
+    for response in responses {
+        let slot = response
+            .id
+            .as_u64()
+            .and_then(|id| slots.get_mut(id as usize));
+        if let Some(slot) = slot {
+            *slot = Some(response.into_result());
+        }
+    }
+
+    slots
+        .into_iter()
+        .enumerate()
+        .map(|(id, slot)| {
+            slot.unwrap_or_else(|| {
+                Err(ZecDevError::HealthCheck(format!("Zebra sent no response for batch call {}", id)))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regtest_never_waits_for_sync() {
+        let regtest: BlockchainInfo =
+            serde_json::from_str(r#"{"chain": "regtest", "blocks": 0, "verificationprogress": 0.0}"#).unwrap();
+        assert!(regtest.is_synced());
+
+        let testnet: BlockchainInfo =
+            serde_json::from_str(r#"{"chain": "test", "blocks": 500, "headers": 2000}"#).unwrap();
+        assert_eq!(testnet.sync_progress(), Some(0.25));
+        assert!(!testnet.is_synced());
+    }
+
+    #[test]
+    fn no_flags_means_no_auth() {
+        let auth = RpcAuth::from_flags(None, None, None);
+        assert_eq!(auth, RpcAuth::None);
+        assert_eq!(auth.credentials().unwrap(), None);
+    }
+
+    #[test]
+    fn cookie_is_split_at_the_first_colon() {
+        assert_eq!(
+            parse_cookie("__cookie__:abc:def\n"),
+            Some(("__cookie__".into(), "abc:def".into()))
+        );
+        assert_eq!(parse_cookie("garbage"), None);
+    }
+
+    #[test]
+    fn batch_results_keep_request_order_around_errors() {
+        let json = r#"[
+            {"jsonrpc": "2.0", "id": 2, "result": "c"},
+            {"jsonrpc": "2.0", "id": 0, "result": "a"},
+            {"jsonrpc": "2.0", "id": 1, "error": {"code": -8, "message": "Block height out of range"}}
+        ]"#;
+        let responses: Vec<RpcResponse> = serde_json::from_str(json).unwrap();
+        let results = order_batch(responses, 4);
+
+        assert_eq!(results[0].as_ref().unwrap(), "a");
+        assert!(matches!(results[1], Err(ZecDevError::Rpc { code: -8, .. })));
+        assert_eq!(results[2].as_ref().unwrap(), "c");
+        assert!(results[3].is_err());
+    }
+}
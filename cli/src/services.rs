@@ -0,0 +1,203 @@
+use crate::commands::up::Backend;
+use crate::config::file::ZEBRA_P2P_PORT;
+use crate::config::settings::Settings;
+use crate::config::Config;
+use crate::docker::compose::ZEBRA_VOLUME;
+use clap::ValueEnum;
+
+/// A service ZecKit manages, and what the CLI needs to know about it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Service {
+    /// Name in docker-compose.yml
+    pub name: &'static str,
+    /// Name in status output
+    pub label: &'static str,
+    /// Backend name (`lwd`, `zaino`) for light-client backends
+    pub backend: Option<&'static str>,
+    /// Talks to Zebra's RPC, so it can only become ready after Zebra
+    pub requires_zebra: bool,
+    /// Where it keeps its state, for `zecdev shell`
+    pub workdir: Option<&'static str>,
+    /// Named volume holding only this service's state, removed by `zecdev reset`
+    pub volume: Option<&'static str>,
+    /// Where the volume is mounted in the container, and `--data-dir` binds instead
+    pub data_path: Option<&'static str>,
+}
+
+pub const ZEBRA: Service = Service {
+    name: "zebra",
+    label: "Zebra",
+    backend: None,
+    requires_zebra: false,
+    workdir: Some("/var/zebra/state"),
+    volume: Some(ZEBRA_VOLUME),
+    data_path: Some("/var/zebra/state"),
+};
+
+pub const FAUCET: Service = Service {
+    name: "faucet",
+    label: "Faucet",
+    backend: None,
+    requires_zebra: true,
+    workdir: Some("/app"),
+    volume: Some("zecdev-faucet-data"),
+    data_path: Some("/var/faucet"),
+};
+
+pub const LIGHTWALLETD: Service = Service {
+    name: "lightwalletd",
+    label: "LightwalletD",
+    backend: Some("lwd"),
+    requires_zebra: true,
+    workdir: Some("/var/lib/lightwalletd"),
+    volume: Some("zecdev-lightwalletd-data"),
+    data_path: Some("/var/lib/lightwalletd"),
+};
+
+pub const ZAINO: Service = Service {
+    name: "zaino",
+    label: "Zaino",
+    backend: Some("zaino"),
+    requires_zebra: true,
+    workdir: Some("/var/lib/zaino"),
+    volume: Some("zecdev-zaino-data"),
+    data_path: Some("/var/lib/zaino"),
+};
+
+impl Service {
+    /// Host port of the service's main endpoint: Zebra's RPC, the faucet API, a backend's gRPC
+    pub fn rpc_port(&self, config: &Config) -> u16 {
+        match self.name {
+            "zebra" => config.zebra_rpc_port,
+            "faucet" => config.faucet_port,
+            "lightwalletd" => config.backend_port,
+            _ => config.zaino_port,
+        }
+    }
+
+    /// Every host port the service publishes, main endpoint first
+    pub fn ports(&self, config: &Config) -> Vec<u16> {
+        let mut ports = vec![self.rpc_port(config)];
+        match self.name {
+            "zebra" => ports.extend([ZEBRA_P2P_PORT, config.zebra_metrics_port]),
+            "lightwalletd" => ports.push(config.backend_metrics_port),
+            _ => {}
+        }
+        ports
+    }
+
+    /// Host-side URL of the main endpoint
+    pub fn url<'a>(&self, settings: &'a Settings) -> &'a str {
+        match (self.name, self.backend) {
+            ("zebra", _) => &settings.zebra_rpc_url,
+            (_, Some(backend)) => settings.backend_url_for(backend),
+            _ => &settings.faucet_api_url,
+        }
+    }
+}
+
+/// The services ZecKit knows and which of them make up a devnet.
+///
+/// A new backend needs a `Service` above, an entry in `ALL` and a
+/// `for_backend` arm.
+pub struct ServiceSet;
+
+impl ServiceSet {
+    /// Every service ZecKit knows how to manage
+    pub const ALL: [Service; 4] = [ZEBRA, FAUCET, LIGHTWALLETD, ZAINO];
+
+    /// Services every devnet runs, whatever its backend
+    const CORE: [Service; 2] = [ZEBRA, FAUCET];
+
+    /// Services that make up the devnet for `backend`
+    pub fn for_backend(backend: Backend) -> Vec<Service> {
+        let mut services = Self::CORE.to_vec();
+        match backend {
+            Backend::None => {}
+            Backend::Lwd => services.push(LIGHTWALLETD),
+            Backend::Zaino => services.push(ZAINO),
+        }
+        services
+    }
+
+    /// Services that make up the devnet for several backends, by backend name
+    pub fn for_backends(backends: &[&str]) -> Vec<Service> {
+        let mut services = Self::CORE.to_vec();
+        for backend in backends.iter().filter_map(|b| Backend::from_str(b, false).ok()) {
+            for service in Self::for_backend(backend) {
+                if !services.contains(&service) {
+                    services.push(service);
+                }
+            }
+        }
+        services
+    }
+
+    /// Whether every devnet needs this service, so it can never be optional
+    pub fn is_core(name: &str) -> bool {
+        Self::CORE.iter().any(|s| s.name == name)
+    }
+
+    /// Compose names of `for_backends(backends)`
+    pub fn names_for(backends: &[&str]) -> Vec<&'static str> {
+        Self::for_backends(backends).iter().map(|s| s.name).collect()
+    }
+
+    /// Compose names of every known service
+    pub fn names() -> Vec<&'static str> {
+        Self::ALL.iter().map(|s| s.name).collect()
+    }
+
+    /// The known service with this compose name
+    pub fn get(name: &str) -> Option<Service> {
+        Self::ALL.into_iter().find(|s| s.name == name)
+    }
+
+    /// The service that runs the backend named `backend` (`lwd`, `zaino`)
+    pub fn backend(backend: &str) -> Option<Service> {
+        Self::ALL.into_iter().find(|s| s.backend == Some(backend))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(services: &[Service]) -> Vec<&'static str> {
+        services.iter().map(|s| s.name).collect()
+    }
+
+    #[test]
+    fn each_backend_yields_its_services() {
+        assert_eq!(names(&ServiceSet::for_backend(Backend::None)), vec!["zebra", "faucet"]);
+        assert_eq!(names(&ServiceSet::for_backend(Backend::Lwd)), vec!["zebra", "faucet", "lightwalletd"]);
+        assert_eq!(names(&ServiceSet::for_backend(Backend::Zaino)), vec!["zebra", "faucet", "zaino"]);
+        assert_eq!(
+            ServiceSet::names_for(&["lwd", "zaino"]),
+            vec!["zebra", "faucet", "lightwalletd", "zaino"]
+        );
+        assert_eq!(ServiceSet::backend("lwd"), Some(LIGHTWALLETD));
+        assert_eq!(ServiceSet::get("zaino").and_then(|s| s.backend), Some("zaino"));
+        assert!(ServiceSet::get("postgres").is_none());
+    }
+
+    #[test]
+    fn services_publish_the_configured_ports() {
+        let config = Config::default();
+        assert_eq!(ZEBRA.rpc_port(&config), 8232);
+        assert_eq!(ZEBRA.ports(&config), vec![8232, ZEBRA_P2P_PORT, config.zebra_metrics_port]);
+        assert_eq!(FAUCET.ports(&config), vec![8080]);
+        assert_eq!(LIGHTWALLETD.ports(&config), vec![config.backend_port, config.backend_metrics_port]);
+        assert_eq!(ZAINO.ports(&config), vec![config.zaino_port]);
+
+        let config: Config = toml::from_str("zebra_rpc_port = 18232\nzaino_port = 19067").unwrap();
+        assert_eq!(ZEBRA.rpc_port(&config), 18232);
+        assert_eq!(ZAINO.rpc_port(&config), 19067);
+    }
+
+    #[test]
+    fn only_zebra_starts_without_zebra() {
+        let independent: Vec<_> = ServiceSet::ALL.iter().filter(|s| !s.requires_zebra).collect();
+        assert_eq!(independent, vec![&ZEBRA]);
+    }
+}
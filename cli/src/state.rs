@@ -0,0 +1,119 @@
+use crate::config::settings::Settings;
+use crate::config::{Config, Network};
+use crate::docker::compose::Layers;
+use crate::error::Result;
+use crate::utils::project_state_dir;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What `up` started, kept in `~/.zeckit/<project>/state.json` until `down`.
+///
+/// Later commands read it so they target the same backends and ports
+/// without being told again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevnetState {
+    /// Light-client backends (`lwd`, `zaino`)
+    pub backends: Vec<String>,
+    pub network: Network,
+    /// Endpoints as they were when the devnet started
+    pub settings: Settings,
+    /// Unix timestamp (seconds)
+    pub started_at: u64,
+    /// Overrides and environment `up` layered, e.g. from `--mount` and `--peers`
+    #[serde(default)]
+    pub layers: Layers,
+}
+
+impl DevnetState {
+    pub fn new(backends: &[&str], network: Network, settings: &Settings, layers: &Layers) -> Self {
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            backends: backends.iter().map(|b| b.to_string()).collect(),
+            network,
+            settings: settings.clone(),
+            started_at,
+            layers: layers.clone(),
+        }
+    }
+
+    /// Where the state for compose project `project` is stored
+    pub fn path(project: &str) -> Result<PathBuf> {
+        Ok(project_state_dir(project)?.join("state.json"))
+    }
+
+    /// The project's state, if `up` has run since the last `down`
+    pub fn load(project: &str) -> Result<Option<Self>> {
+        let path = Self::path(project)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&contents)?))
+    }
+
+    pub fn save(&self, project: &str) -> Result<()> {
+        let path = Self::path(project)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Forget the project's state; nothing to do if there is none
+    pub fn clear(project: &str) -> Result<()> {
+        match std::fs::remove_file(Self::path(project)?) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Endpoints of the project's devnet: as `up` recorded them, else from zeckit.toml
+    pub fn settings_for(project: &str) -> Result<Settings> {
+        match Self::load(project)? {
+            Some(state) => Ok(state.settings),
+            None => Ok(Settings::from_config(&Config::load()?)),
+        }
+    }
+
+    /// Network of the project's devnet: as `up` recorded it, else from zeckit.toml
+    pub fn network_for(project: &str) -> Result<Network> {
+        match Self::load(project)? {
+            Some(state) => Ok(state.network),
+            None => Ok(Config::load()?.resolve_network(None)),
+        }
+    }
+
+    /// Overrides and environment of the project's devnet: as `up` layered them, else none
+    pub fn layers_for(project: &str) -> Result<Layers> {
+        Ok(Self::load(project)?.map(|state| state.layers).unwrap_or_default())
+    }
+
+    /// Backend names in the form `Settings` and `HealthChecker` take
+    pub fn backend_names(&self) -> Vec<&str> {
+        self.backends.iter().map(|b| b.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_file_round_trips() {
+        let state = DevnetState::new(&["zaino"], Network::Testnet, &Settings::default(), &Layers::default());
+        let json = serde_json::to_string(&state).unwrap();
+        let loaded: DevnetState = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.backend_names(), vec!["zaino"]);
+        assert_eq!(loaded.network, Network::Testnet);
+        assert_eq!(loaded.settings.zaino_url, "http://127.0.0.1:9068");
+        assert!(json.contains(r#""network":"testnet""#));
+    }
+}
@@ -1,32 +1,223 @@
+use crate::error::ZecDevError;
+use colored::*;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle, WeakProgressBar};
+use std::ffi::OsStr;
+use std::path::PathBuf;
 use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tracing::span::EnteredSpan;
 
-/// Check if Docker is installed and running
-pub fn check_docker() -> bool {
-    Command::new("docker")
-        .arg("--version")
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+static VERBOSITY: OnceLock<Verbosity> = OnceLock::new();
+
+/// Spinners handed out by `spinner()`, so logged commands can print around them
+static SPINNERS: Mutex<Vec<WeakProgressBar>> = Mutex::new(Vec::new());
+
+/// How much output commands produce, from `--quiet` / `--verbose`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Errors and final results only
+    Quiet,
+    #[default]
+    Normal,
+    /// Also echo every docker command before running it
+    Verbose,
+}
+
+impl Verbosity {
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        if quiet {
+            Verbosity::Quiet
+        } else if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+
+    /// Make this the verbosity for the rest of the process
+    pub fn install(self) {
+        let _ = VERBOSITY.set(self);
+    }
+}
+
+pub fn is_quiet() -> bool {
+    VERBOSITY.get() == Some(&Verbosity::Quiet)
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSITY.get() == Some(&Verbosity::Verbose)
+}
+
+/// Record `cmd` in the log file, and with `--verbose` print it as a shell
+/// line that reproduces it by hand.
+///
+/// Hold the returned span while the command runs so its duration is logged.
+pub fn log_command(cmd: &Command) -> EnteredSpan {
+    let line = command_line(cmd);
+    let span = tracing::debug_span!("docker", command = %line).entered();
+
+    if is_verbose() {
+        let line = format!("{} {}", "$".dimmed(), line.dimmed());
+        match active_spinner() {
+            // Print above the spinner instead of through the middle of it
+            Some(pb) => pb.suspend(|| eprintln!("{}", line)),
+            None => eprintln!("{}", line),
+        }
+    }
+    span
+}
+
+/// `cmd` as a shell line, including its working directory and extra environment
+pub fn command_line(cmd: &Command) -> String {
+    let mut line = String::new();
+    if let Some(dir) = cmd.get_current_dir() {
+        line.push_str(&format!("cd {} && ", shell_quote(&dir.to_string_lossy())));
+    }
+    for (key, value) in cmd.get_envs() {
+        if let Some(value) = value {
+            line.push_str(&format!("{}={} ", key.to_string_lossy(), shell_quote(&value.to_string_lossy())));
+        }
+    }
+    line.push_str(&cmd.get_program().to_string_lossy());
+    for arg in cmd.get_args() {
+        line.push(' ');
+        line.push_str(&shell_quote(&arg.to_string_lossy()));
+    }
+    line
+}
+
+/// The most recent spinner that is still being drawn
+fn active_spinner() -> Option<ProgressBar> {
+    let mut spinners = SPINNERS.lock().ok()?;
+    spinners.retain(|weak| weak.upgrade().is_some_and(|pb| !pb.is_finished()));
+    spinners.last().and_then(|weak| weak.upgrade())
+}
+
+/// Quote `arg` for a POSIX shell when it contains anything unusual
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+%".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Directory holding docker-compose.yml (the parent when run from cli/)
+pub fn project_root() -> std::io::Result<PathBuf> {
+    let current_dir = std::env::current_dir()?;
+    if current_dir.ends_with("cli") {
+        if let Some(parent) = current_dir.parent() {
+            return Ok(parent.to_path_buf());
+        }
+    }
+    Ok(current_dir)
+}
+
+/// Per-user state directory (`~/.zeckit`)
+pub fn zeckit_home() -> std::io::Result<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".zeckit"))
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "could not determine home directory"))
+}
+
+/// Per-project state directory (`~/.zeckit/<project>`), holding its state,
+/// lock and generated compose overrides
+pub fn project_state_dir(project: &str) -> std::io::Result<PathBuf> {
+    Ok(zeckit_home()?.join(project))
+}
+
+/// Whether color should be turned off, per `--no-color` or https://no-color.org
+pub fn color_disabled(no_color_flag: bool, no_color_env: Option<&OsStr>) -> bool {
+    no_color_flag || no_color_env.is_some_and(|v| !v.is_empty())
 }
 
-/// Check if Docker Compose is available
-pub fn check_docker_compose() -> bool {
-    Command::new("docker")
-        .arg("compose")
-        .arg("version")
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+/// Turn off ANSI styling for both `colored` text and indicatif spinners
+pub fn disable_color() {
+    colored::control::set_override(false);
+    console::set_colors_enabled(false);
+    console::set_colors_enabled_stderr(false);
 }
 
-/// Print a formatted banner
+/// Print an error, plus its remediation hint if it has one
+pub fn print_error(e: &ZecDevError) {
+    eprintln!("{} {}", "Error:".red().bold(), e);
+    if let Some(hint) = e.hint() {
+        eprintln!("{} {}", "Hint:".yellow().bold(), hint.yellow());
+    }
+}
+
+/// Print a formatted banner (skipped with `--quiet`)
 pub fn print_banner(title: &str) {
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  {}", title);
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    if is_quiet() {
+        return;
+    }
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("{}", format!("  {}", title).cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
     println!();
 }
 
+/// Container for several spinners; draws nothing with `--quiet`
+pub fn progress_group() -> MultiProgress {
+    if is_quiet() {
+        MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+    } else {
+        MultiProgress::new()
+    }
+}
+
+/// Spinner used while waiting on services; hidden with `--quiet`
+pub fn spinner() -> ProgressBar {
+    let pb = if is_quiet() {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new_spinner()
+    };
+    pb.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.green} {msg}")
+            .unwrap()
+    );
+    if let Ok(mut spinners) = SPINNERS.lock() {
+        spinners.push(pb.downgrade());
+    }
+    pb
+}
+
+/// Print rows as left-aligned columns under a bold header
+pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            if i < widths.len() {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("  {}", format_row(headers.to_vec()).bold());
+    for row in rows {
+        println!("  {}", format_row(row.iter().map(|c| c.as_str()).collect()));
+    }
+}
+
 /// Format bytes for display
 pub fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -42,4 +233,66 @@ pub fn format_bytes(bytes: u64) -> String {
     } else {
         format!("{} B", bytes)
     }
-}
\ No newline at end of file
+}
+
+/// Parse a human duration such as `90`, `120s`, `500ms`, `5m` or `1h`.
+///
+/// A bare number is taken as seconds.
+pub fn parse_duration(input: &str) -> std::result::Result<Duration, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (value, unit) = input.split_at(split);
+
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration '{}'", input))?;
+
+    match unit {
+        "ms" => Ok(Duration::from_millis(value)),
+        "" | "s" => Ok(Duration::from_secs(value)),
+        "m" => Ok(Duration::from_secs(value * 60)),
+        "h" => Ok(Duration::from_secs(value * 3600)),
+        _ => Err(format!(
+            "invalid duration unit '{}' (expected ms, s, m or h)",
+            unit
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_wins_over_verbose() {
+        assert_eq!(Verbosity::from_flags(true, true), Verbosity::Quiet);
+        assert_eq!(Verbosity::from_flags(false, true), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(false, false), Verbosity::Normal);
+    }
+
+    #[test]
+    fn shell_quote_only_wraps_unusual_arguments() {
+        assert_eq!(shell_quote("--format"), "--format");
+        assert_eq!(shell_quote("{{.Names}}"), "'{{.Names}}'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn no_color_env_must_be_non_empty() {
+        assert!(!color_disabled(false, None));
+        assert!(!color_disabled(false, Some(OsStr::new(""))));
+        assert!(color_disabled(false, Some(OsStr::new("1"))));
+        assert!(color_disabled(true, None));
+    }
+
+    #[test]
+    fn disabled_color_emits_no_escape_sequences() {
+        disable_color();
+
+        let banner = format!("{} {}", "ZecKit".cyan().bold(), "✓ ready".green());
+        assert!(!banner.contains('\x1b'));
+        assert_eq!(banner, "ZecKit ✓ ready");
+    }
+}
@@ -0,0 +1,107 @@
+use crate::error::{Result, ZecDevError};
+use crate::rpc::{BlockchainInfo, ZebraRpc};
+use crate::utils::project_state_dir;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// JSON-RPC "method not found"
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Kind of address `zecdev wallet new` asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressKind {
+    Transparent,
+    Unified,
+}
+
+impl AddressKind {
+    /// RPC method and params that generate this kind of address
+    fn rpc_call(&self) -> (&'static str, Vec<serde_json::Value>) {
+        match self {
+            AddressKind::Transparent => ("getnewaddress", vec![]),
+            AddressKind::Unified => ("z_getnewaddress", vec!["unified".into()]),
+        }
+    }
+}
+
+/// The devnet's generated wallet, kept in `~/.zeckit/<project>/wallet.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Wallet {
+    pub address: String,
+    pub kind: AddressKind,
+    /// Chain the address was generated on, as reported by Zebra
+    pub chain: String,
+    /// Unix timestamp (seconds)
+    pub created_at: u64,
+}
+
+impl Wallet {
+    /// Ask the node's wallet RPC for a fresh address
+    pub async fn generate(rpc: &ZebraRpc, kind: AddressKind) -> Result<Self> {
+        let (method, params) = kind.rpc_call();
+        let mut results = rpc
+            .batch(vec![("getblockchaininfo", vec![]), (method, params)])
+            .await?
+            .into_iter();
+        
+        let info: BlockchainInfo = serde_json::from_value(results.next().expect("two calls")?)?;
+        let chain = info.chain;
+        let address = match results.next().expect("two calls") {
+            Ok(value) => serde_json::from_value(value)?,
+            Err(ZecDevError::Rpc { code: METHOD_NOT_FOUND, .. }) => {
+                return Err(ZecDevError::WalletUnsupported(method));
+            }
+            Err(e) => return Err(e),
+        };
+        
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        
+        Ok(Self { address, kind, chain, created_at })
+    }
+    
+    /// Where the wallet for compose project `project` is stored
+    pub fn path(project: &str) -> Result<PathBuf> {
+        Ok(project_state_dir(project)?.join("wallet.json"))
+    }
+    
+    /// The project's saved wallet, if one has been created
+    pub fn load(project: &str) -> Result<Option<Self>> {
+        let path = Self::path(project)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        
+        let contents = std::fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&contents)?))
+    }
+    
+    /// Save as the project's wallet, replacing any earlier one
+    pub fn save(&self, project: &str) -> Result<PathBuf> {
+        let path = Self::path(project)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn wallet_file_round_trips() {
+        let json = r#"{"address":"tmWallet","kind":"transparent","chain":"regtest","created_at":1700000000}"#;
+        let wallet: Wallet = serde_json::from_str(json).unwrap();
+        
+        assert_eq!(wallet.kind, AddressKind::Transparent);
+        assert_eq!(serde_json::to_string(&wallet).unwrap(), json);
+    }
+}
@@ -24,6 +24,8 @@ class BaseConfig:
     FAUCET_AMOUNT_MAX = float(os.environ.get('FAUCET_AMOUNT_MAX', '100.0'))
     FAUCET_AMOUNT_DEFAULT = float(os.environ.get('FAUCET_AMOUNT_DEFAULT', '10.0'))
     FAUCET_LOW_BALANCE_THRESHOLD = float(os.environ.get('FAUCET_LOW_BALANCE_THRESHOLD', '100.0'))
+    # Seconds an address must wait between payouts (0 disables the cooldown)
+    FAUCET_COOLDOWN_SECS = int(os.environ.get('FAUCET_COOLDOWN_SECS', '0'))
     
     # Rate Limiting (requests per window)
     RATE_LIMIT_ENABLED = os.environ.get('RATE_LIMIT_ENABLED', 'true').lower() == 'true'
@@ -60,6 +62,9 @@ class BaseConfig:
            cls.FAUCET_AMOUNT_DEFAULT > cls.FAUCET_AMOUNT_MAX:
             raise ValueError("FAUCET_AMOUNT_DEFAULT must be between MIN and MAX")
         
+        if cls.FAUCET_COOLDOWN_SECS < 0:
+            raise ValueError("FAUCET_COOLDOWN_SECS must not be negative")
+        
         return True
 
 
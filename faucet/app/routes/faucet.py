@@ -6,6 +6,7 @@ from flask import Blueprint, jsonify, request, current_app
 from datetime import datetime
 import logging
 import re
+import time
 
 from ..zebra_rpc import ZebraRPCError
 
@@ -13,6 +14,19 @@ logger = logging.getLogger(__name__)
 
 faucet_bp = Blueprint('faucet', __name__)
 
+# Last payout time (monotonic seconds) per address, for FAUCET_COOLDOWN_SECS
+_last_payout: dict[str, float] = {}
+
+
+def cooldown_remaining(address: str, cooldown: int, now: float) -> int:
+    """
+    Seconds until address may be funded again (0 if it may be funded now)
+    """
+    last = _last_payout.get(address)
+    if not cooldown or last is None:
+        return 0
+    return max(0, int(last + cooldown - now + 0.999))
+
 
 def validate_address(address: str) -> tuple[bool, str]:
     """
@@ -84,6 +98,17 @@ def request_funds():
             "code": "INVALID_ADDRESS"
         }), 400
     
+    # Per-address cooldown
+    retry_after = cooldown_remaining(
+        to_address, current_app.config['FAUCET_COOLDOWN_SECS'], time.monotonic()
+    )
+    if retry_after:
+        return jsonify({
+            "error": f"Address was funded recently; try again in {retry_after}s",
+            "code": "RATE_LIMITED",
+            "retry_after": retry_after
+        }), 429
+    
     # Get amount (with validation)
     try:
         amount = float(data.get('amount', current_app.config['FAUCET_AMOUNT_DEFAULT']))
@@ -135,6 +160,8 @@ def request_funds():
                 "code": "TRANSACTION_FAILED"
             }), 500
         
+        _last_payout[to_address] = time.monotonic()
+        
         # Success response
         response = {
             "txid": txid,
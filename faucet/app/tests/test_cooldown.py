@@ -0,0 +1,19 @@
+"""
+ZecKit Faucet - Cooldown Tests
+Unit tests for the per-address payout cooldown
+"""
+from app.routes import faucet
+
+
+def test_cooldown_disabled_by_default():
+    """A zero cooldown never blocks an address"""
+    faucet._last_payout['tmAlice'] = 100.0
+    assert faucet.cooldown_remaining('tmAlice', 0, 100.0) == 0
+
+
+def test_cooldown_blocks_recently_funded_address():
+    """Only the funded address waits, rounded up to whole seconds"""
+    faucet._last_payout['tmAlice'] = 100.0
+    assert faucet.cooldown_remaining('tmAlice', 60, 130.5) == 30
+    assert faucet.cooldown_remaining('tmAlice', 60, 160.0) == 0
+    assert faucet.cooldown_remaining('tmBob', 60, 130.5) == 0